@@ -0,0 +1,119 @@
+//! # Input Debounce
+//!
+//! Every keystroke parses the inputs and solves the scene again, which is noticeable on a
+//! divider with many legs. With a debounce interval set, a keystroke only stores the entered
+//! text; the scene is parsed and solved once no key was pressed for the interval. While a
+//! keystroke is pending the application subscribes to a timer of its generation, a later
+//! keystroke restarts it and only the timer of the last one recalculates, so the final text
+//! is never left unparsed.
+//!
+//! On by default with an interval of [`DEFAULT_INTERVAL_MS`]. Like the interface language,
+//! the interval is kept per thread.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+/// Longest interval the settings accept, in milliseconds.
+pub const MAX_INTERVAL_MS: u64 = 1000;
+
+/// Interval a new thread starts with, in milliseconds.
+pub const DEFAULT_INTERVAL_MS: u64 = 150;
+
+thread_local! {
+    static INTERVAL_MS: Cell<u64> = const { Cell::new(DEFAULT_INTERVAL_MS) };
+}
+
+/// Sets the debounce interval for the current thread, `0` turns the debounce off.
+pub fn set_interval_ms(ms: u64) {
+    INTERVAL_MS.with(|i| i.set(ms.min(MAX_INTERVAL_MS)));
+}
+
+/// Returns the debounce interval of the current thread in milliseconds.
+pub fn interval_ms() -> u64 {
+    INTERVAL_MS.with(|i| i.get())
+}
+
+pub fn interval() -> Duration {
+    Duration::from_millis(interval_ms())
+}
+
+pub fn is_on() -> bool {
+    interval_ms() > 0
+}
+
+/// Keystrokes waiting for the recalculation. Every keystroke gets the next generation, its
+/// timer brings the generation back.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Debounce {
+    generation: u64,
+    pending: bool,
+}
+
+impl Debounce {
+    /// Registers a keystroke and returns the generation for its timer.
+    pub fn keystroke(&mut self) -> u64 {
+        self.generation += 1;
+        self.pending = true;
+
+        self.generation
+    }
+
+    /// Generation of the last keystroke while it waits for the recalculation.
+    pub fn pending(&self) -> Option<u64> {
+        self.pending.then_some(self.generation)
+    }
+
+    /// `true` if the timer of `generation` has to recalculate: it belongs to the last
+    /// keystroke and nothing recalculated since. A later keystroke has its own timer.
+    pub fn is_due(&mut self, generation: u64) -> bool {
+        let due = self.pending && generation == self.generation;
+        if due {
+            self.pending = false;
+        }
+
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_keystroke_is_due() {
+        let mut debounce = Debounce::default();
+
+        let first = debounce.keystroke();
+        let second = debounce.keystroke();
+        let last = debounce.keystroke();
+        assert_eq!(debounce.pending(), Some(last));
+
+        // таймеры ранних нажатий ничего не делают
+        assert!(!debounce.is_due(first));
+        assert!(!debounce.is_due(second));
+        assert!(debounce.is_due(last));
+        // the same timer does not recalculate twice
+        assert_eq!(debounce.pending(), None);
+        assert!(!debounce.is_due(last));
+
+        // a keystroke after the timer fired starts over
+        let next = debounce.keystroke();
+        assert!(!debounce.is_due(last));
+        assert!(debounce.is_due(next));
+    }
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(interval(), Duration::from_millis(150));
+
+        set_interval_ms(50);
+        assert!(is_on());
+        assert_eq!(interval(), Duration::from_millis(50));
+
+        set_interval_ms(5000);
+        assert_eq!(interval_ms(), MAX_INTERVAL_MS);
+
+        set_interval_ms(0);
+        assert!(!is_on());
+    }
+}
@@ -0,0 +1,171 @@
+//! # Standard Resistor Values
+//!
+//! IEC 60063 preferred number series. Every series lists the values of one decade, the
+//! nearest standard value is looked up in the decade of the requested value:
+//!
+//! ```rust
+//! # use ecw::eseries::ESeries;
+//! assert_eq!(ESeries::E24.nearest(7000.0), Some(6800.0));
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+
+#[rustfmt::skip]
+const E12: [f64; 12] = [
+    1.0, 1.2, 1.5, 1.8, 2.2, 2.7, 3.3, 3.9, 4.7, 5.6, 6.8, 8.2,
+];
+
+#[rustfmt::skip]
+const E24: [f64; 24] = [
+    1.0, 1.1, 1.2, 1.3, 1.5, 1.6, 1.8, 2.0, 2.2, 2.4, 2.7, 3.0,
+    3.3, 3.6, 3.9, 4.3, 4.7, 5.1, 5.6, 6.2, 6.8, 7.5, 8.2, 9.1,
+];
+
+// каждое второе значение E96
+#[rustfmt::skip]
+const E48: [f64; 48] = [
+    1.00, 1.05, 1.10, 1.15, 1.21, 1.27, 1.33, 1.40, 1.47, 1.54, 1.62, 1.69,
+    1.78, 1.87, 1.96, 2.05, 2.15, 2.26, 2.37, 2.49, 2.61, 2.74, 2.87, 3.01,
+    3.16, 3.32, 3.48, 3.65, 3.83, 4.02, 4.22, 4.42, 4.64, 4.87, 5.11, 5.36,
+    5.62, 5.90, 6.19, 6.49, 6.81, 7.15, 7.50, 7.87, 8.25, 8.66, 9.09, 9.53,
+];
+
+#[rustfmt::skip]
+const E96: [f64; 96] = [
+    1.00, 1.02, 1.05, 1.07, 1.10, 1.13, 1.15, 1.18, 1.21, 1.24, 1.27, 1.30,
+    1.33, 1.37, 1.40, 1.43, 1.47, 1.50, 1.54, 1.58, 1.62, 1.65, 1.69, 1.74,
+    1.78, 1.82, 1.87, 1.91, 1.96, 2.00, 2.05, 2.10, 2.15, 2.21, 2.26, 2.32,
+    2.37, 2.43, 2.49, 2.55, 2.61, 2.67, 2.74, 2.80, 2.87, 2.94, 3.01, 3.09,
+    3.16, 3.24, 3.32, 3.40, 3.48, 3.57, 3.65, 3.74, 3.83, 3.92, 4.02, 4.12,
+    4.22, 4.32, 4.42, 4.53, 4.64, 4.75, 4.87, 4.99, 5.11, 5.23, 5.36, 5.49,
+    5.62, 5.76, 5.90, 6.04, 6.19, 6.34, 6.49, 6.65, 6.81, 6.98, 7.15, 7.32,
+    7.50, 7.68, 7.87, 8.06, 8.25, 8.45, 8.66, 8.87, 9.09, 9.31, 9.53, 9.76,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum ESeries {
+    E12,
+    #[default]
+    E24,
+    E48,
+    E96,
+}
+
+impl ESeries {
+    pub const ALL: [ESeries; 4] = [ESeries::E12, ESeries::E24, ESeries::E48, ESeries::E96];
+    /// Series a calculated resistance is checked against.
+    pub const PRECISE: [ESeries; 3] = [ESeries::E24, ESeries::E48, ESeries::E96];
+
+    /// Values of one decade, from 1.0 to below 10.
+    pub fn values(&self) -> &'static [f64] {
+        match self {
+            ESeries::E12 => &E12,
+            ESeries::E24 => &E24,
+            ESeries::E48 => &E48,
+            ESeries::E96 => &E96,
+        }
+    }
+
+    /// Usual tolerance of the series, in percent.
+    pub fn tolerance(&self) -> f64 {
+        match self {
+            ESeries::E12 => 10.0,
+            ESeries::E24 => 5.0,
+            ESeries::E48 => 2.0,
+            ESeries::E96 => 1.0,
+        }
+    }
+
+    /// Nearest standard value, closeness is measured as a ratio since the series are
+    /// geometric. `None` for zero, negative or non-finite values.
+    pub fn nearest(&self, value: f64) -> Option<f64> {
+        if !value.is_finite() || value <= 0.0 {
+            return None;
+        }
+
+        let decade = 10f64.powf(value.log10().floor());
+        let mantissa = value / decade;
+
+        // первое значение следующей декады тоже кандидат: 9.6 ближе к 10, чем к 9.1
+        let nearest = self
+            .values()
+            .iter()
+            .copied()
+            .chain(std::iter::once(10.0))
+            .min_by(|a, b| {
+                let da = (mantissa / a).ln().abs();
+                let db = (mantissa / b).ln().abs();
+                da.total_cmp(&db)
+            })?;
+
+        // округление убирает хвосты вида 6799.999999999999
+        Some(round_significant(nearest * decade))
+    }
+
+    /// Nearest standard value of an ideal one and the error it brings in, in percent:
+    /// `(standard − ideal) / ideal × 100`.
+    pub fn nearest_error(&self, ideal: f64) -> Option<(f64, f64)> {
+        let standard = self.nearest(ideal)?;
+
+        Some((standard, (standard - ideal) / ideal * 100.0))
+    }
+}
+
+/// Rounds to 3 significant digits, enough for every series.
+fn round_significant(value: f64) -> f64 {
+    let digits = 2 - value.log10().floor() as i32;
+
+    // степени десяти точно представимы, поэтому делим и умножаем только на них
+    if digits >= 0 {
+        let scale = 10f64.powi(digits);
+        (value * scale).round() / scale
+    } else {
+        let scale = 10f64.powi(-digits);
+        (value / scale).round() * scale
+    }
+}
+
+impl fmt::Display for ESeries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ESeries::E12 => write!(f, "E12"),
+            ESeries::E24 => write!(f, "E24"),
+            ESeries::E48 => write!(f, "E48"),
+            ESeries::E96 => write!(f, "E96"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest() {
+        assert_eq!(ESeries::E24.nearest(7000.0), Some(6800.0));
+        assert_eq!(ESeries::E24.nearest(3000.0), Some(3000.0));
+        assert_eq!(ESeries::E24.nearest(9.6), Some(10.0));
+        assert_eq!(ESeries::E12.nearest(0.0042), Some(0.0039));
+        assert_eq!(ESeries::E96.nearest(7000.0), Some(6980.0));
+        assert_eq!(ESeries::E24.nearest(0.0), None);
+        assert_eq!(ESeries::E24.nearest(-100.0), None);
+        assert_eq!(ESeries::E48.nearest(7000.0), Some(7150.0));
+    }
+
+    #[test]
+    fn test_nearest_error() {
+        assert_eq!(ESeries::E24.nearest_error(3300.0), Some((3300.0, 0.0)));
+
+        let (standard, error) = ESeries::E24.nearest_error(3456.0).unwrap();
+        assert_eq!(standard, 3600.0);
+        assert!((error - 144.0 / 3456.0 * 100.0).abs() < 1e-12);
+
+        let (standard, error) = ESeries::E96.nearest_error(3456.0).unwrap();
+        assert_eq!(standard, 3480.0);
+        assert!(error > 0.0 && error < 1.0);
+
+        assert_eq!(ESeries::E48.nearest_error(0.0), None);
+    }
+}
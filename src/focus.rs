@@ -0,0 +1,95 @@
+//! # Input Focus
+//!
+//! Calculator behaviour of the input fields: a field that gets the focus by a click has its
+//! whole text selected, so typing replaces the value, and Enter moves the focus to the next
+//! field of the scene with its text selected as well. Which field is next is decided by the
+//! scene; this module only names the fields and produces the focus tasks.
+//!
+//! iced reports no focus change, so after every click the application asks which field is
+//! focused and selects the text only if it is not the field focused before.
+
+use std::borrow::Cow;
+
+use iced::advanced::widget::{operate, operation};
+use iced::Task;
+
+pub use iced::advanced::widget::Id;
+
+/// Focuses the field `name` and selects its text.
+pub fn focus<M: Send + 'static>(name: impl Into<Cow<'static, str>>) -> Task<M> {
+    let id = Id::new(name);
+
+    operate(operation::focusable::focus(id.clone()))
+        .chain(operate(operation::text_input::select_all(id)))
+}
+
+/// Asks which field is focused after a click: `on_focused` gets its id, `on_none` is sent if
+/// no field is focused.
+pub fn find_focused<M: Clone + Send + 'static>(on_focused: fn(Id) -> M, on_none: M) -> Task<M> {
+    // поиск ничего не возвращает, если фокуса нет, поэтому отдельно считаем поля
+    let none = operate(operation::focusable::count()).then(move |count| match count.focused {
+        Some(_) => Task::none(),
+        None => Task::done(on_none.clone()),
+    });
+
+    Task::batch([
+        operate(operation::focusable::find_focused()).map(on_focused),
+        none,
+    ])
+}
+
+/// The field that has the focus, as far as the application knows.
+#[derive(Debug, Clone, Default)]
+pub struct Focus {
+    field: Option<Id>,
+}
+
+impl Focus {
+    /// Records the field focused after a click. Returns the task selecting its text if the
+    /// field has just got the focus; a click inside the focused field moves the cursor only.
+    pub fn clicked<M: Send + 'static>(&mut self, field: Option<Id>) -> Task<M> {
+        if field == self.field {
+            return Task::none();
+        }
+        self.field = field.clone();
+
+        match field {
+            Some(id) => operate(operation::text_input::select_all(id)),
+            None => Task::none(),
+        }
+    }
+
+    /// Moves the focus to the field `name` and selects its text, e.g. on Enter.
+    pub fn advance<M: Send + 'static>(&mut self, name: impl Into<Cow<'static, str>>) -> Task<M> {
+        let name = name.into();
+        self.field = Some(Id::new(name.clone()));
+
+        focus(name)
+    }
+
+    /// `true` if the field `name` has the focus.
+    pub fn is_focused(&self, name: &'static str) -> bool {
+        self.field == Some(Id::new(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_tracking() {
+        let mut focus = Focus::default();
+        assert!(!focus.is_focused("voltage"));
+
+        let _: Task<()> = focus.clicked(Some(Id::new("voltage")));
+        assert!(focus.is_focused("voltage"));
+
+        let _: Task<()> = focus.advance("current");
+        assert!(focus.is_focused("current"));
+        assert!(!focus.is_focused("voltage"));
+
+        let _: Task<()> = focus.clicked(None);
+        assert!(!focus.is_focused("current"));
+    }
+}
@@ -1,6 +1,7 @@
 use iced::widget::{markdown, Scrollable};
 use iced::{Element, Theme};
 
+use crate::i18n::{t, Key};
 use crate::ohm_law;
 use crate::voltage_divider;
 
@@ -14,6 +15,12 @@ pub enum Message {
     LinkClicked(()),
 }
 
+impl Default for Help {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Help {
     pub fn new() -> Self {
         let help1 = ohm_law::help();
@@ -27,19 +34,20 @@ impl Help {
         t.push_str(&help2.1);
 
         Self {
-            markdown: markdown::parse(&t).collect(),
+            markdown: markdown::parse(&tables_to_code(&t)).collect(),
         }
     }
 
     pub fn title(&self) -> String {
-        String::from("Help")
+        String::from(t(Key::Help))
     }
 
-    pub fn view(&self) -> Element<Message> {
+    /// The help text in the colors of the active `theme`.
+    pub fn view(&self, theme: &Theme) -> Element<'_, Message> {
         let t = markdown::view(
             &self.markdown,
             markdown::Settings::default(),
-            markdown::Style::from_palette(Theme::TokyoNightStorm.palette()),
+            markdown::Style::from_palette(theme.palette()),
         )
         .map(|_v| Message::LinkClicked(()));
 
@@ -48,3 +56,122 @@ impl Help {
 
     pub fn update(&mut self, _message: Message) {}
 }
+
+/// The markdown widget skips tables, so every table of the help text is turned into a code
+/// block with aligned columns. Formulas stay readable in the monospace font.
+fn tables_to_code(text: &str) -> String {
+    let mut result = String::new();
+    let mut table: Vec<Vec<String>> = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('|') {
+            let cells: Vec<String> = trimmed
+                .trim_matches('|')
+                .split('|')
+                .map(|cell| cell.trim().replace("**", ""))
+                .collect();
+            // строка-разделитель под заголовком
+            if !cells
+                .iter()
+                .all(|cell| !cell.is_empty() && cell.chars().all(|c| matches!(c, '-' | ':')))
+            {
+                table.push(cells);
+            }
+            continue;
+        }
+
+        if !table.is_empty() {
+            result.push_str(&format_table(&table));
+            table.clear();
+        }
+
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    if table.is_empty() {
+        result.pop();
+    } else {
+        result.push_str(&format_table(&table));
+    }
+
+    result
+}
+
+fn format_table(table: &[Vec<String>]) -> String {
+    let columns = table.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            table
+                .iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |row: &[String]| {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = row.get(i).map(String::as_str).unwrap_or("");
+                format!("{}{}", cell, " ".repeat(width - cell.chars().count()))
+            })
+            .collect();
+        format!("{}\n", cells.join("  ").trim_end())
+    };
+
+    let mut result = String::from("```\n");
+    if let Some((header, rows)) = table.split_first() {
+        result.push_str(&format_row(header));
+        let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+        result.push_str(&format!("{}\n", rule.join("  ")));
+        for row in rows {
+            result.push_str(&format_row(row));
+        }
+    }
+    result.push_str("```\n");
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_to_code() {
+        let text = "Text\n| A | **B** |\n|---|:--|\n| U | I × R |\n| R | U / I |\nMore text";
+
+        assert_eq!(
+            tables_to_code(text),
+            "Text\n```\nA  B\n─  ─────\nU  I × R\nR  U / I\n```\nMore text"
+        );
+        assert_eq!(tables_to_code("No tables"), "No tables");
+    }
+
+    #[test]
+    fn test_help_markdown() {
+        let help = Help::new();
+        let code_blocks = help
+            .markdown
+            .iter()
+            .filter(|item| matches!(item, markdown::Item::CodeBlock(_)))
+            .count();
+
+        // таблицы формул обеих сцен
+        assert_eq!(code_blocks, 2);
+        assert!(help
+            .markdown
+            .iter()
+            .any(|item| matches!(item, markdown::Item::Heading(..))));
+        assert!(help
+            .markdown
+            .iter()
+            .any(|item| matches!(item, markdown::Item::List { .. })));
+    }
+}
@@ -0,0 +1,714 @@
+//! # Interface Translations
+//!
+//! All user-visible labels, hints and headers of the scenes are looked up here by [`Key`]
+//! instead of being written as literals in the view code.
+//!
+//! The active language is kept per thread (the GUI runs on a single thread), so views only
+//! need to call [`t`]:
+//!
+//! ```rust
+//! # use ecw::i18n::{set_lang, t, Key, Lang};
+//! set_lang(Lang::Russian);
+//! assert_eq!(t(Key::Voltage), "Напряжение");
+//! ```
+
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    static LANG: Cell<Lang> = const { Cell::new(Lang::English) };
+}
+
+/// Supported interface languages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    English,
+    Russian,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::English, Lang::Russian];
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lang::English => write!(f, "English"),
+            Lang::Russian => write!(f, "Русский"),
+        }
+    }
+}
+
+/// Translatable strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    AppTitle,
+    OhmLaw,
+    VoltageDivider,
+    Help,
+    // Quantities
+    Voltage,
+    Current,
+    Resistance,
+    Power,
+    // Result table rows
+    ValueNom,
+    ValueMax,
+    ValueMin,
+    TolPlus,
+    TolMinus,
+    TolPlusPercent,
+    TolMinusPercent,
+    // Ohm law
+    ExampleVoltage,
+    ExampleCurrent,
+    ExampleResistance,
+    ExamplePower,
+    NominalExplanation,
+    At,
+    AtNominal,
+    And,
+    WithoutTolerance,
+    MeasurementMode,
+    FreezeResults,
+    LoadExample,
+    Template,
+    TemplateHalfSupply,
+    TemplateHalfSupplyHint,
+    TemplateLadder,
+    TemplateLadderHint,
+    TemplateFeedback,
+    TemplateFeedbackHint,
+    DefaultLegs,
+    InputTruncated,
+    MaxInputLength,
+    MaxInputLengthHint,
+    IncorrectInputLength,
+    ResultTables,
+    MonospaceValues,
+    MonospaceValuesHint,
+    DefaultLegsHint,
+    IncorrectLegCount,
+    ResultsFrozen,
+    ShowWork,
+    Schematic,
+    PowerColorMap,
+    RatedPower,
+    SweepMode,
+    ExampleSweep,
+    SweepExpectedStep,
+    SweepStepNotPositive,
+    SweepExpectedNumber,
+    SweepNeedsOneInput,
+    SweepTruncated,
+    SupplySweep,
+    ExampleSupplySweep,
+    SupplySweepUnsolved,
+    StandardValues,
+    TransposeTable,
+    TableRows,
+    Inputs,
+    Calculating,
+    CalcTypeNone,
+    CalcTypeOverdetermined,
+    NearestValue,
+    ErrorPercent,
+    CopyCsv,
+    InputsConsistent,
+    InputsInconsistent,
+    Spread,
+    // Voltage divider
+    ExampleLeg,
+    ResistanceFieldError,
+    VoltageFieldError,
+    BothFieldsEmpty,
+    ResistanceFieldEmpty,
+    VoltageFieldEmpty,
+    AllFieldsCorrect,
+    AddLeg,
+    LegsLimit,
+    CurrentMaxHint,
+    CurrentMaxFieldError,
+    DividerCurrent,
+    ExceedsBudget,
+    ScaleResistors,
+    ReverseCurrent,
+    InconsistentLegs,
+    NegativeResistance,
+    RatioMode,
+    Ratio,
+    TotalResistance,
+    ExampleRatio,
+    ExampleTotalResistance,
+    RatioFieldError,
+    TotalResistanceFieldError,
+    RatioOutOfRange,
+    Calculated,
+    Standard,
+    GeneratorMode,
+    TopVoltage,
+    BottomVoltage,
+    Taps,
+    ExampleTopVoltage,
+    ExampleBottomVoltage,
+    ExampleTaps,
+    EmptyTap,
+    RailsOrder,
+    NoTaps,
+    TapsNotMonotonic,
+    TapOutsideRails,
+    TotalNotPositive,
+    CreateLegs,
+    // Exit confirmation
+    UnsavedChanges,
+    Discard,
+    ExportAndExit,
+    Cancel,
+    CopyReport,
+    // Pinned result
+    PinResult,
+    ExportJson,
+    CopyErrorReport,
+    Unpin,
+    Pinned,
+    Delta,
+    // Tolerance mode
+    ToleranceLimits,
+    ToleranceStatistical,
+    ToleranceInterval,
+    AssumedTolerance,
+    // Theme
+    ThemeAuto,
+    ThemeLight,
+    ThemeDark,
+    // Settings
+    Settings,
+    PlausibilityLimits,
+    PlausibilityLimitsHint,
+    MaxVoltage,
+    MaxCurrent,
+    MaxPower,
+    MinResistance,
+    MaxCurrentDensity,
+    LimitOffHint,
+    DefaultTolerances,
+    DefaultTolerancesHint,
+    DefaultToleranceOffHint,
+    IncorrectPercent,
+    InputDebounce,
+    InputDebounceHint,
+    DebounceInterval,
+    DebounceOffHint,
+    IncorrectInterval,
+    SafetyMargins,
+    SafetyMarginsHint,
+    Derating,
+    Headroom,
+    MarginOffHint,
+    IncorrectFactor,
+    SelfCheck,
+    SelfCheckHint,
+    SelfCheckAtStartup,
+    RunSelfCheck,
+    SelfCheckPassed,
+    SelfCheckFailed,
+    AboveLimit,
+    Unit,
+    UnitMismatch,
+    BelowLimit,
+    MinusToleranceAbove100,
+    ClampedAtZero,
+    // Auxiliary outputs
+    Area,
+    ExampleArea,
+    PrefixPico,
+    PrefixNano,
+    PrefixMicro,
+    PrefixMilli,
+    PrefixKilo,
+    PrefixMega,
+    PrefixGiga,
+    PrefixTera,
+    AreaNotPositive,
+    Conductance,
+    CurrentDensity,
+    PowerRating,
+    SupplyCurrent,
+    // Launch arguments
+    UnknownArgument,
+    MissingArgumentValue,
+    UnknownScene,
+    FieldsOfOtherScene,
+    ExpectedOnOff,
+    FieldComputed,
+    // Presets
+    Presets,
+    PresetsHint,
+    PresetName,
+    PresetValue,
+    AddPreset,
+    PresetNameEmpty,
+    PresetValueEmpty,
+    ExportPresets,
+    ImportPresets,
+    ReplaceOnImport,
+    PresetsAdded,
+    PresetsReplaced,
+    PresetsKept,
+    ImportFailed,
+    ClipboardEmpty,
+}
+
+/// Sets the interface language for the current thread.
+pub fn set_lang(lang: Lang) {
+    LANG.with(|l| l.set(lang));
+}
+
+/// Returns the interface language of the current thread.
+pub fn lang() -> Lang {
+    LANG.with(|l| l.get())
+}
+
+/// Looks up the translation of `key` in the active language.
+pub fn t(key: Key) -> &'static str {
+    match lang() {
+        Lang::English => english(key),
+        Lang::Russian => russian(key),
+    }
+}
+
+fn english(key: Key) -> &'static str {
+    match key {
+        Key::AppTitle => "Electrical Calculation Wizard",
+        Key::OhmLaw => "Ohm Law",
+        Key::VoltageDivider => "Voltage Divider",
+        Key::Help => "Help",
+        Key::Voltage => "Voltage",
+        Key::Current => "Current",
+        Key::Resistance => "Resistance",
+        Key::Power => "Power",
+        Key::ValueNom => "Value nom",
+        Key::ValueMax => "Value max",
+        Key::ValueMin => "Value min",
+        Key::TolPlus => "Tol plus",
+        Key::TolMinus => "Tol minus",
+        Key::TolPlusPercent => "Tol plus, %",
+        Key::TolMinusPercent => "Tol minus, %",
+        Key::ExampleVoltage => "Example: 10.5 +3% -7.6%",
+        Key::ExampleCurrent => "Example: 100m +1% -1%",
+        Key::ExampleResistance => "Example: 10k 5%",
+        Key::ExamplePower => "Example: 1k 5%",
+        Key::NominalExplanation => "Nominal values: every input is taken at its nominal value.",
+        Key::At => "at",
+        Key::AtNominal => "at nominal",
+        Key::And => "and",
+        Key::WithoutTolerance => "calculated without tolerance",
+        Key::MeasurementMode => "Measurement mode: keep all fields editable to check 3 or 4 known values",
+        Key::FreezeResults => "Freeze results while entering values",
+        Key::LoadExample => "Load example",
+        Key::Template => "Template",
+        Key::TemplateHalfSupply => "Half-supply bias",
+        Key::TemplateHalfSupplyHint => "two equal resistors bias a node at half of a 12V supply, e.g. the input of a single-supply op amp",
+        Key::TemplateLadder => "3-output ladder",
+        Key::TemplateLadderHint => "four equal resistors on 12V give the taps 9V, 6V and 3V",
+        Key::TemplateFeedback => "Feedback divider for 3.3V regulator",
+        Key::TemplateFeedbackHint => "the top resistor is solved so that 3.3V out gives the 0.8V reference of the regulator over a 10k bottom resistor",
+        Key::DefaultLegs => "Legs",
+        Key::InputTruncated => "Text over the input limit was cut, characters:",
+        Key::MaxInputLength => "Max length",
+        Key::MaxInputLengthHint => "Longer pasted text is cut, 256 characters if empty",
+        Key::IncorrectInputLength => "Expected a whole number of characters above zero",
+        Key::ResultTables => "Result tables",
+        Key::MonospaceValues => "Monospace values",
+        Key::MonospaceValuesHint => "Values are shown in a monospaced font, so the digits of a column line up; row labels keep the default font.",
+        Key::DefaultLegsHint => "Legs of a new divider, 2 if empty",
+        Key::IncorrectLegCount => "Expected a whole number from 1 to 64",
+        Key::ResultsFrozen => "Results are frozen: unfreeze to calculate",
+        Key::ShowWork => "Show work: formulas with the substituted values",
+        Key::Schematic => "Schematic",
+        Key::PowerColorMap => "Power color map",
+        Key::RatedPower => "Rated power",
+        Key::SweepMode => "Sweep: tabulate the results over a range of one input",
+        Key::ExampleSweep => "Range or list. Example: 1..10 step 1 or 1, 2.2, 4.7",
+        Key::SweepExpectedStep => "expected a step after the range, e.g. 1..10 step 1",
+        Key::SweepStepNotPositive => "the step must be above zero",
+        Key::SweepExpectedNumber => "expected a single number, e.g. 4.7k",
+        Key::SweepNeedsOneInput => "Enter one more value besides the swept one",
+        Key::SweepTruncated => "Too many points, the table is cut at",
+        Key::SupplySweep => "Sweep the supply voltage U1",
+        Key::ExampleSupplySweep => "Range or list of U1. Example: 4.5..5.5 step 0.5",
+        Key::SupplySweepUnsolved => "The divider must solve with at least two legs at every point",
+        Key::StandardValues => "Standard values",
+        Key::TransposeTable => "Quantities as rows",
+        Key::TableRows => "Rows",
+        Key::Inputs => "Inputs",
+        Key::Calculating => "calculating",
+        Key::CalcTypeNone => "Fill in any two fields to calculate the other two",
+        Key::CalcTypeOverdetermined => "More than two inputs: every pair is calculated and compared",
+        Key::NearestValue => "Nearest",
+        Key::ErrorPercent => "Error, %",
+        Key::CopyCsv => "Copy CSV",
+        Key::InputsConsistent => "The entered values are consistent within their tolerances.",
+        Key::InputsInconsistent => "Warning: the entered values disagree by more than their combined tolerances.",
+        Key::Spread => "spread",
+        Key::ExampleLeg => "Example: 1k 5%",
+        Key::ResistanceFieldError => "Resistance field error",
+        Key::VoltageFieldError => "Voltage field error",
+        Key::BothFieldsEmpty => "Both resistance and voltage fields are empty.",
+        Key::ResistanceFieldEmpty => "Resistance field is empty.",
+        Key::VoltageFieldEmpty => "Voltage field is empty.",
+        Key::AllFieldsCorrect => "All fields are correct.",
+        Key::AddLeg => "Add leg",
+        Key::LegsLimit => "The maximum number of legs is reached",
+        Key::CurrentMaxHint => "Optional divider current budget. Example: 1m",
+        Key::CurrentMaxFieldError => "Max current field error",
+        Key::DividerCurrent => "Divider current",
+        Key::ExceedsBudget => "exceeds the budget",
+        Key::ScaleResistors => "Scale resistors",
+        Key::ReverseCurrent => "The series current is negative: it flows in reverse direction, from the bottom leg to the top one.",
+        Key::InconsistentLegs => "The entered resistance and voltage disagree with the series current beyond the tolerances in legs",
+        Key::NegativeResistance => "the tap voltages imply a negative resistance for",
+        Key::RatioMode => "Divider from ratio: get the two resistors for an output/input ratio",
+        Key::Ratio => "Ratio",
+        Key::TotalResistance => "R total",
+        Key::ExampleRatio => "Output to input voltage ratio. Example: 0.3",
+        Key::ExampleTotalResistance => "Total resistance of the divider. Example: 10k 1%",
+        Key::RatioFieldError => "Ratio field error",
+        Key::TotalResistanceFieldError => "Total resistance field error",
+        Key::RatioOutOfRange => "the ratio must be between 0 and 1",
+        Key::Calculated => "Calculated",
+        Key::Standard => "Standard",
+        Key::GeneratorMode => "Divider from taps: create the legs for a list of tap voltages",
+        Key::TopVoltage => "U top",
+        Key::BottomVoltage => "U bottom",
+        Key::Taps => "Taps",
+        Key::ExampleTopVoltage => "Voltage of the top rail. Example: 5",
+        Key::ExampleBottomVoltage => "Voltage of the bottom rail, ground if empty. Example: 0",
+        Key::ExampleTaps => "Comma-separated tap voltages. Example: 1, 2.5, 3.3",
+        Key::EmptyTap => "empty tap between commas",
+        Key::RailsOrder => "The top rail must be above the bottom one.",
+        Key::NoTaps => "Enter at least one tap voltage.",
+        Key::TapsNotMonotonic => "The taps must be strictly increasing or decreasing.",
+        Key::TapOutsideRails => "Every tap must lie between the rails.",
+        Key::TotalNotPositive => "The total resistance must be above zero.",
+        Key::CreateLegs => "Create legs",
+        Key::UnsavedChanges => "There are unsaved inputs. Discard them and exit?",
+        Key::Discard => "Discard",
+        Key::ExportAndExit => "Export JSON and exit",
+        Key::Cancel => "Cancel",
+        Key::CopyReport => "Copy diagnostic report",
+        Key::PinResult => "Pin result",
+        Key::ExportJson => "Export JSON",
+        Key::CopyErrorReport => "Copy error report",
+        Key::Unpin => "Unpin",
+        Key::Pinned => "Pinned nom",
+        Key::Delta => "Δ, %",
+        Key::ToleranceLimits => "Limits",
+        Key::ToleranceStatistical => "Statistical",
+        Key::ToleranceInterval => "Interval",
+        Key::AssumedTolerance => "assumed",
+        Key::ThemeAuto => "Theme: system",
+        Key::ThemeLight => "Light theme",
+        Key::ThemeDark => "Dark theme",
+        Key::Settings => "Settings",
+        Key::PlausibilityLimits => "Plausibility limits",
+        Key::PlausibilityLimitsHint => "Entries outside these limits are flagged with a warning under the field. The calculation is not blocked.",
+        Key::MaxVoltage => "Max voltage",
+        Key::MaxCurrent => "Max current",
+        Key::MaxPower => "Max power",
+        Key::MinResistance => "Min resistance",
+        Key::MaxCurrentDensity => "Max density, A/mm²",
+        Key::LimitOffHint => "Leave empty to turn the check off. Example: 1k",
+        Key::DefaultTolerances => "Default tolerances",
+        Key::DefaultTolerancesHint => "Applied to values entered without a tolerance. An explicit 0% is kept.",
+        Key::DefaultToleranceOffHint => "Leave empty for no default. Example: 1%",
+        Key::IncorrectPercent => "expected a percentage of 0 or more, e.g. 1%",
+        Key::InputDebounce => "Input debounce",
+        Key::InputDebounceHint => "Fields are parsed once typing pauses for the interval. Helps with large dividers.",
+        Key::DebounceInterval => "Interval, ms",
+        Key::DebounceOffHint => "Leave empty or 0 to parse every keystroke. Default: 150",
+        Key::IncorrectInterval => "expected whole milliseconds from 0 to 1000",
+        Key::SafetyMargins => "Safety margins",
+        Key::SafetyMarginsHint => "The power rating is the dissipated power times the derating, the supply current is the drawn current times the headroom.",
+        Key::Derating => "Power derating",
+        Key::Headroom => "Current headroom",
+        Key::MarginOffHint => "Leave empty for no margin. Example: 2 or 1.2x",
+        Key::IncorrectFactor => "expected a factor of 1 or more",
+        Key::SelfCheck => "Self-check",
+        Key::SelfCheckHint => "Parses reference values, formats them back and compares the text with the expected one. A mismatch points at the number formatting of the system.",
+        Key::SelfCheckAtStartup => "Run at startup of debug builds",
+        Key::RunSelfCheck => "Run now",
+        Key::SelfCheckPassed => "all reference values match",
+        Key::SelfCheckFailed => "self-check mismatch",
+        Key::AboveLimit => "above the plausibility limit",
+        Key::Unit => "unit",
+        Key::UnitMismatch => "doesn't match",
+        Key::BelowLimit => "below the plausibility limit",
+        Key::MinusToleranceAbove100 => "minus tolerance above 100%, the minimum changes sign",
+        Key::ClampedAtZero => "clamped",
+        Key::Area => "Area, mm²",
+        Key::ExampleArea => "Optional conductor cross-section for the current density. Example: 0.5",
+        Key::PrefixPico => "pico",
+        Key::PrefixNano => "nano",
+        Key::PrefixMicro => "micro",
+        Key::PrefixMilli => "milli",
+        Key::PrefixKilo => "kilo",
+        Key::PrefixMega => "mega",
+        Key::PrefixGiga => "giga",
+        Key::PrefixTera => "tera",
+        Key::AreaNotPositive => "the area must be above zero",
+        Key::Conductance => "Conductance",
+        Key::CurrentDensity => "Current density",
+        Key::PowerRating => "Power rating",
+        Key::SupplyCurrent => "Supply current",
+        Key::UnknownArgument => "unknown argument, skipped",
+        Key::MissingArgumentValue => "no value given, skipped",
+        Key::UnknownScene => "unknown scene, expected ohm, divider, settings, presets or help",
+        Key::FieldsOfOtherScene => "the fields are of the Ohm's law scene, skipped",
+        Key::ExpectedOnOff => "expected on or off, skipped",
+        Key::FieldComputed => "the field is computed from the others, skipped",
+        Key::Presets => "Presets",
+        Key::PresetsHint => "A preset is offered in the ★ list next to the fields of its kind. The same name replaces a preset; click a name to edit it.",
+        Key::PresetName => "Name",
+        Key::PresetValue => "Value, e.g. 5 5%",
+        Key::AddPreset => "Save",
+        Key::PresetNameEmpty => "the preset needs a name",
+        Key::PresetValueEmpty => "the preset needs a value",
+        Key::ExportPresets => "Copy as JSON",
+        Key::ImportPresets => "Import from clipboard",
+        Key::ReplaceOnImport => "Replace presets with the same name",
+        Key::PresetsAdded => "Added",
+        Key::PresetsReplaced => "replaced",
+        Key::PresetsKept => "kept existing",
+        Key::ImportFailed => "Import failed",
+        Key::ClipboardEmpty => "the clipboard holds no text",
+    }
+}
+
+fn russian(key: Key) -> &'static str {
+    match key {
+        Key::AppTitle => "Мастер электрических расчётов",
+        Key::OhmLaw => "Закон Ома",
+        Key::VoltageDivider => "Делитель напряжения",
+        Key::Help => "Справка",
+        Key::Voltage => "Напряжение",
+        Key::Current => "Ток",
+        Key::Resistance => "Сопротивление",
+        Key::Power => "Мощность",
+        Key::ValueNom => "Номинал",
+        Key::ValueMax => "Максимум",
+        Key::ValueMin => "Минимум",
+        Key::TolPlus => "Допуск +",
+        Key::TolMinus => "Допуск −",
+        Key::TolPlusPercent => "Допуск +, %",
+        Key::TolMinusPercent => "Допуск −, %",
+        Key::ExampleVoltage => "Пример: 10.5 +3% -7.6%",
+        Key::ExampleCurrent => "Пример: 100m +1% -1%",
+        Key::ExampleResistance => "Пример: 10k 5%",
+        Key::ExamplePower => "Пример: 1k 5%",
+        Key::NominalExplanation => "Номинальные значения: все входные величины взяты по номиналу.",
+        Key::At => "при",
+        Key::AtNominal => "по номиналу",
+        Key::And => "и",
+        Key::WithoutTolerance => "рассчитано без учёта допуска",
+        Key::MeasurementMode => "Режим измерений: все поля доступны для проверки 3 или 4 известных величин",
+        Key::FreezeResults => "Не пересчитывать, пока вводятся значения",
+        Key::LoadExample => "Загрузить пример",
+        Key::Template => "Шаблон",
+        Key::TemplateHalfSupply => "Смещение на половину питания",
+        Key::TemplateHalfSupplyHint => "два равных резистора задают половину питания 12V, например на входе ОУ с однополярным питанием",
+        Key::TemplateLadder => "Лестница на 3 выхода",
+        Key::TemplateLadderHint => "четыре равных резистора на 12V дают отводы 9V, 6V и 3V",
+        Key::TemplateFeedback => "Обратная связь стабилизатора 3.3V",
+        Key::TemplateFeedbackHint => "верхний резистор рассчитывается так, чтобы при 3.3V на выходе опорные 0.8V стабилизатора были на нижнем резисторе 10k",
+        Key::DefaultLegs => "Плечи",
+        Key::InputTruncated => "Текст длиннее предела ввода обрезан, символов:",
+        Key::MaxInputLength => "Длина ввода",
+        Key::MaxInputLengthHint => "Более длинный вставленный текст обрезается, 256 символов если пусто",
+        Key::IncorrectInputLength => "Ожидается целое число символов больше нуля",
+        Key::ResultTables => "Таблицы результатов",
+        Key::MonospaceValues => "Моноширинные значения",
+        Key::MonospaceValuesHint => "Значения выводятся моноширинным шрифтом, чтобы цифры в столбце стояли ровно; подписи строк остаются шрифтом по умолчанию.",
+        Key::DefaultLegsHint => "Плечей у нового делителя, 2 если пусто",
+        Key::IncorrectLegCount => "Ожидается целое число от 1 до 64",
+        Key::ResultsFrozen => "Расчёт приостановлен: снимите отметку, чтобы пересчитать",
+        Key::ShowWork => "Показать решение: формулы с подставленными значениями",
+        Key::Schematic => "Схема",
+        Key::PowerColorMap => "Цветовая карта мощности",
+        Key::RatedPower => "Номинальная мощность",
+        Key::SweepMode => "Развёртка: таблица результатов по диапазону одной величины",
+        Key::ExampleSweep => "Диапазон или список. Пример: 1..10 step 1 или 1, 2.2, 4.7",
+        Key::SweepExpectedStep => "после диапазона ожидается шаг, например 1..10 step 1",
+        Key::SweepStepNotPositive => "шаг должен быть больше нуля",
+        Key::SweepExpectedNumber => "ожидается одно число, например 4.7k",
+        Key::SweepNeedsOneInput => "Введите ещё одну величину, кроме развёртываемой",
+        Key::SweepTruncated => "Слишком много точек, таблица обрезана до",
+        Key::SupplySweep => "Развёртка напряжения питания U1",
+        Key::ExampleSupplySweep => "Диапазон или список U1. Пример: 4.5..5.5 step 0.5",
+        Key::SupplySweepUnsolved => "Делитель из двух и более плеч должен решаться в каждой точке",
+        Key::StandardValues => "Стандартные значения",
+        Key::TransposeTable => "Величины по строкам",
+        Key::TableRows => "Строки",
+        Key::Inputs => "Ввод",
+        Key::Calculating => "вычисляются",
+        Key::CalcTypeNone => "Заполните любые два поля, чтобы вычислить два других",
+        Key::CalcTypeOverdetermined => "Введено больше двух величин: расчёт по каждой паре и сравнение",
+        Key::NearestValue => "Ближайшее",
+        Key::ErrorPercent => "Ошибка, %",
+        Key::CopyCsv => "Копировать CSV",
+        Key::InputsConsistent => "Введённые значения согласуются в пределах допусков.",
+        Key::InputsInconsistent => "Внимание: введённые значения расходятся больше, чем допускают их допуски.",
+        Key::Spread => "разброс",
+        Key::ExampleLeg => "Пример: 1k 5%",
+        Key::ResistanceFieldError => "Ошибка в поле сопротивления",
+        Key::VoltageFieldError => "Ошибка в поле напряжения",
+        Key::BothFieldsEmpty => "Поля сопротивления и напряжения пусты.",
+        Key::ResistanceFieldEmpty => "Поле сопротивления пусто.",
+        Key::VoltageFieldEmpty => "Поле напряжения пусто.",
+        Key::AllFieldsCorrect => "Все поля заполнены корректно.",
+        Key::AddLeg => "Добавить плечо",
+        Key::LegsLimit => "Достигнуто максимальное число плеч",
+        Key::CurrentMaxHint => "Необязательное ограничение тока делителя. Пример: 1m",
+        Key::CurrentMaxFieldError => "Ошибка в поле максимального тока",
+        Key::DividerCurrent => "Ток делителя",
+        Key::ExceedsBudget => "превышает допустимый",
+        Key::ScaleResistors => "Масштабировать резисторы",
+        Key::ReverseCurrent => "Ток делителя отрицательный: он течёт в обратном направлении, от нижнего плеча к верхнему.",
+        Key::InconsistentLegs => "Введённые сопротивление и напряжение не согласуются с током делителя с учётом допусков в плечах",
+        Key::NegativeResistance => "напряжения отводов дают отрицательное сопротивление",
+        Key::RatioMode => "Делитель по коэффициенту: подобрать два резистора для отношения выход/вход",
+        Key::Ratio => "Коэфф.",
+        Key::TotalResistance => "R общ.",
+        Key::ExampleRatio => "Отношение выходного напряжения к входному. Пример: 0.3",
+        Key::ExampleTotalResistance => "Общее сопротивление делителя. Пример: 10k 1%",
+        Key::RatioFieldError => "Ошибка в поле коэффициента",
+        Key::TotalResistanceFieldError => "Ошибка в поле общего сопротивления",
+        Key::RatioOutOfRange => "коэффициент должен быть между 0 и 1",
+        Key::Calculated => "Расчётное",
+        Key::Standard => "Стандартное",
+        Key::GeneratorMode => "Делитель по отводам: создать плечи для списка напряжений отводов",
+        Key::TopVoltage => "U верх",
+        Key::BottomVoltage => "U низ",
+        Key::Taps => "Отводы",
+        Key::ExampleTopVoltage => "Напряжение верхней шины. Пример: 5",
+        Key::ExampleBottomVoltage => "Напряжение нижней шины, земля если пусто. Пример: 0",
+        Key::ExampleTaps => "Напряжения отводов через запятую. Пример: 1, 2.5, 3.3",
+        Key::EmptyTap => "пустой отвод между запятыми",
+        Key::RailsOrder => "Верхняя шина должна быть выше нижней.",
+        Key::NoTaps => "Введите хотя бы одно напряжение отвода.",
+        Key::TapsNotMonotonic => "Отводы должны строго возрастать или убывать.",
+        Key::TapOutsideRails => "Каждый отвод должен лежать между шинами.",
+        Key::TotalNotPositive => "Общее сопротивление должно быть больше нуля.",
+        Key::CreateLegs => "Создать плечи",
+        Key::UnsavedChanges => "Введённые данные не сохранены. Отбросить их и выйти?",
+        Key::Discard => "Отбросить",
+        Key::ExportAndExit => "Экспорт JSON и выход",
+        Key::Cancel => "Отмена",
+        Key::CopyReport => "Копировать отчёт",
+        Key::PinResult => "Закрепить результат",
+        Key::ExportJson => "Экспорт JSON",
+        Key::CopyErrorReport => "Копировать отчёт об ошибке",
+        Key::Unpin => "Открепить",
+        Key::Pinned => "Закреплено",
+        Key::Delta => "Δ, %",
+        Key::ToleranceLimits => "Предельный",
+        Key::ToleranceStatistical => "Статистич.",
+        Key::ToleranceInterval => "Интервальный",
+        Key::AssumedTolerance => "принято",
+        Key::ThemeAuto => "Тема: системная",
+        Key::ThemeLight => "Светлая тема",
+        Key::ThemeDark => "Тёмная тема",
+        Key::Settings => "Настройки",
+        Key::PlausibilityLimits => "Пределы правдоподобия",
+        Key::PlausibilityLimitsHint => "Значения за этими пределами отмечаются предупреждением под полем. Расчёт не блокируется.",
+        Key::MaxVoltage => "Макс. напряжение",
+        Key::MaxCurrent => "Макс. ток",
+        Key::MaxPower => "Макс. мощность",
+        Key::MinResistance => "Мин. сопротивление",
+        Key::MaxCurrentDensity => "Макс. плотн., А/мм²",
+        Key::LimitOffHint => "Оставьте пустым, чтобы отключить проверку. Пример: 1k",
+        Key::DefaultTolerances => "Допуски по умолчанию",
+        Key::DefaultTolerancesHint => "Применяются к значениям, введённым без допуска. Явно указанный 0% сохраняется.",
+        Key::DefaultToleranceOffHint => "Оставьте пустым, чтобы не задавать допуск. Пример: 1%",
+        Key::IncorrectPercent => "ожидается процент от 0 и больше, например 1%",
+        Key::InputDebounce => "Задержка разбора ввода",
+        Key::InputDebounceHint => "Поля разбираются, когда ввод приостановлен на заданное время. Помогает на больших делителях.",
+        Key::DebounceInterval => "Интервал, мс",
+        Key::DebounceOffHint => "Оставьте пустым или 0, чтобы разбирать каждое нажатие. По умолчанию: 150",
+        Key::IncorrectInterval => "ожидается целое число миллисекунд от 0 до 1000",
+        Key::SafetyMargins => "Запасы",
+        Key::SafetyMarginsHint => "Мощность резистора — рассеиваемая мощность, умноженная на запас по мощности; ток источника — потребляемый ток, умноженный на запас по току.",
+        Key::Derating => "Запас по мощн.",
+        Key::Headroom => "Запас по току",
+        Key::MarginOffHint => "Оставьте пустым, чтобы не брать запас. Пример: 2 или 1.2x",
+        Key::IncorrectFactor => "ожидается множитель не меньше 1",
+        Key::SelfCheck => "Самопроверка",
+        Key::SelfCheckHint => "Разбирает эталонные значения, форматирует их обратно и сравнивает текст с ожидаемым. Расхождение указывает на форматирование чисел в системе.",
+        Key::SelfCheckAtStartup => "Запускать при старте отладочной сборки",
+        Key::RunSelfCheck => "Запустить",
+        Key::SelfCheckPassed => "все эталонные значения совпадают",
+        Key::SelfCheckFailed => "расхождение самопроверки",
+        Key::AboveLimit => "выше предела правдоподобия",
+        Key::Unit => "единица",
+        Key::UnitMismatch => "не соответствует величине",
+        Key::BelowLimit => "ниже предела правдоподобия",
+        Key::MinusToleranceAbove100 => "минусовой допуск больше 100%, минимум меняет знак",
+        Key::ClampedAtZero => "ограничено",
+        Key::Area => "Сечение, мм²",
+        Key::ExampleArea => "Необязательное сечение проводника для плотности тока. Пример: 0.5",
+        Key::PrefixPico => "пико",
+        Key::PrefixNano => "нано",
+        Key::PrefixMicro => "микро",
+        Key::PrefixMilli => "милли",
+        Key::PrefixKilo => "кило",
+        Key::PrefixMega => "мега",
+        Key::PrefixGiga => "гига",
+        Key::PrefixTera => "тера",
+        Key::AreaNotPositive => "сечение должно быть больше нуля",
+        Key::Conductance => "Проводимость",
+        Key::CurrentDensity => "Плотность тока",
+        Key::PowerRating => "Мощность резистора",
+        Key::SupplyCurrent => "Ток источника",
+        Key::UnknownArgument => "неизвестный аргумент пропущен",
+        Key::MissingArgumentValue => "нет значения, аргумент пропущен",
+        Key::UnknownScene => "неизвестная сцена, ожидается ohm, divider, settings, presets или help",
+        Key::FieldsOfOtherScene => "поля относятся к сцене закона Ома и пропущены",
+        Key::ExpectedOnOff => "ожидается on или off, пропущено",
+        Key::FieldComputed => "поле вычисляется из других и пропущено",
+        Key::Presets => "Пресеты",
+        Key::PresetsHint => "Пресет предлагается в списке ★ у полей своего вида. Пресет с тем же именем заменяется; щелчок по имени открывает его для правки.",
+        Key::PresetName => "Имя",
+        Key::PresetValue => "Значение, напр. 5 5%",
+        Key::AddPreset => "Сохранить",
+        Key::PresetNameEmpty => "у пресета должно быть имя",
+        Key::PresetValueEmpty => "у пресета должно быть значение",
+        Key::ExportPresets => "Копировать как JSON",
+        Key::ImportPresets => "Импорт из буфера",
+        Key::ReplaceOnImport => "Заменять пресеты с тем же именем",
+        Key::PresetsAdded => "Добавлено",
+        Key::PresetsReplaced => "заменено",
+        Key::PresetsKept => "оставлено прежних",
+        Key::ImportFailed => "Ошибка импорта",
+        Key::ClipboardEmpty => "в буфере обмена нет текста",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_language() {
+        set_lang(Lang::English);
+        assert_eq!(t(Key::Voltage), "Voltage");
+
+        set_lang(Lang::Russian);
+        assert_eq!(lang(), Lang::Russian);
+        assert_eq!(t(Key::Voltage), "Напряжение");
+        assert_eq!(t(Key::AddLeg), "Добавить плечо");
+
+        set_lang(Lang::English);
+        assert_eq!(t(Key::AddLeg), "Add leg");
+    }
+}
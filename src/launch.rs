@@ -0,0 +1,161 @@
+//! # Launch Arguments
+//!
+//! The window can open right into a scene with its fields filled in:
+//!
+//! ```text
+//! ecw --scene ohm --voltage "12 5%" --current 100m
+//! ```
+//!
+//! `--self-check off` skips the startup self-check of debug builds, see [`crate::self_check`].
+//!
+//! Arguments that can't be used are skipped: the application starts as far as it can and
+//! shows a notice per skipped argument instead of refusing to start.
+
+use crate::i18n::{t, Key};
+use crate::ohm_law::FieldId;
+
+/// Scene to open the window with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StartScene {
+    #[default]
+    OhmLaw,
+    VoltageDivider,
+    Settings,
+    Presets,
+    Help,
+}
+
+impl StartScene {
+    fn from_arg(arg: &str) -> Option<StartScene> {
+        match arg {
+            "ohm" | "ohm_law" => Some(StartScene::OhmLaw),
+            "divider" | "voltage_divider" => Some(StartScene::VoltageDivider),
+            "settings" => Some(StartScene::Settings),
+            "presets" => Some(StartScene::Presets),
+            "help" => Some(StartScene::Help),
+            _ => None,
+        }
+    }
+}
+
+/// Initial state of the application parsed from the launch arguments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Launch {
+    pub scene: StartScene,
+    /// Ohm's law fields to fill in, in the order given.
+    pub ohm_law: Vec<(FieldId, String)>,
+    /// Whether to run the startup self-check, `None` leaves the setting as it is.
+    pub self_check: Option<bool>,
+    /// Arguments that were skipped and why.
+    pub notices: Vec<String>,
+}
+
+/// Parses the arguments after the program name. A field of a scene other than `--scene` is
+/// skipped with a notice, as is an unknown argument or one without a value.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Launch {
+    let mut launch = Launch::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let Some(name) = arg.strip_prefix("--") else {
+            launch.notice(&arg, t(Key::UnknownArgument));
+            continue;
+        };
+        let Some(value) = args.next() else {
+            launch.notice(&arg, t(Key::MissingArgumentValue));
+            break;
+        };
+
+        match name {
+            "scene" => match StartScene::from_arg(&value) {
+                Some(scene) => launch.scene = scene,
+                None => launch.notice(&arg, t(Key::UnknownScene)),
+            },
+            "voltage" => launch.ohm_law.push((FieldId::Voltage, value)),
+            "current" => launch.ohm_law.push((FieldId::Current, value)),
+            "resistance" => launch.ohm_law.push((FieldId::Resistance, value)),
+            "power" => launch.ohm_law.push((FieldId::Power, value)),
+            "area" => launch.ohm_law.push((FieldId::Area, value)),
+            "self-check" => match value.as_str() {
+                "on" => launch.self_check = Some(true),
+                "off" => launch.self_check = Some(false),
+                _ => launch.notice(&arg, t(Key::ExpectedOnOff)),
+            },
+            // файлов делителя пока нет
+            _ => launch.notice(&arg, t(Key::UnknownArgument)),
+        }
+    }
+
+    // поля закона Ома в другой сцене не видны
+    if launch.scene != StartScene::OhmLaw && !launch.ohm_law.is_empty() {
+        launch.ohm_law.clear();
+        launch.notice("--scene", t(Key::FieldsOfOtherScene));
+    }
+
+    launch
+}
+
+impl Launch {
+    fn notice(&mut self, arg: &str, message: &str) {
+        self.notices.push(format!("{}: {}", arg, message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(args(&[])), Launch::default());
+
+        let launch = parse_args(args(&[
+            "--scene",
+            "ohm",
+            "--voltage",
+            "12 5%",
+            "--current",
+            "100m",
+        ]));
+        assert_eq!(launch.scene, StartScene::OhmLaw);
+        assert_eq!(
+            launch.ohm_law,
+            vec![
+                (FieldId::Voltage, "12 5%".to_string()),
+                (FieldId::Current, "100m".to_string())
+            ]
+        );
+        assert!(launch.notices.is_empty());
+
+        let launch = parse_args(args(&["--scene", "divider", "--self-check", "off"]));
+        assert_eq!(launch.scene, StartScene::VoltageDivider);
+        assert_eq!(launch.self_check, Some(false));
+    }
+
+    #[test]
+    fn test_parse_args_fallback() {
+        // неизвестная сцена: открывается сцена по умолчанию
+        let launch = parse_args(args(&["--scene", "rlc", "--voltage", "5"]));
+        assert_eq!(launch.scene, StartScene::OhmLaw);
+        assert_eq!(launch.ohm_law.len(), 1);
+        assert_eq!(launch.notices.len(), 1);
+        assert!(launch.notices[0].starts_with("--scene: "));
+
+        let launch = parse_args(args(&["--preset", "my.ecw", "12", "--current"]));
+        assert_eq!(launch.notices.len(), 3);
+        assert!(launch.notices[2].starts_with("--current: "));
+
+        let launch = parse_args(args(&["--self-check", "no"]));
+        assert_eq!(launch.self_check, None);
+        assert_eq!(launch.notices.len(), 1);
+
+        let launch = parse_args(args(&["--scene", "help", "--voltage", "5"]));
+        assert_eq!(launch.scene, StartScene::Help);
+        assert!(launch.ohm_law.is_empty());
+        assert_eq!(launch.notices.len(), 1);
+    }
+}
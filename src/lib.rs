@@ -0,0 +1,32 @@
+//! # Electrical Calculation Wizard
+//!
+//! The quantities with their tolerances, the input parsers, the interface translations and
+//! the scenes of the GUI. The binary only runs the application on top of this library, the
+//! integration tests in `tests/` use it directly: a scene can be driven by its messages
+//! without a window.
+
+pub mod debounce;
+pub mod eseries;
+pub mod focus;
+pub mod help;
+pub mod i18n;
+pub mod launch;
+pub mod margins;
+pub mod ohm_law;
+pub mod parser;
+pub mod plausibility;
+pub mod presets;
+pub mod report;
+pub mod result_rows;
+pub mod self_check;
+pub mod settings;
+pub mod snapshot;
+pub mod status;
+pub mod style;
+pub mod sweep;
+pub mod theme;
+pub mod types;
+pub mod voltage_divider;
+pub mod widgets;
+
+pub use types::parse;
@@ -1,15 +1,25 @@
 #![windows_subsystem = "windows"]
-use iced::widget::{button, container::Style, row, Column, Container, Text};
-use iced::{Color, Element, Fill, Settings, Size, Theme};
+use ecw::debounce::{self, Debounce};
+use ecw::focus::{self, Focus};
+use ecw::launch::{self, Launch, StartScene};
+use ecw::parser;
+use ecw::self_check;
+use ecw::status::SceneStatus;
+use ecw::theme::ThemeChoice;
+use ecw::types::tolerance_mode::{set_tolerance_mode, tolerance_mode, ToleranceMode};
+use ecw::widgets::table::CellCopy;
+use iced::widget::{button, center, opaque, pick_list, row, stack, text, Column, Container, Text};
+use iced::{keyboard, mouse, window, Element, Fill, Settings, Size, Subscription, Task, Theme};
 
-mod help;
-mod ohm_law;
-mod parser;
-mod types;
-mod voltage_divider;
+use ecw::{help, i18n, ohm_law, presets, report, settings, style, theme, voltage_divider};
 
 fn main() -> iced::Result {
+    let launch = launch::parse_args(std::env::args().skip(1));
+
     iced::application(App::title, App::update, App::view)
+        .subscription(App::subscription)
+        .theme(App::theme)
+        .exit_on_close_request(false)
         .window(iced::window::Settings {
             size: Size {
                 width: 800.0,
@@ -26,12 +36,69 @@ fn main() -> iced::Result {
             ..Default::default()
         })
         .centered()
-        .run()
+        .run_with(move || (App::new(launch), Task::none()))
 }
 
-#[derive(Default)]
 struct App {
     scene: Scene,
+    // окно, закрытие которого ждёт подтверждения
+    close_request: Option<window::Id>,
+    theme: ThemeChoice,
+    // тема по настройке ОС, обновляется при получении окном фокуса
+    system_theme: Theme,
+    debounce: Debounce,
+    focus: Focus,
+    // пропущенные аргументы запуска и расхождения самопроверки, видны до закрытия
+    notices: Vec<String>,
+    // Shift при копировании ячейки выбирает точное число
+    modifiers: keyboard::Modifiers,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new(Launch::default())
+    }
+}
+
+impl App {
+    /// The application opened as the launch arguments say. Debug builds run the self-check
+    /// first, its mismatches join the notices.
+    fn new(launch: Launch) -> Self {
+        let mut notices = launch.notices;
+        if let Some(on) = launch.self_check {
+            self_check::set_at_startup(on);
+        }
+        if cfg!(debug_assertions) && self_check::at_startup() {
+            let failed = i18n::t(i18n::Key::SelfCheckFailed);
+            notices.extend(
+                self_check::run()
+                    .into_iter()
+                    .map(|mismatch| format!("{}: {}", failed, mismatch)),
+            );
+        }
+        let scene = match launch.scene {
+            StartScene::OhmLaw => {
+                let (scene, skipped) = ohm_law::OhmLaw::prefilled(launch.ohm_law);
+                notices.extend(skipped);
+                Scene::OhmLawMsg(Box::new(scene))
+            }
+            StartScene::VoltageDivider => Scene::VoltageDivider(Box::default()),
+            StartScene::Settings => Scene::Settings(Box::new(settings::Settings::new())),
+            StartScene::Presets => Scene::Presets(presets::Presets::new()),
+            StartScene::Help => Scene::Help(help::Help::new()),
+        };
+
+        Self {
+            scene,
+            close_request: None,
+            theme: ThemeChoice::default(),
+            system_theme: theme::system_theme(),
+            debounce: Debounce::default(),
+            focus: Focus::default(),
+            notices,
+            modifiers: keyboard::Modifiers::default(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -39,13 +106,34 @@ enum Message {
     SwitchScene(SceneType),
     OhmLawMsg(ohm_law::Message),
     VoltageDivider(voltage_divider::Message),
+    Settings(settings::Message),
+    Presets(presets::Message),
     Help(help::Message),
+    LanguageSelected(i18n::Lang),
+    ToleranceModeSelected(ToleranceMode),
+    ThemeSelected(ThemeChoice),
+    WindowFocused,
+    /// A left click anywhere, the focused field is looked up after it.
+    Clicked,
+    Focused(focus::Id),
+    FocusLost,
+    Recalculate,
+    DebounceElapsed(u64),
+    CopyReport,
+    CloseRequested(window::Id),
+    ExitDiscard,
+    ExitExport,
+    ExitCancel,
+    DismissNotices,
+    ModifiersChanged(keyboard::Modifiers),
 }
 
 #[derive(Debug)]
 enum Scene {
-    OhmLawMsg(ohm_law::OhmLaw),
-    VoltageDivider(voltage_divider::VoltageDivider),
+    OhmLawMsg(Box<ohm_law::OhmLaw>),
+    VoltageDivider(Box<voltage_divider::VoltageDivider>),
+    Settings(Box<settings::Settings>),
+    Presets(presets::Presets),
     Help(help::Help),
 }
 
@@ -53,46 +141,174 @@ enum Scene {
 enum SceneType {
     OhmLaw,
     VoltageDivider,
+    Settings,
+    Presets,
     Help,
 }
 
 impl Default for Scene {
     fn default() -> Self {
-        Scene::OhmLawMsg(ohm_law::OhmLaw::default())
+        Scene::OhmLawMsg(Box::default())
     }
 }
 
 impl App {
     fn title(&self) -> String {
-        const TITLE_MAIN: &str = "Electrical Calculation Wizard";
-
         let title_scene = match &self.scene {
             Scene::OhmLawMsg(s) => s.title(),
             Scene::VoltageDivider(s) => s.title(),
+            Scene::Settings(s) => s.title(),
+            Scene::Presets(s) => s.title(),
             Scene::Help(s) => s.title(),
         };
 
-        format!("{} - {}", title_scene, TITLE_MAIN)
+        format!("{} - {}", title_scene, i18n::t(i18n::Key::AppTitle))
+    }
+
+    fn theme(&self) -> Theme {
+        self.theme.resolve(&self.system_theme)
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let focus = iced::event::listen_with(|event, _status, _id| match event {
+            iced::Event::Window(window::Event::Focused) => Some(Message::WindowFocused),
+            // поле ввода забирает щелчок себе, поэтому статус события не важен
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                Some(Message::Clicked)
+            }
+            iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(Message::ModifiersChanged(modifiers))
+            }
+            _ => None,
+        });
+
+        // таймер подписан, пока ждёт последнее нажатие; новое поколение перезапускает его
+        let debounce = match self.debounce.pending() {
+            Some(generation) => iced::time::every(debounce::interval())
+                .with(generation)
+                .map(|(generation, _)| Message::DebounceElapsed(generation)),
+            None => Subscription::none(),
+        };
+
+        Subscription::batch([
+            window::close_requests().map(Message::CloseRequested),
+            focus,
+            debounce,
+        ])
+    }
+
+    /// `true` if the current scene holds user input that would be lost on exit.
+    fn is_dirty(&self) -> bool {
+        match &self.scene {
+            Scene::OhmLawMsg(scene) => scene.is_dirty(),
+            Scene::VoltageDivider(scene) => scene.is_dirty(),
+            Scene::Settings(_) | Scene::Presets(_) | Scene::Help(_) => false,
+        }
+    }
+
+    /// Status of the current scene; the other scenes are empty as they start anew on switch.
+    fn status(&self) -> SceneStatus {
+        match &self.scene {
+            Scene::OhmLawMsg(scene) => scene.status(),
+            Scene::VoltageDivider(scene) => scene.status(),
+            Scene::Settings(_) | Scene::Presets(_) | Scene::Help(_) => SceneStatus::Empty,
+        }
+    }
+
+    /// JSON report of the current scene to attach to bug reports.
+    fn diagnostic_report(&self) -> String {
+        match &self.scene {
+            Scene::OhmLawMsg(scene) => scene.diagnostic_report(),
+            Scene::VoltageDivider(scene) => scene.diagnostic_report(),
+            Scene::Settings(_) => settings::Settings::report(),
+            Scene::Presets(_) => report::to_json("presets", presets::presets()),
+            Scene::Help(_) => report::to_json("help", ()),
+        }
     }
 
-    fn update(&mut self, message: Message) {
+    fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::SwitchScene(scene_type) => {
                 self.scene = match scene_type {
-                    SceneType::OhmLaw => Scene::OhmLawMsg(ohm_law::OhmLaw::default()),
-                    SceneType::VoltageDivider => {
-                        Scene::VoltageDivider(voltage_divider::VoltageDivider::default())
-                    }
+                    SceneType::OhmLaw => Scene::OhmLawMsg(Box::default()),
+                    SceneType::VoltageDivider => Scene::VoltageDivider(Box::default()),
+                    SceneType::Settings => Scene::Settings(Box::new(settings::Settings::new())),
+                    SceneType::Presets => Scene::Presets(presets::Presets::new()),
                     SceneType::Help => Scene::Help(help::Help::new()),
                 };
             }
-            Message::VoltageDivider(msg) => {
+            Message::VoltageDivider(voltage_divider::Message::FieldSubmitted(leg, field)) => {
+                if let Scene::VoltageDivider(scene) = &self.scene {
+                    if let Some((leg, field)) = scene.next_field(leg, field) {
+                        return self.focus.advance(field.input_id(leg));
+                    }
+                }
+            }
+            Message::VoltageDivider(voltage_divider::Message::CopyCell(copy)) => {
+                return self.copy_cell(&copy);
+            }
+            Message::VoltageDivider(mut msg) => {
+                self.cap_input(msg.text_mut());
                 if let Scene::VoltageDivider(scene) = &mut self.scene {
+                    if debounce::is_on() && msg.is_text_input() {
+                        scene.store_text(msg);
+                        self.debounce.keystroke();
+                        return Task::none();
+                    }
                     scene.update(msg);
                 }
             }
-            Message::OhmLawMsg(msg) => {
+            Message::OhmLawMsg(ohm_law::Message::CopySweepCsv) => {
+                if let Scene::OhmLawMsg(scene) = &self.scene {
+                    return iced::clipboard::write(scene.sweep_csv());
+                }
+            }
+            Message::OhmLawMsg(ohm_law::Message::FieldSubmitted(field)) => {
+                if let Scene::OhmLawMsg(scene) = &self.scene {
+                    if let Some(field) = scene.next_field(field) {
+                        return self.focus.advance(field.input_id());
+                    }
+                }
+            }
+            Message::OhmLawMsg(ohm_law::Message::CopyErrorReport(field)) => {
+                if let Scene::OhmLawMsg(scene) = &self.scene {
+                    if let Some(report) = scene.error_report(field) {
+                        return iced::clipboard::write(report);
+                    }
+                }
+            }
+            Message::OhmLawMsg(ohm_law::Message::CopyCell(copy)) => return self.copy_cell(&copy),
+            Message::OhmLawMsg(ohm_law::Message::ExportJson) => {
                 if let Scene::OhmLawMsg(scene) = &mut self.scene {
+                    scene.mark_exported();
+                    return iced::clipboard::write(scene.result_json(false));
+                }
+            }
+            Message::OhmLawMsg(mut msg) => {
+                self.cap_input(msg.text_mut());
+                if let Scene::OhmLawMsg(scene) = &mut self.scene {
+                    if debounce::is_on() && msg.is_text_input() {
+                        scene.store_text(msg);
+                        self.debounce.keystroke();
+                        return Task::none();
+                    }
+                    scene.update(msg);
+                }
+            }
+            Message::Settings(msg) => {
+                if let Scene::Settings(scene) = &mut self.scene {
+                    scene.update(msg);
+                }
+            }
+            Message::Presets(presets::Message::Export) => {
+                return iced::clipboard::write(presets::export_json());
+            }
+            Message::Presets(presets::Message::Import) => {
+                return iced::clipboard::read()
+                    .map(|json| Message::Presets(presets::Message::Imported(json)));
+            }
+            Message::Presets(msg) => {
+                if let Scene::Presets(scene) = &mut self.scene {
                     scene.update(msg);
                 }
             }
@@ -101,24 +317,142 @@ impl App {
                     scene.update(msg);
                 }
             }
+            Message::LanguageSelected(lang) => {
+                i18n::set_lang(lang);
+                // сообщения об ошибках ввода хранятся уже переведёнными
+                return Task::done(Message::Recalculate);
+            }
+            Message::ToleranceModeSelected(mode) => {
+                set_tolerance_mode(mode);
+                return Task::done(Message::Recalculate);
+            }
+            Message::ThemeSelected(theme) => self.theme = theme,
+            Message::WindowFocused => self.system_theme = theme::system_theme(),
+            Message::CopyReport => return iced::clipboard::write(self.diagnostic_report()),
+            Message::Recalculate => match &mut self.scene {
+                Scene::OhmLawMsg(scene) => scene.recalculate(),
+                Scene::VoltageDivider(scene) => scene.recalculate(),
+                Scene::Settings(_) | Scene::Presets(_) | Scene::Help(_) => (),
+            },
+            Message::Clicked => {
+                return focus::find_focused(Message::Focused, Message::FocusLost);
+            }
+            Message::Focused(id) => return self.focus.clicked(Some(id)),
+            Message::FocusLost => return self.focus.clicked(None),
+            Message::DebounceElapsed(generation) => {
+                if self.debounce.is_due(generation) {
+                    return self.update(Message::Recalculate);
+                }
+            }
+            Message::CloseRequested(id) => {
+                if !self.is_dirty() {
+                    return window::close(id);
+                }
+                self.close_request = Some(id);
+            }
+            Message::ExitDiscard => {
+                if let Some(id) = self.close_request.take() {
+                    return window::close(id);
+                }
+            }
+            Message::ExitExport => {
+                if let (Some(id), Scene::OhmLawMsg(scene)) = (self.close_request, &mut self.scene) {
+                    self.close_request = None;
+                    scene.mark_exported();
+                    // окно закрывается после записи в буфер обмена
+                    return iced::clipboard::write(scene.result_json(false))
+                        .chain(window::close(id));
+                }
+            }
+            Message::ExitCancel => self.close_request = None,
+            Message::DismissNotices => self.notices.clear(),
+            Message::ModifiersChanged(modifiers) => self.modifiers = modifiers,
+        }
+
+        Task::none()
+    }
+
+    /// Copies a result cell as shown, or the exact number while Shift is held.
+    fn copy_cell(&self, copy: &CellCopy) -> Task<Message> {
+        iced::clipboard::write(copy.text(self.modifiers.shift()).to_string())
+    }
+
+    /// Cuts entered text over the input limit, e.g. a pasted document, so it is neither
+    /// stored nor parsed in full. The notice is shown once until dismissed.
+    fn cap_input(&mut self, text: Option<&mut String>) {
+        if !text.is_some_and(parser::truncate_input) {
+            return;
+        }
+
+        let notice = format!(
+            "{} {}",
+            i18n::t(i18n::Key::InputTruncated),
+            parser::max_input_len()
+        );
+        if !self.notices.contains(&notice) {
+            self.notices.push(notice);
         }
     }
 
-    fn view_sidebar(&self) -> Element<Message> {
+    fn view_sidebar(&self) -> Element<'_, Message> {
+        let (ohm_law_status, voltage_divider_status) = match &self.scene {
+            Scene::OhmLawMsg(_) => (self.status(), SceneStatus::Empty),
+            Scene::VoltageDivider(_) => (SceneStatus::Empty, self.status()),
+            Scene::Settings(_) | Scene::Presets(_) | Scene::Help(_) => {
+                (SceneStatus::Empty, SceneStatus::Empty)
+            }
+        };
+
         Column::new()
             .push(
-                button("Ohm Law")
+                button(scene_label(i18n::t(i18n::Key::OhmLaw), ohm_law_status))
                     .on_press(Message::SwitchScene(SceneType::OhmLaw))
                     .width(Fill),
             )
             .push(
-                button("Voltage Divider")
-                    .on_press(Message::SwitchScene(SceneType::VoltageDivider))
-                    .width(Fill),
+                button(scene_label(
+                    i18n::t(i18n::Key::VoltageDivider),
+                    voltage_divider_status,
+                ))
+                .on_press(Message::SwitchScene(SceneType::VoltageDivider))
+                .width(Fill),
             )
             .push(Text::new("").height(Fill))
             .push(
-                button("Help")
+                button(Text::new(i18n::t(i18n::Key::CopyReport)).size(12))
+                    .on_press(Message::CopyReport)
+                    .style(button::secondary)
+                    .width(Fill),
+            )
+            .push(
+                pick_list(
+                    i18n::Lang::ALL,
+                    Some(i18n::lang()),
+                    Message::LanguageSelected,
+                )
+                .width(Fill),
+            )
+            .push(
+                pick_list(
+                    ToleranceMode::ALL,
+                    Some(tolerance_mode()),
+                    Message::ToleranceModeSelected,
+                )
+                .width(Fill),
+            )
+            .push(pick_list(ThemeChoice::ALL, Some(self.theme), Message::ThemeSelected).width(Fill))
+            .push(
+                button(i18n::t(i18n::Key::Settings))
+                    .on_press(Message::SwitchScene(SceneType::Settings))
+                    .width(Fill),
+            )
+            .push(
+                button(i18n::t(i18n::Key::Presets))
+                    .on_press(Message::SwitchScene(SceneType::Presets))
+                    .width(Fill),
+            )
+            .push(
+                button(i18n::t(i18n::Key::Help))
                     .on_press(Message::SwitchScene(SceneType::Help))
                     .width(Fill),
             )
@@ -126,32 +460,253 @@ impl App {
             .into()
     }
 
-    fn view_context(&self) -> Element<Message> {
+    fn view_context(&self) -> Element<'_, Message> {
         match &self.scene {
             Scene::OhmLawMsg(scene) => scene.view().map(Message::OhmLawMsg),
             Scene::VoltageDivider(scene) => scene.view().map(Message::VoltageDivider),
-            Scene::Help(scene) => scene.view().map(Message::Help),
+            Scene::Settings(scene) => scene.view().map(Message::Settings),
+            Scene::Presets(scene) => scene.view().map(Message::Presets),
+            Scene::Help(scene) => scene.view(&self.theme()).map(Message::Help),
         }
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let sidebar = Container::new(self.view_sidebar())
             .padding(5)
             .width(150)
             .height(Fill)
-            .style(|_t: &Theme| Style {
-                background: Some(Color::from_rgb8(8, 21, 40).into()),
-                ..Style::default()
-            });
-        let content = Container::new(self.view_context())
+            .style(style::sidebar_container);
+        let mut context = Column::new();
+        if !self.notices.is_empty() {
+            context = context.push(self.view_notices());
+        }
+        let content = Container::new(context.push(self.view_context()).spacing(10))
             .padding(10)
             .height(Fill)
             .width(Fill)
-            .style(|_t: &Theme| Style {
-                background: Some(Color::from_rgb8(246, 246, 246).into()),
-                ..Style::default()
-            });
+            .style(style::content_container);
+
+        let base = row![sidebar, content];
+
+        if self.close_request.is_some() {
+            stack![base, self.view_exit_confirmation()].into()
+        } else {
+            base.into()
+        }
+    }
+
+    /// Skipped launch arguments and self-check mismatches above the scene, closed by the ✕
+    /// button.
+    fn view_notices(&self) -> Element<'_, Message> {
+        let notices = self.notices.iter().fold(Column::new(), |column, notice| {
+            column.push(text(notice).size(12).style(style::warning_text))
+        });
+
+        row![
+            notices.width(Fill),
+            button(text("✕").size(12))
+                .on_press(Message::DismissNotices)
+                .style(button::text),
+        ]
+        .into()
+    }
+
+    fn view_exit_confirmation(&self) -> Element<'_, Message> {
+        // экспортировать можно только результат закона Ома
+        let export = match self.scene {
+            Scene::OhmLawMsg(_) => Some(
+                button(i18n::t(i18n::Key::ExportAndExit))
+                    .on_press(Message::ExitExport)
+                    .style(button::secondary),
+            ),
+            _ => None,
+        };
+        let buttons = row![button(i18n::t(i18n::Key::Discard)).on_press(Message::ExitDiscard)]
+            .push_maybe(export)
+            .push(
+                button(i18n::t(i18n::Key::Cancel))
+                    .on_press(Message::ExitCancel)
+                    .style(button::secondary),
+            )
+            .spacing(10);
+        let dialog = Container::new(
+            Column::new()
+                .push(Text::new(i18n::t(i18n::Key::UnsavedChanges)))
+                .push(buttons)
+                .spacing(15),
+        )
+        .padding(20)
+        .width(400)
+        .style(style::dialog_container);
+
+        opaque(center(dialog).style(style::backdrop_container))
+    }
+}
+
+/// Sidebar button label with a badge for the scene status.
+fn scene_label(label: &str, status: SceneStatus) -> Element<'_, Message> {
+    let badge = match status {
+        SceneStatus::Error => text("●").style(text::danger),
+        SceneStatus::Solved => text("✓").style(text::success),
+        SceneStatus::Empty | SceneStatus::Incomplete => text(""),
+    };
+
+    row![text(label).width(Fill), badge].spacing(5).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dirty_scene_switch() {
+        let mut app = App::default();
+        assert!(!app.is_dirty());
+
+        let _ = app.update(Message::OhmLawMsg(ohm_law::Message::InputVoltageChanged(
+            "10".to_string(),
+        )));
+        assert!(app.is_dirty());
+
+        // a new scene starts without input
+        let _ = app.update(Message::SwitchScene(SceneType::VoltageDivider));
+        assert!(!app.is_dirty());
+
+        let _ = app.update(Message::VoltageDivider(
+            voltage_divider::Message::InputResistanceChanged(0, "1k".to_string()),
+        ));
+        assert!(app.is_dirty());
+
+        let _ = app.update(Message::SwitchScene(SceneType::Help));
+        assert!(!app.is_dirty());
+
+        // экспорт сохраняет результат, правка снова помечает сцену
+        let _ = app.update(Message::SwitchScene(SceneType::OhmLaw));
+        let _ = app.update(Message::OhmLawMsg(ohm_law::Message::InputVoltageChanged(
+            "10".to_string(),
+        )));
+        let _ = app.update(Message::OhmLawMsg(ohm_law::Message::ExportJson));
+        assert!(!app.is_dirty());
+        let _ = app.update(Message::OhmLawMsg(ohm_law::Message::InputVoltageChanged(
+            "12".to_string(),
+        )));
+        assert!(app.is_dirty());
+
+        // the close dialog can export instead of discarding
+        let id = window::Id::unique();
+        let _ = app.update(Message::CloseRequested(id));
+        assert_eq!(app.close_request, Some(id));
+        let _ = app.update(Message::ExitExport);
+        assert_eq!(app.close_request, None);
+        assert!(!app.is_dirty());
+    }
+
+    #[test]
+    fn test_close_request() {
+        let mut app = App::default();
+        let id = window::Id::unique();
+
+        // nothing to lose: the window closes right away
+        let _ = app.update(Message::CloseRequested(id));
+        assert_eq!(app.close_request, None);
+
+        let _ = app.update(Message::OhmLawMsg(ohm_law::Message::InputVoltageChanged(
+            "10".to_string(),
+        )));
+        let _ = app.update(Message::CloseRequested(id));
+        assert_eq!(app.close_request, Some(id));
+
+        let _ = app.update(Message::ExitCancel);
+        assert_eq!(app.close_request, None);
+        assert!(app.is_dirty());
+
+        let _ = app.update(Message::CloseRequested(id));
+        let _ = app.update(Message::ExitDiscard);
+        assert_eq!(app.close_request, None);
+    }
+
+    #[test]
+    fn test_launch() {
+        let app = App::new(launch::parse_args(
+            ["--voltage", "12x", "--current", "2"].map(String::from),
+        ));
+        assert!(matches!(app.scene, Scene::OhmLawMsg(_)));
+        assert_eq!(app.notices.len(), 1);
+        assert_eq!(app.status(), SceneStatus::Incomplete);
+
+        let mut app = App::new(launch::parse_args(
+            ["--scene", "divider", "--power", "1"].map(String::from),
+        ));
+        assert!(matches!(app.scene, Scene::VoltageDivider(_)));
+        assert_eq!(app.notices.len(), 1);
+        let _ = app.update(Message::DismissNotices);
+        assert!(app.notices.is_empty());
+
+        // нулевой делитель при запуске: расчёт не выполняется
+        for args in [
+            ["--voltage", "10", "--current", "0"],
+            ["--voltage", "10", "--power", "0"],
+        ] {
+            let app = App::new(launch::parse_args(args.map(String::from)));
+            let Scene::OhmLawMsg(ohm_law) = &app.scene else {
+                panic!("expected the Ohm's law scene");
+            };
+            assert!(app.notices.is_empty());
+            let result: serde_json::Value =
+                serde_json::from_str(&ohm_law.result_json(true)).unwrap();
+            assert!(result["resistance"].is_null());
+        }
+    }
+
+    #[test]
+    fn test_debounce() {
+        // пересчёт отложен по умолчанию
+        let mut app = App::default();
+        assert!(debounce::is_on());
+
+        let _ = app.update(Message::OhmLawMsg(ohm_law::Message::InputVoltageChanged(
+            "10".to_string(),
+        )));
+        let _ = app.update(Message::OhmLawMsg(ohm_law::Message::InputCurrentChanged(
+            "2".to_string(),
+        )));
+        // текст сохранён, но сцена ещё не решена
+        assert!(app.is_dirty());
+        assert_eq!(app.status(), SceneStatus::Incomplete);
+
+        // подписка на таймер ждёт последнее нажатие
+        assert_eq!(app.debounce.pending(), Some(2));
+
+        // only the timer of the last keystroke recalculates
+        let _ = app.update(Message::DebounceElapsed(1));
+        assert_eq!(app.status(), SceneStatus::Incomplete);
+        let _ = app.update(Message::DebounceElapsed(2));
+        assert_eq!(app.status(), SceneStatus::Solved);
+        assert_eq!(app.debounce.pending(), None);
+    }
+
+    #[test]
+    fn test_input_cap() {
+        let mut app = App::default();
+        let pasted = "1".repeat(parser::MAX_INPUT_LEN * 1000);
+
+        for _ in 0..2 {
+            let _ = app.update(Message::OhmLawMsg(ohm_law::Message::InputVoltageChanged(
+                pasted.clone(),
+            )));
+        }
+        // обрезанный текст разбирается, уведомление одно
+        assert_eq!(app.notices.len(), 1);
+        assert_eq!(app.status(), SceneStatus::Incomplete);
+        let Scene::OhmLawMsg(scene) = &app.scene else {
+            unreachable!();
+        };
+        assert_eq!(scene.error_report(ohm_law::FieldId::Voltage), None);
 
-        row![sidebar, content].into()
+        let _ = app.update(Message::SwitchScene(SceneType::VoltageDivider));
+        let _ = app.update(Message::VoltageDivider(
+            voltage_divider::Message::InputTapsChanged("5, ".repeat(1000)),
+        ));
+        assert_eq!(app.notices.len(), 1);
     }
 }
@@ -0,0 +1,78 @@
+//! # Safety Margins
+//!
+//! Factors the computed values are scaled by to pick a part: the power rating of a resistor
+//! is the dissipated power times the derating, the supply current is the drawn current times
+//! the headroom. Both are set on the settings page to match the house rules.
+//!
+//! Like the interface language, the margins are kept per thread.
+
+use std::cell::Cell;
+
+use serde::Serialize;
+
+thread_local! {
+    static MARGINS: Cell<Margins> = const { Cell::new(Margins::DEFAULT) };
+}
+
+/// Multipliers of the computed values, `1.0` for no margin.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Margins {
+    pub derating: f64,
+    pub headroom: f64,
+}
+
+impl Margins {
+    pub const DEFAULT: Margins = Margins {
+        derating: 2.0,
+        headroom: 1.2,
+    };
+}
+
+impl Default for Margins {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Sets the margins for the current thread.
+pub fn set_margins(margins: Margins) {
+    MARGINS.with(|m| m.set(margins));
+}
+
+/// Returns the margins of the current thread.
+pub fn margins() -> Margins {
+    MARGINS.with(|m| m.get())
+}
+
+/// Parses a factor of at least one, `"2"`, `"2x"` or `"×2"`. `None` on anything else.
+pub fn parse_factor(input: &str) -> Option<f64> {
+    let number = input
+        .trim()
+        .trim_start_matches(['×', 'x'])
+        .trim_end_matches(['×', 'x'])
+        .trim();
+
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|factor| *factor >= 1.0 && factor.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_factor() {
+        assert_eq!(parse_factor("2"), Some(2.0));
+        assert_eq!(parse_factor(" 1.5x "), Some(1.5));
+        assert_eq!(parse_factor("×1.2"), Some(1.2));
+        assert_eq!(parse_factor("1"), Some(1.0));
+
+        // запас меньше единицы занижал бы номинал детали
+        assert_eq!(parse_factor("0.5"), None);
+        assert_eq!(parse_factor("inf"), None);
+        assert_eq!(parse_factor("x"), None);
+        assert_eq!(parse_factor(""), None);
+    }
+}
@@ -1,26 +1,277 @@
-use iced::widget::{Column, Container, Row, Rule, Text, TextInput};
-use iced::{Alignment, Color, Element, Fill};
-
-use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
-use crate::types::{Measurement, ParserError};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use iced::widget::{button, checkbox, pick_list, Button, Column, Container, Row, Text, TextInput};
+use iced::{Alignment, Element, Fill};
+use serde::Serialize;
+
+use crate::i18n::{t, Key};
+use crate::margins;
+use crate::plausibility;
+use crate::presets::{self, Kind, Preset};
+use crate::report::{self, Bounds};
+use crate::result_rows::{self, ResultRow, RowSet};
+use crate::snapshot::Snapshot;
+use crate::status::SceneStatus;
+use crate::style::{self, TextStyle};
+use crate::sweep::{self, Sweep};
+use crate::types::{
+    calculate_division_with_tolerance, calculate_multiplication_with_tolerance,
+    calculate_square_root_with_tolerance, default_tolerance, Bound, Dim, Measurement, ParserError,
+    Tolerance, ToleranceProvenance,
+};
+use crate::types::{conductance::Conductance, current::Current, power::Power};
+use crate::types::{resistance::Resistance, voltage::Voltage};
+use crate::widgets::measurement_input::{Event, MeasurementInput};
+use crate::widgets::table::{self, Cell, CellCopy, Table, TableRow};
+use crate::widgets::{eseries_check, row_menu};
 
 #[derive(Debug, Clone)]
 pub struct OhmLaw {
     fields_enable: FieldsEnable,
     data_raw: OhmDataRaw,
     data: OhmData,
+    provenance: OhmProvenance,
     calc_type: CalcType,
+    selected_row: Option<usize>,
+    measurement_mode: bool,
+    overdetermined: OverdeterminedResult,
+    dirty: bool,
+    pinned: Option<Snapshot>,
+    // предупреждения о неправдоподобных значениях: напряжение, ток, сопротивление, мощность
+    warnings: [Option<String>; 4],
+    // сечение проводника для плотности тока, мм²
+    area_raw: String,
+    area: Result<f64, ParserError>,
+    aux: AuxOutputs,
+    // пошаговое решение: формулы с подставленными значениями
+    show_work: bool,
+    steps: Vec<String>,
+    // закреплённые поля: только для чтения, но участвуют в расчёте
+    locked: Vec<FieldId>,
+    sweep: SweepInput,
+    // список ближайших стандартных значений раскрыт
+    standard_values: bool,
+    // таблица результата: величины по строкам
+    transposed: bool,
+    // открыт выбор видимых строк таблицы
+    rows_menu: bool,
+    // расчёт приостановлен: ввод запоминается, результаты не меняются
+    frozen: bool,
+}
+
+/// Sweep over one input: the variable, its points and the solution at every point.
+#[derive(Debug, Clone)]
+struct SweepInput {
+    enabled: bool,
+    variable: FieldId,
+    raw: String,
+    sweep: Result<Sweep, ParserError>,
+    rows: Vec<OhmData>,
+}
+
+impl Default for SweepInput {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            variable: FieldId::Voltage,
+            raw: String::new(),
+            sweep: Err(ParserError::EmptyInput),
+            rows: Vec::new(),
+        }
+    }
+}
+
+/// Derived outputs shown under the result table.
+#[derive(Debug, Clone, Default)]
+struct AuxOutputs {
+    conductance: Option<Conductance>,
+    /// Power times the derating, the rating of the resistor to pick.
+    power_rating: Option<Power>,
+    /// Current times the headroom, what the supply must deliver.
+    supply_current: Option<Current>,
+    current_density: Option<CurrentDensity>,
+    density_warning: Option<String>,
+}
+
+/// Current density in A/mm²: the nominal value and the band of the current magnitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CurrentDensity {
+    nominal: f64,
+    min: f64,
+    max: f64,
+}
+
+/// Current density of `current` through a conductor cross-section `area` in mm². `None`
+/// unless the area is above zero.
+fn current_density(current: &Current, area: f64) -> Option<CurrentDensity> {
+    if area <= 0.0 || !area.is_finite() {
+        return None;
+    }
+
+    let (min, max) = (current.min_value().abs(), current.max_value().abs());
+
+    Some(CurrentDensity {
+        nominal: current.get_nominal_value().abs() / area,
+        min: min.min(max) / area,
+        max: min.max(max) / area,
+    })
+}
+
+fn parse_area(input: &str) -> Result<f64, ParserError> {
+    if input.trim().is_empty() {
+        return Err(ParserError::EmptyInput);
+    }
+
+    match input.trim().parse::<f64>() {
+        Ok(area) if area > 0.0 && area.is_finite() => Ok(area),
+        Ok(_) => Err(ParserError::IncorrectInput(
+            t(Key::AreaNotPositive).to_string(),
+        )),
+        Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+// имена в JSON результата остаются прежними
+#[derive(Debug, Clone, Copy, Serialize)]
 enum CalcType {
     None, // None
-    VCRP, // Input V, C; Calc R, P
-    VRCP, // Input V, R; Calc C, P
-    VPCR, // Input V, P; Calc C, R
-    CRVP, // Input C, R; Calc V, P
-    CPVR, // Input C, P; Calc V, R
-    RPVC, // Input R, P; Calc V, C
+    #[serde(rename = "VCRP")]
+    VcRp, // Input V, C; Calc R, P
+    #[serde(rename = "VRCP")]
+    VrCp, // Input V, R; Calc C, P
+    #[serde(rename = "VPCR")]
+    VpCr, // Input V, P; Calc C, R
+    #[serde(rename = "CRVP")]
+    CrVp, // Input C, R; Calc V, P
+    #[serde(rename = "CPVR")]
+    CpVr, // Input C, P; Calc V, R
+    #[serde(rename = "RPVC")]
+    RpVc, // Input R, P; Calc V, C
+    Overdetermined, // Input 3 or 4 values; Calc every pair and compare
+}
+
+impl CalcType {
+    /// Caption over the form: which quantities are entered and which are calculated, e.g.
+    /// "Inputs: Voltage, Resistance → calculating Current and Power".
+    fn description(&self) -> String {
+        let ([a, b], [c, d]) = match self {
+            CalcType::None => return t(Key::CalcTypeNone).to_string(),
+            CalcType::Overdetermined => return t(Key::CalcTypeOverdetermined).to_string(),
+            CalcType::VcRp => ([Key::Voltage, Key::Current], [Key::Resistance, Key::Power]),
+            CalcType::VrCp => ([Key::Voltage, Key::Resistance], [Key::Current, Key::Power]),
+            CalcType::VpCr => ([Key::Voltage, Key::Power], [Key::Current, Key::Resistance]),
+            CalcType::CrVp => ([Key::Current, Key::Resistance], [Key::Voltage, Key::Power]),
+            CalcType::CpVr => ([Key::Current, Key::Power], [Key::Voltage, Key::Resistance]),
+            CalcType::RpVc => ([Key::Resistance, Key::Power], [Key::Voltage, Key::Current]),
+        };
+
+        format!(
+            "{}: {}, {} → {} {} {} {}",
+            t(Key::Inputs),
+            t(a),
+            t(b),
+            t(Key::Calculating),
+            t(c),
+            t(Key::And),
+            t(d)
+        )
+    }
+}
+
+// Пары входных величин, по которым можно выполнить расчет
+const PAIRS: [CalcType; 6] = [
+    CalcType::VcRp,
+    CalcType::VrCp,
+    CalcType::VpCr,
+    CalcType::CrVp,
+    CalcType::CpVr,
+    CalcType::RpVc,
+];
+
+impl CalcType {
+    /// Indexes (voltage, current, resistance, power) of the two inputs of a pair.
+    fn inputs(&self) -> (usize, usize) {
+        match self {
+            CalcType::VcRp => (0, 1),
+            CalcType::VrCp => (0, 2),
+            CalcType::VpCr => (0, 3),
+            CalcType::CrVp => (1, 2),
+            CalcType::CpVr => (1, 3),
+            CalcType::RpVc => (2, 3),
+            CalcType::None | CalcType::Overdetermined => (0, 0),
+        }
+    }
+
+    /// Formula of a quantity calculated from the pair, `None` for the inputs of the pair.
+    fn formula(&self, field: FieldId) -> Option<&'static str> {
+        use FieldId::{Current, Power, Resistance, Voltage};
+
+        match (self, field) {
+            (CalcType::VcRp | CalcType::VpCr | CalcType::CpVr, Resistance) => Some("R = V / I"),
+            (CalcType::VcRp | CalcType::VrCp | CalcType::CrVp, Power) => Some("P = V × I"),
+            (CalcType::VrCp, Current) => Some("I = V / R"),
+            (CalcType::VpCr, Current) => Some("I = P / V"),
+            (CalcType::CrVp, Voltage) => Some("V = I × R"),
+            (CalcType::CpVr, Voltage) => Some("V = P / I"),
+            (CalcType::RpVc, Voltage) => Some("V = √(P × R)"),
+            (CalcType::RpVc, Current) => Some("I = √(P / R)"),
+            _ => None,
+        }
+    }
+}
+
+/// Quotes a CSV field that holds a comma, a quote or a line break, e.g. an entered
+/// `1,000`.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Calculation type for the filled voltage, current, resistance and power.
+fn calc_type_of(filled: [bool; 4]) -> CalcType {
+    if filled.iter().filter(|f| **f).count() > 2 {
+        return CalcType::Overdetermined;
+    }
+
+    match filled {
+        [true, true, _, _] => CalcType::VcRp,
+        [true, _, true, _] => CalcType::VrCp,
+        [true, _, _, true] => CalcType::VpCr,
+        [_, true, true, _] => CalcType::CrVp,
+        [_, true, _, true] => CalcType::CpVr,
+        [_, _, true, true] => CalcType::RpVc,
+        _ => CalcType::None,
+    }
+}
+
+// относительная погрешность вычислений: без допусков 10 В / 2 А дают мощность
+// 20 и 20.000000000000004 по разным парам
+const CONSISTENCY_REL_EPS: f64 = 1e-9;
+const CONSISTENCY_ABS_EPS: f64 = 1e-15;
+
+/// `true` if the tolerance bands of all candidates intersect pairwise. A candidate without
+/// tolerance must lie within the band of the other one, two of them must be equal up to the
+/// rounding of the calculation.
+fn bands_overlap<M: Measurement>(candidates: &[M]) -> bool {
+    let agree = |a: &M, b: &M| {
+        let in_band = match (a.get_tolerance(), b.get_tolerance()) {
+            (None, Some(_)) => a.within(b),
+            (Some(_), None) => b.within(a),
+            _ => a.overlaps(b),
+        };
+
+        in_band || a.approx_eq(b, CONSISTENCY_REL_EPS, CONSISTENCY_ABS_EPS)
+    };
+
+    candidates
+        .iter()
+        .enumerate()
+        .all(|(i, a)| candidates[i + 1..].iter().all(|b| agree(a, b)))
 }
 
 impl Default for OhmLaw {
@@ -29,7 +280,25 @@ impl Default for OhmLaw {
             fields_enable: FieldsEnable::default(),
             data_raw: OhmDataRaw::default(),
             data: OhmData::default(),
+            provenance: OhmProvenance::default(),
             calc_type: CalcType::None,
+            selected_row: None,
+            measurement_mode: false,
+            overdetermined: OverdeterminedResult::default(),
+            dirty: false,
+            pinned: None,
+            warnings: Default::default(),
+            area_raw: String::new(),
+            area: Err(ParserError::EmptyInput),
+            aux: AuxOutputs::default(),
+            show_work: false,
+            steps: Vec::new(),
+            locked: Vec::new(),
+            sweep: SweepInput::default(),
+            standard_values: false,
+            transposed: false,
+            rows_menu: false,
+            frozen: false,
         }
     }
 }
@@ -53,12 +322,13 @@ impl Default for FieldsEnable {
     }
 }
 
+/// Entered or calculated values of the scene.
 #[derive(Debug, Clone)]
-struct OhmData {
-    voltage: Result<Voltage, ParserError>,
-    current: Result<Current, ParserError>,
-    resistance: Result<Resistance, ParserError>,
-    power: Result<Power, ParserError>,
+pub struct OhmData {
+    pub voltage: Result<Voltage, ParserError>,
+    pub current: Result<Current, ParserError>,
+    pub resistance: Result<Resistance, ParserError>,
+    pub power: Result<Power, ParserError>,
 }
 
 impl Default for OhmData {
@@ -72,7 +342,26 @@ impl Default for OhmData {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Candidate values of every quantity when more than two values are entered, one per
+/// pair of inputs it can be calculated from (an entered value is a candidate of itself).
+#[derive(Debug, Clone, Default, Serialize)]
+struct OverdeterminedResult {
+    voltage: Vec<Voltage>,
+    current: Vec<Current>,
+    resistance: Vec<Resistance>,
+    power: Vec<Power>,
+    consistent: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct OhmProvenance {
+    voltage: Option<ToleranceProvenance>,
+    current: Option<ToleranceProvenance>,
+    resistance: Option<ToleranceProvenance>,
+    power: Option<ToleranceProvenance>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 struct OhmDataRaw {
     voltage: String,
     current: String,
@@ -80,15 +369,181 @@ struct OhmDataRaw {
     power: String,
 }
 
-impl Default for OhmDataRaw {
-    fn default() -> Self {
-        Self {
-            voltage: String::new(),
-            current: String::new(),
-            resistance: String::new(),
-            power: String::new(),
+impl OhmDataRaw {
+    fn is_empty(&self) -> bool {
+        [&self.voltage, &self.current, &self.resistance, &self.power]
+            .iter()
+            .all(|s| s.trim().is_empty())
+    }
+}
+
+/// Version of the [`ResultSnapshot`] fields, bumped on every change of them.
+/// 2: every value carries its provenance.
+const RESULT_SCHEMA: u32 = 2;
+
+/// The result of the scene for downstream tooling: every quantity with its tolerance band and
+/// the calculation type. A quantity that is not solved is `null`.
+#[derive(Debug, Serialize)]
+struct ResultSnapshot {
+    schema: u32,
+    calc_type: CalcType,
+    voltage: Option<ExportedValue>,
+    current: Option<ExportedValue>,
+    resistance: Option<ExportedValue>,
+    power: Option<ExportedValue>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportedValue {
+    #[serde(flatten)]
+    bounds: Bounds,
+    provenance: Provenance,
+}
+
+/// Where a value comes from: the formula it is calculated by, `null` for an entered value,
+/// and the entered values it depends on.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Provenance {
+    formula: Option<&'static str>,
+    inputs: Vec<InputRef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct InputRef {
+    name: &'static str,
+    raw: String,
+}
+
+/// Provenance of the value of `field` for `calc_type`; `raw` gives the entered text of a field.
+fn provenance(calc_type: CalcType, field: FieldId, raw: impl Fn(FieldId) -> String) -> Provenance {
+    let input = |field: FieldId| InputRef {
+        name: field.export_name(),
+        raw: raw(field),
+    };
+
+    match calc_type.formula(field) {
+        Some(formula) => {
+            let (a, b) = calc_type.inputs();
+            Provenance {
+                formula: Some(formula),
+                inputs: vec![input(FieldId::SWEEP[a]), input(FieldId::SWEEP[b])],
+            }
+        }
+        None => Provenance {
+            formula: None,
+            inputs: vec![input(field)],
+        },
+    }
+}
+
+/// Fields of an exported value and the result rows that show them. The absolute
+/// tolerances are not exported, the percent ones are the `tolerance`.
+const EXPORTED_ROWS: [(&str, &[ResultRow]); 4] = [
+    ("nominal", &[ResultRow::ValueNom]),
+    ("max", &[ResultRow::ValueMax]),
+    ("min", &[ResultRow::ValueMin]),
+    (
+        "tolerance",
+        &[ResultRow::TolPlusPercent, ResultRow::TolMinusPercent],
+    ),
+];
+
+impl ResultSnapshot {
+    /// The JSON without the fields whose rows are all `hidden`.
+    fn to_json(&self, hidden: RowSet) -> String {
+        if hidden.is_empty() {
+            return serde_json::to_string_pretty(self).unwrap_or_default();
+        }
+
+        let mut json = serde_json::to_value(self).unwrap_or_default();
+        for quantity in ["voltage", "current", "resistance", "power"] {
+            let Some(value) = json.get_mut(quantity).and_then(|v| v.as_object_mut()) else {
+                continue;
+            };
+            for (field, rows) in EXPORTED_ROWS {
+                if rows.iter().all(|row| hidden.contains(*row)) {
+                    value.remove(field);
+                }
+            }
+        }
+
+        serde_json::to_string_pretty(&json).unwrap_or_default()
+    }
+}
+
+/// Input field of the scene, for the messages that act on one field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldId {
+    Voltage,
+    Current,
+    Resistance,
+    Power,
+    Area,
+}
+
+impl FieldId {
+    /// Inputs that can be swept.
+    const SWEEP: [FieldId; 4] = [
+        FieldId::Voltage,
+        FieldId::Current,
+        FieldId::Resistance,
+        FieldId::Power,
+    ];
+
+    /// Fields of the form in the order Enter moves the focus.
+    const FORM: [FieldId; 5] = [
+        FieldId::Voltage,
+        FieldId::Current,
+        FieldId::Resistance,
+        FieldId::Power,
+        FieldId::Area,
+    ];
+
+    /// Name of the field in the exported results.
+    fn export_name(self) -> &'static str {
+        match self {
+            FieldId::Voltage => "voltage",
+            FieldId::Current => "current",
+            FieldId::Resistance => "resistance",
+            FieldId::Power => "power",
+            FieldId::Area => "area",
+        }
+    }
+
+    /// Kind of the presets offered next to the field, `None` for the area.
+    fn kind(self) -> Option<Kind> {
+        match self {
+            FieldId::Voltage => Some(Kind::Voltage),
+            FieldId::Current => Some(Kind::Current),
+            FieldId::Resistance => Some(Kind::Resistance),
+            FieldId::Power => Some(Kind::Power),
+            FieldId::Area => None,
         }
     }
+
+    /// Name of the input widget, see [`crate::focus`].
+    pub fn input_id(self) -> &'static str {
+        match self {
+            FieldId::Voltage => "ohm_law.voltage",
+            FieldId::Current => "ohm_law.current",
+            FieldId::Resistance => "ohm_law.resistance",
+            FieldId::Power => "ohm_law.power",
+            FieldId::Area => "ohm_law.area",
+        }
+    }
+}
+
+impl fmt::Display for FieldId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            FieldId::Voltage => Key::Voltage,
+            FieldId::Current => Key::Current,
+            FieldId::Resistance => Key::Resistance,
+            FieldId::Power => Key::Power,
+            FieldId::Area => Key::Area,
+        };
+        write!(f, "{}", t(key))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -97,14 +552,141 @@ pub enum Message {
     InputCurrentChanged(String),
     InputResistanceChanged(String),
     InputPowerChanged(String),
+    InputAreaChanged(String),
+    ClearField(FieldId),
+    /// A prefix picked next to a field, see [`Dim::append_to`].
+    PrefixSelected(FieldId, Dim),
+    /// A preset picked next to a field, its value replaces the entered text.
+    PresetSelected(FieldId, Preset),
+    LockToggled(FieldId),
+    ResultRowSelected(usize),
+    MeasurementModeToggled(bool),
+    ShowWorkToggled(bool),
+    SweepModeToggled(bool),
+    SweepVariableSelected(FieldId),
+    InputSweepChanged(String),
+    /// Handled by the application: copies [`OhmLaw::sweep_csv`] to the clipboard.
+    CopySweepCsv,
+    /// Handled by the application: copies [`OhmLaw::result_json`] to the clipboard.
+    ExportJson,
+    /// Right click on a result cell. Handled by the application: copies the cell as shown,
+    /// or the exact number with Shift held.
+    CopyCell(CellCopy),
+    /// Enter in a field. Handled by the application: focuses [`OhmLaw::next_field`].
+    FieldSubmitted(FieldId),
+    StandardValuesToggled,
+    TransposedToggled(bool),
+    RowsMenuToggled,
+    RowVisibilityToggled(ResultRow),
+    /// Handled by the application: copies [`OhmLaw::error_report`] of the field.
+    CopyErrorReport(FieldId),
+    FrozenToggled(bool),
+    PinResult,
+    Unpin,
+    /// Replaces the scene with [`EXAMPLE`] solved.
+    LoadExample,
+}
+
+impl Message {
+    /// Text entered in `field`.
+    fn input(field: FieldId, s: String) -> Message {
+        match field {
+            FieldId::Voltage => Message::InputVoltageChanged(s),
+            FieldId::Current => Message::InputCurrentChanged(s),
+            FieldId::Resistance => Message::InputResistanceChanged(s),
+            FieldId::Power => Message::InputPowerChanged(s),
+            FieldId::Area => Message::InputAreaChanged(s),
+        }
+    }
+
+    /// Message of `event` of the input of `field`.
+    fn from_input(field: FieldId, event: Event) -> Message {
+        match event {
+            Event::Input(s) => Message::input(field, s),
+            Event::Submit => Message::FieldSubmitted(field),
+            Event::Clear => Message::ClearField(field),
+            Event::Prefix(dim) => Message::PrefixSelected(field, dim),
+            Event::Preset(preset) => Message::PresetSelected(field, preset),
+            Event::LockToggled => Message::LockToggled(field),
+            Event::CopyErrorReport => Message::CopyErrorReport(field),
+        }
+    }
+
+    /// Entered text carried by the message, e.g. to cut a paste over the input limit.
+    pub fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Message::InputVoltageChanged(s)
+            | Message::InputCurrentChanged(s)
+            | Message::InputResistanceChanged(s)
+            | Message::InputPowerChanged(s)
+            | Message::InputAreaChanged(s)
+            | Message::InputSweepChanged(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// `true` for a keystroke in an input field, see [`OhmLaw::store_text`].
+    pub fn is_text_input(&self) -> bool {
+        matches!(
+            self,
+            Message::InputVoltageChanged(_)
+                | Message::InputCurrentChanged(_)
+                | Message::InputResistanceChanged(_)
+                | Message::InputPowerChanged(_)
+                | Message::InputAreaChanged(_)
+        )
+    }
 }
 
+/// A worked example for new users: 12V across a load drawing 2A.
+const EXAMPLE: [(FieldId, &str); 2] = [(FieldId::Voltage, "12"), (FieldId::Current, "2")];
+
+// Строки таблицы результатов, которые зависят от стороны допуска
+const RESULT_ROW_BOUNDS: [Option<Bound>; 7] = [
+    None,               // Value nom
+    Some(Bound::Plus),  // Value max
+    Some(Bound::Minus), // Value min
+    Some(Bound::Plus),  // Tol plus
+    Some(Bound::Minus), // Tol minus
+    Some(Bound::Plus),  // Tol plus, %
+    Some(Bound::Minus), // Tol minus, %
+];
+
 impl OhmLaw {
+    /// The scene with the fields filled in as if typed, e.g. from the launch arguments. A
+    /// value that doesn't parse or goes into a computed field is skipped; the notices say
+    /// which and why.
+    pub fn prefilled(inputs: Vec<(FieldId, String)>) -> (OhmLaw, Vec<String>) {
+        let mut ohm_law = OhmLaw::default();
+        let mut notices = Vec::new();
+
+        for (field, raw) in inputs {
+            let arg = format!("--{}", field.export_name());
+            if !ohm_law.is_editable(field) {
+                notices.push(format!("{}: {}", arg, t(Key::FieldComputed)));
+                continue;
+            }
+
+            ohm_law.update(Message::input(field, raw));
+            if let Some(error) = ohm_law.parse_error(field) {
+                notices.push(format!("{}: {}", arg, error));
+                ohm_law.update(Message::input(field, String::new()));
+            }
+        }
+
+        (ohm_law, notices)
+    }
+
     pub fn title(&self) -> String {
-        String::from("Ohm Law")
+        String::from(t(Key::OhmLaw))
     }
 
     pub fn update(&mut self, message: Message) {
+        // ввод запоминается без разбора, как при задержке разбора
+        if self.frozen && message.is_text_input() {
+            return self.store_text(message);
+        }
+
         match message {
             Message::InputVoltageChanged(s) => {
                 self.data_raw.voltage = s;
@@ -122,388 +704,1505 @@ impl OhmLaw {
                 self.data_raw.power = s;
                 self.data.power = self.data_raw.power.parse::<Power>();
             }
+            Message::InputAreaChanged(s) => {
+                self.area = parse_area(&s);
+                self.area_raw = s;
+            }
+            Message::ClearField(field) if self.is_locked(field) => return,
+            Message::ClearField(field) => {
+                // как ввод пустой строки: поле разбирается заново, и блокировка снимается
+                return self.update(Message::input(field, String::new()));
+            }
+            Message::PrefixSelected(field, _) if !self.is_editable(field) => return,
+            Message::PrefixSelected(field, dim) => {
+                let raw = dim.append_to(self.raw(field));
+                return self.update(Message::input(field, raw));
+            }
+            Message::PresetSelected(field, _) if !self.is_editable(field) => return,
+            Message::PresetSelected(field, preset) => {
+                let raw = field.kind().and_then(|kind| preset.insert_into(kind));
+                if let Some(raw) = raw {
+                    return self.update(Message::input(field, raw.to_string()));
+                }
+                return;
+            }
+            Message::LockToggled(field) => {
+                if self.is_locked(field) {
+                    self.locked.retain(|f| *f != field);
+                } else {
+                    self.locked.push(field);
+                }
+                return;
+            }
+            Message::ResultRowSelected(row) => {
+                self.selected_row = if self.selected_row == Some(row) {
+                    None
+                } else {
+                    Some(row)
+                };
+                return;
+            }
+            Message::MeasurementModeToggled(enable) => self.measurement_mode = enable,
+            Message::ShowWorkToggled(enable) => {
+                self.show_work = enable;
+                return;
+            }
+            Message::SweepModeToggled(enable) => self.sweep.enabled = enable,
+            Message::SweepVariableSelected(variable) => self.sweep.variable = variable,
+            Message::InputSweepChanged(s) => {
+                self.sweep.sweep = sweep::parse_sweep(&s);
+                self.sweep.raw = s;
+            }
+            Message::CopySweepCsv
+            | Message::ExportJson
+            | Message::CopyCell(_)
+            | Message::FieldSubmitted(_)
+            | Message::CopyErrorReport(_) => return,
+            Message::StandardValuesToggled => {
+                self.standard_values = !self.standard_values;
+                return;
+            }
+            Message::TransposedToggled(transposed) => {
+                self.transposed = transposed;
+                return;
+            }
+            Message::RowsMenuToggled => {
+                self.rows_menu = !self.rows_menu;
+                return;
+            }
+            Message::RowVisibilityToggled(row) => {
+                let mut hidden = result_rows::hidden_rows();
+                hidden.ohm_law = hidden.ohm_law.toggled(row);
+                result_rows::set_hidden_rows(hidden);
+
+                // скрытую строку нельзя снять с выбора
+                if self
+                    .selected_row
+                    .is_some_and(|id| hidden.ohm_law.contains_index(id))
+                {
+                    self.selected_row = None;
+                }
+                return;
+            }
+            Message::FrozenToggled(frozen) => {
+                self.frozen = frozen;
+                if frozen {
+                    // пока расчёт стоит, все поля открыты для ввода
+                    self.fields_enable = FieldsEnable::default();
+                } else {
+                    self.recalculate();
+                }
+                return;
+            }
+            Message::PinResult => {
+                self.pinned = Some(self.snapshot());
+                return;
+            }
+            Message::Unpin => {
+                self.pinned = None;
+                return;
+            }
+            Message::LoadExample => {
+                let inputs = EXAMPLE.map(|(field, raw)| (field, raw.to_string()));
+                (*self, _) = Self::prefilled(inputs.into());
+                return;
+            }
+        }
+
+        self.dirty = self.has_input();
+        if self.frozen {
+            return;
         }
 
         self.determine_calctype();
         self.update_field_accessibility();
         self.calculating();
+        self.check_plausibility();
     }
 
-    fn determine_calctype(&mut self) {
-        let voltage_filled = !self.data_raw.voltage.trim().is_empty() && self.data.voltage.is_ok();
-        let current_filled = !self.data_raw.current.trim().is_empty() && self.data.current.is_ok();
-        let resistance_filled =
-            !self.data_raw.resistance.trim().is_empty() && self.data.resistance.is_ok();
-        let power_filled = !self.data_raw.power.trim().is_empty() && self.data.power.is_ok();
-
-        match (
-            voltage_filled,
-            current_filled,
-            resistance_filled,
-            power_filled,
-        ) {
-            (true, true, _, _) => self.calc_type = CalcType::VCRP,
-            (true, _, true, _) => self.calc_type = CalcType::VRCP,
-            (true, _, _, true) => self.calc_type = CalcType::VPCR,
-            (_, true, true, _) => self.calc_type = CalcType::CRVP,
-            (_, true, _, true) => self.calc_type = CalcType::CPVR,
-            (_, _, true, true) => self.calc_type = CalcType::RPVC,
-            _ => self.calc_type = CalcType::None,
+    /// Stores the text of a keystroke without parsing it, for the input debounce: the scene
+    /// is parsed and solved later by [`Self::recalculate`]. Other messages are updated as
+    /// usual.
+    pub fn store_text(&mut self, message: Message) {
+        let (field, text) = match message {
+            Message::InputVoltageChanged(s) => (FieldId::Voltage, s),
+            Message::InputCurrentChanged(s) => (FieldId::Current, s),
+            Message::InputResistanceChanged(s) => (FieldId::Resistance, s),
+            Message::InputPowerChanged(s) => (FieldId::Power, s),
+            Message::InputAreaChanged(s) => (FieldId::Area, s),
+            other => return self.update(other),
+        };
+
+        *self.raw_mut(field) = text;
+        self.dirty = self.has_input();
+    }
+
+    // расчётные поля не учитываются: важен только ввод пользователя
+    fn has_input(&self) -> bool {
+        !self.data_raw.is_empty()
+            || !self.area_raw.trim().is_empty()
+            || !self.sweep.raw.trim().is_empty()
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot::capture(
+            &self.data.voltage,
+            &self.data.current,
+            &self.data.resistance,
+            &self.data.power,
+        )
+    }
+
+    /// JSON of the result: nominal value, bounds and tolerance of the four quantities, each
+    /// with its provenance, and the calculation type. The values of the rows hidden from the
+    /// result table are left out unless `include_hidden`.
+    pub fn result_json(&self, include_hidden: bool) -> String {
+        // без расчёта в данных могут остаться значения прошлого расчёта
+        let solved = !matches!(self.calc_type, CalcType::None);
+        let [voltage, current, resistance, power] = self.filled().map(|filled| filled || solved);
+        let value = |field: FieldId, bounds: Result<Bounds, ParserError>, solved: bool| {
+            bounds.ok().filter(|_| solved).map(|bounds| ExportedValue {
+                bounds,
+                provenance: provenance(self.calc_type, field, |f| self.raw(f).to_string()),
+            })
+        };
+
+        ResultSnapshot {
+            schema: RESULT_SCHEMA,
+            calc_type: self.calc_type,
+            voltage: value(
+                FieldId::Voltage,
+                report::output(&self.data.voltage),
+                voltage,
+            ),
+            current: value(
+                FieldId::Current,
+                report::output(&self.data.current),
+                current,
+            ),
+            resistance: value(
+                FieldId::Resistance,
+                report::output(&self.data.resistance),
+                resistance,
+            ),
+            power: value(FieldId::Power, report::output(&self.data.power), power),
+        }
+        .to_json(match include_hidden {
+            true => RowSet::EMPTY,
+            false => result_rows::hidden_rows().ohm_law,
+        })
+    }
+
+    /// The message of the field if its input does not parse, e.g. "unexpected character 'x'
+    /// at position 4". An empty field is not an error.
+    fn parse_error(&self, field: FieldId) -> Option<&str> {
+        let error = match field {
+            FieldId::Voltage => self.data.voltage.as_ref().err(),
+            FieldId::Current => self.data.current.as_ref().err(),
+            FieldId::Resistance => self.data.resistance.as_ref().err(),
+            FieldId::Power => self.data.power.as_ref().err(),
+            FieldId::Area => self.area.as_ref().err(),
+        };
+
+        match error? {
+            ParserError::IncorrectInput(message) => Some(message),
+            ParserError::EmptyInput => None,
+        }
+    }
+
+    /// Report of the parse failure of the field for a bug report, `None` if it parses.
+    pub fn error_report(&self, field: FieldId) -> Option<String> {
+        let message = self.parse_error(field)?;
+
+        Some(report::parse_error(
+            field.export_name(),
+            self.raw(field),
+            message,
+        ))
+    }
+
+    /// Diagnostic JSON report of the scene: raw inputs, their parse results, the calculation
+    /// type and every output with its tolerance bounds.
+    pub fn diagnostic_report(&self) -> String {
+        #[derive(Serialize)]
+        struct Parsed {
+            voltage: Result<Voltage, ParserError>,
+            current: Result<Current, ParserError>,
+            resistance: Result<Resistance, ParserError>,
+            power: Result<Power, ParserError>,
+        }
+
+        #[derive(Serialize)]
+        struct Outputs {
+            voltage: Result<Bounds, ParserError>,
+            current: Result<Bounds, ParserError>,
+            resistance: Result<Bounds, ParserError>,
+            power: Result<Bounds, ParserError>,
+        }
+
+        #[derive(Serialize)]
+        struct State<'a> {
+            inputs: &'a OhmDataRaw,
+            area_raw: &'a str,
+            parsed: Parsed,
+            calc_type: CalcType,
+            measurement_mode: bool,
+            outputs: Outputs,
+            overdetermined: &'a OverdeterminedResult,
+        }
+
+        let state = State {
+            inputs: &self.data_raw,
+            area_raw: &self.area_raw,
+            parsed: Parsed {
+                voltage: self.data_raw.voltage.parse(),
+                current: self.data_raw.current.parse(),
+                resistance: self.data_raw.resistance.parse(),
+                power: self.data_raw.power.parse(),
+            },
+            calc_type: self.calc_type,
+            measurement_mode: self.measurement_mode,
+            outputs: Outputs {
+                voltage: report::output(&self.data.voltage),
+                current: report::output(&self.data.current),
+                resistance: report::output(&self.data.resistance),
+                power: report::output(&self.data.power),
+            },
+            overdetermined: &self.overdetermined,
+        };
+
+        report::to_json("ohm_law", state)
+    }
+
+    /// Parses all inputs again and recalculates the results, e.g. after a global setting
+    /// changed. The entered text is kept as is. Frozen results are not recalculated.
+    pub fn recalculate(&mut self) {
+        if self.frozen {
+            return;
+        }
+
+        self.data.voltage = self.data_raw.voltage.parse::<Voltage>();
+        self.data.current = self.data_raw.current.parse::<Current>();
+        self.data.resistance = self.data_raw.resistance.parse::<Resistance>();
+        self.data.power = self.data_raw.power.parse::<Power>();
+        self.area = parse_area(&self.area_raw);
+
+        self.determine_calctype();
+        self.update_field_accessibility();
+        self.calculating();
+        self.check_plausibility();
+    }
+
+    /// Checks the entered values against the plausibility limits. Calculated values are not
+    /// checked: the warning points at the field with the typo.
+    fn check_plausibility(&mut self) {
+        fn check<M: Measurement>(raw: &str, value: &Result<M, ParserError>) -> Option<String> {
+            if raw.trim().is_empty() {
+                return None;
+            }
+            let value = value.as_ref().ok()?;
+            plausibility::check_unit(raw, value)
+                .or_else(|| plausibility::check(value, &plausibility::limits()))
+                .or_else(|| plausibility::check_tolerance(value))
+        }
+
+        self.warnings = [
+            check(&self.data_raw.voltage, &self.data.voltage),
+            check(&self.data_raw.current, &self.data.current),
+            check(&self.data_raw.resistance, &self.data.resistance),
+            check(&self.data_raw.power, &self.data.power),
+        ];
+        self.aux.density_warning = self.aux.current_density.and_then(|density| {
+            plausibility::check_current_density(density.nominal, &plausibility::limits())
+        });
+    }
+
+    /// `true` if the scene holds user input that would be lost on exit.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// The result was exported, the input is no longer lost on exit until it changes again.
+    pub fn mark_exported(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Status of the scene for the sidebar badge.
+    pub fn status(&self) -> SceneStatus {
+        let data = [
+            self.data.voltage.as_ref().err(),
+            self.data.current.as_ref().err(),
+            self.data.resistance.as_ref().err(),
+            self.data.power.as_ref().err(),
+        ];
+
+        if self.data_raw.is_empty() {
+            SceneStatus::Empty
+        } else if data
+            .iter()
+            .chain([&self.area.as_ref().err()])
+            .any(|e| matches!(e, Some(ParserError::IncorrectInput(_))))
+        {
+            SceneStatus::Error
+        } else if matches!(self.calc_type, CalcType::None) {
+            SceneStatus::Incomplete
+        } else {
+            SceneStatus::Solved
         }
     }
 
+    fn determine_calctype(&mut self) {
+        self.calc_type = calc_type_of(self.filled());
+    }
+
     fn update_field_accessibility(&mut self) {
+        // в режиме измерений все поля остаются доступными для ввода
+        if self.measurement_mode {
+            self.fields_enable = FieldsEnable::default();
+            return;
+        }
+
         match self.calc_type {
-            CalcType::VCRP => {
+            CalcType::VcRp => {
                 self.fields_enable.resistance = false;
                 self.fields_enable.power = false;
 
-                self.data_raw.resistance.clear();
-                self.data_raw.power.clear();
+                self.clear_unlocked(FieldId::Resistance);
+                self.clear_unlocked(FieldId::Power);
             }
-            CalcType::VRCP => {
+            CalcType::VrCp => {
                 self.fields_enable.current = false;
                 self.fields_enable.power = false;
 
-                self.data_raw.current.clear();
-                self.data_raw.power.clear();
+                self.clear_unlocked(FieldId::Current);
+                self.clear_unlocked(FieldId::Power);
             }
-            CalcType::VPCR => {
+            CalcType::VpCr => {
                 self.fields_enable.current = false;
                 self.fields_enable.resistance = false;
 
-                self.data_raw.current.clear();
-                self.data_raw.resistance.clear();
+                self.clear_unlocked(FieldId::Current);
+                self.clear_unlocked(FieldId::Resistance);
             }
-            CalcType::CRVP => {
+            CalcType::CrVp => {
                 self.fields_enable.voltage = false;
                 self.fields_enable.power = false;
 
-                self.data_raw.voltage.clear();
-                self.data_raw.power.clear();
+                self.clear_unlocked(FieldId::Voltage);
+                self.clear_unlocked(FieldId::Power);
             }
-            CalcType::CPVR => {
+            CalcType::CpVr => {
                 self.fields_enable.voltage = false;
                 self.fields_enable.resistance = false;
 
-                self.data_raw.resistance.clear();
-                self.data_raw.voltage.clear();
+                self.clear_unlocked(FieldId::Resistance);
+                self.clear_unlocked(FieldId::Voltage);
             }
-            CalcType::RPVC => {
+            CalcType::RpVc => {
                 self.fields_enable.voltage = false;
                 self.fields_enable.current = false;
 
-                self.data_raw.voltage.clear();
-                self.data_raw.current.clear();
+                self.clear_unlocked(FieldId::Voltage);
+                self.clear_unlocked(FieldId::Current);
+            }
+            CalcType::None | CalcType::Overdetermined => {
+                self.fields_enable = FieldsEnable::default()
             }
-            CalcType::None => self.fields_enable = FieldsEnable::default(),
+        }
+    }
+
+    fn is_locked(&self, field: FieldId) -> bool {
+        self.locked.contains(&field)
+    }
+
+    /// Clears the text of a field blocked by the calculation type and its value, so the field
+    /// shows no error or hint of the text it held. A locked field keeps both, even if its text
+    /// does not parse.
+    fn clear_unlocked(&mut self, field: FieldId) {
+        if self.is_locked(field) {
+            return;
+        }
+
+        self.raw_mut(field).clear();
+        // значение рассчитывается заново, если расчёт возможен
+        match field {
+            FieldId::Voltage => self.data.voltage = Err(ParserError::EmptyInput),
+            FieldId::Current => self.data.current = Err(ParserError::EmptyInput),
+            FieldId::Resistance => self.data.resistance = Err(ParserError::EmptyInput),
+            FieldId::Power => self.data.power = Err(ParserError::EmptyInput),
+            FieldId::Area => self.area = Err(ParserError::EmptyInput),
+        }
+    }
+
+    /// `true` if the field takes input: neither blocked by the calculation type nor locked.
+    fn is_editable(&self, field: FieldId) -> bool {
+        let enable = match field {
+            FieldId::Voltage => self.fields_enable.voltage,
+            FieldId::Current => self.fields_enable.current,
+            FieldId::Resistance => self.fields_enable.resistance,
+            FieldId::Power => self.fields_enable.power,
+            FieldId::Area => true,
+        };
+
+        enable && !self.is_locked(field)
+    }
+
+    /// The field Enter in `field` moves the focus to: the next editable one of the form, from
+    /// the last field back to the first. `None` if no other field is editable.
+    pub fn next_field(&self, field: FieldId) -> Option<FieldId> {
+        let position = FieldId::FORM.iter().position(|f| *f == field)?;
+
+        FieldId::FORM
+            .iter()
+            .cycle()
+            .skip(position + 1)
+            .take(FieldId::FORM.len() - 1)
+            .copied()
+            .find(|f| self.is_editable(*f))
+    }
+
+    fn raw(&self, field: FieldId) -> &str {
+        match field {
+            FieldId::Voltage => &self.data_raw.voltage,
+            FieldId::Current => &self.data_raw.current,
+            FieldId::Resistance => &self.data_raw.resistance,
+            FieldId::Power => &self.data_raw.power,
+            FieldId::Area => &self.area_raw,
+        }
+    }
+
+    fn raw_mut(&mut self, field: FieldId) -> &mut String {
+        match field {
+            FieldId::Voltage => &mut self.data_raw.voltage,
+            FieldId::Current => &mut self.data_raw.current,
+            FieldId::Resistance => &mut self.data_raw.resistance,
+            FieldId::Power => &mut self.data_raw.power,
+            FieldId::Area => &mut self.area_raw,
         }
     }
 
     fn calculating(&mut self) {
-        match self.calc_type {
-            CalcType::VCRP => {
-                if let (Ok(voltage), Ok(current)) =
-                    (self.data.voltage.clone(), self.data.current.clone())
-                {
-                    self.data.resistance = Ok(voltage / current);
-                    self.data.power = Ok(voltage * current);
+        self.calculating_provenance();
+
+        if let CalcType::Overdetermined = self.calc_type {
+            self.calculating_overdetermined();
+        } else if Self::zero_divisor(self.calc_type, &self.data) {
+            // нулевой делитель: рассчитываемые величины остаются пустыми
+            self.overdetermined = OverdeterminedResult::default();
+            Self::clear_calculated(self.calc_type, &mut self.data);
+        } else {
+            self.overdetermined = OverdeterminedResult::default();
+            self.data = Self::solve(self.calc_type, &self.data);
+        }
+        self.steps = Self::work_steps(self.calc_type, &self.data);
+        self.sweep.rows = self.calculating_sweep();
+
+        self.calculating_aux();
+    }
+
+    /// Derived outputs of the solved values: the conductance, the power rating and supply
+    /// current with the [`margins`] and, if an area is entered, the current density.
+    fn calculating_aux(&mut self) {
+        let conductance = self
+            .data
+            .resistance
+            .as_ref()
+            .ok()
+            .and_then(Resistance::conductance);
+        let current_density = match (&self.data.current, &self.area) {
+            (Ok(current), Ok(area)) => current_density(current, *area),
+            _ => None,
+        };
+
+        let margins = margins::margins();
+        let power_rating = self.data.power.as_ref().ok().map(|power| Power {
+            value: power.value * margins.derating,
+            tolerance: power.tolerance,
+        });
+        let supply_current = self.data.current.as_ref().ok().map(|current| Current {
+            value: current.value * margins.headroom,
+            tolerance: current.tolerance,
+        });
+
+        self.aux = AuxOutputs {
+            conductance,
+            power_rating,
+            supply_current,
+            current_density,
+            density_warning: None,
+        };
+    }
+
+    /// `true` if solving the pair of `calc_type` would divide by a zero input: V / I, P / V,
+    /// P / I, V / R or P / R.
+    fn zero_divisor(calc_type: CalcType, data: &OhmData) -> bool {
+        fn zero<M: Measurement>(value: &Result<M, ParserError>) -> bool {
+            matches!(value, Ok(value) if value.get_nominal_value() == 0.0)
+        }
+
+        match calc_type {
+            CalcType::VcRp | CalcType::CpVr => zero(&data.current),
+            CalcType::VrCp | CalcType::RpVc => zero(&data.resistance),
+            // при P = 0 ток нулевой, и R = V / I делит на ноль
+            CalcType::VpCr => zero(&data.voltage) || zero(&data.power),
+            CalcType::CrVp | CalcType::None | CalcType::Overdetermined => false,
+        }
+    }
+
+    /// Empties the quantities `calc_type` calculates, the inputs of the pair are kept.
+    fn clear_calculated(calc_type: CalcType, data: &mut OhmData) {
+        let calculated = |field| calc_type.formula(field).is_some();
+        if calculated(FieldId::Voltage) {
+            data.voltage = Err(ParserError::EmptyInput);
+        }
+        if calculated(FieldId::Current) {
+            data.current = Err(ParserError::EmptyInput);
+        }
+        if calculated(FieldId::Resistance) {
+            data.resistance = Err(ParserError::EmptyInput);
+        }
+        if calculated(FieldId::Power) {
+            data.power = Err(ParserError::EmptyInput);
+        }
+    }
+
+    /// Calculates the missing quantities from the pair of inputs selected by `calc_type`.
+    fn solve(calc_type: CalcType, data: &OhmData) -> OhmData {
+        let mut data = data.clone();
+
+        match calc_type {
+            CalcType::VcRp => {
+                if let (Ok(voltage), Ok(current)) = (data.voltage.clone(), data.current.clone()) {
+                    data.resistance = Ok(voltage / current);
+                    data.power = Ok(voltage * current);
                 }
             }
-            CalcType::VRCP => {
+            CalcType::VrCp => {
                 if let (Ok(voltage), Ok(resistance)) =
-                    (self.data.voltage.clone(), self.data.resistance.clone())
+                    (data.voltage.clone(), data.resistance.clone())
                 {
+                    // при R = 0 ток не определён, поля остаются пустыми
+                    if resistance.value == 0.0 {
+                        return data;
+                    }
                     let current = voltage / resistance;
 
-                    self.data.current = Ok(current);
-                    self.data.power = Ok(voltage * current);
+                    data.current = Ok(current);
+                    data.power = Ok(voltage * current);
                 }
             }
-            CalcType::VPCR => {
-                if let (Ok(voltage), Ok(power)) =
-                    (self.data.voltage.clone(), self.data.power.clone())
-                {
+            CalcType::VpCr => {
+                if let (Ok(voltage), Ok(power)) = (data.voltage.clone(), data.power.clone()) {
                     let current = power / voltage;
 
-                    self.data.current = Ok(current);
-                    self.data.resistance = Ok(voltage / current);
+                    data.current = Ok(current);
+                    data.resistance = Ok(voltage / current);
                 }
             }
-            CalcType::CRVP => {
+            CalcType::CrVp => {
                 if let (Ok(resistance), Ok(current)) =
-                    (self.data.resistance.clone(), self.data.current.clone())
+                    (data.resistance.clone(), data.current.clone())
                 {
                     let voltage = current * resistance;
 
-                    self.data.voltage = Ok(voltage);
-                    self.data.power = Ok(voltage * current);
+                    data.voltage = Ok(voltage);
+                    data.power = Ok(voltage * current);
                 }
             }
-            CalcType::CPVR => {
-                if let (Ok(power), Ok(current)) =
-                    (self.data.power.clone(), self.data.current.clone())
-                {
-                    let voltage = power * current;
+            CalcType::CpVr => {
+                if let (Ok(power), Ok(current)) = (data.power.clone(), data.current.clone()) {
+                    let voltage = power / current;
 
-                    self.data.voltage = Ok(voltage);
-                    self.data.resistance = Ok(voltage / current);
+                    data.voltage = Ok(voltage);
+                    data.resistance = Ok(voltage / current);
                 }
             }
-            CalcType::RPVC => {
-                if let (Ok(power), Ok(resistance)) =
-                    (self.data.power.clone(), self.data.resistance.clone())
-                {
-                    let voltage = Voltage {
-                        value: (power.value * resistance.value).sqrt(),
-                        tolerance: None,
-                    };
-                    let current = Current {
-                        value: (power.value / resistance.value).sqrt(),
-                        tolerance: None,
-                    };
-
-                    self.data.voltage = Ok(voltage);
-                    self.data.current = Ok(current);
+            CalcType::RpVc => {
+                if let (Ok(power), Ok(resistance)) = (data.power.clone(), data.resistance.clone()) {
+                    // при R <= 0 корень не определён, поля остаются пустыми
+                    if resistance.value <= 0.0 {
+                        return data;
+                    }
+                    let (product, tol) =
+                        calculate_multiplication_with_tolerance(&power, &resistance);
+                    let (value, tolerance) = calculate_square_root_with_tolerance(product, tol);
+                    let voltage = Voltage { value, tolerance };
+
+                    let (quotient, tol) = calculate_division_with_tolerance(&power, &resistance);
+                    let (value, tolerance) = calculate_square_root_with_tolerance(quotient, tol);
+                    let current = Current { value, tolerance };
+
+                    data.voltage = Ok(voltage);
+                    data.current = Ok(current);
                 }
             }
-            CalcType::None => (),
+            CalcType::None | CalcType::Overdetermined => (),
         }
-    }
 
-    pub fn view(&self) -> Element<Message> {
-        Column::new()
-            .push(self.view_form())
-            .push(self.view_result())
-            .into()
+        data
     }
 
-    fn view_result(&self) -> Element<Message> {
-        fn format_measurement<T: Measurement, E>(data: Result<T, E>) -> (String, String, String) {
-            match data {
-                Ok(measurement) => (
-                    measurement.get_value_nom(),
-                    measurement.get_value_min(),
-                    measurement.get_value_max(),
-                ),
-                Err(_) => ("N/A".to_string(), "N/A".to_string(), "N/A".to_string()),
-            }
-        }
-        fn format_tol<T: Measurement, E>(data: Result<T, E>) -> (String, String, String, String) {
-            match data {
-                Ok(measurement) => (
-                    measurement.get_tol_value_plus(),
-                    measurement.get_tol_value_minus(),
-                    measurement.get_tol_percent_plus(),
-                    measurement.get_tol_percent_minus(),
-                ),
-                Err(_) => (
-                    "N/A".to_string(),
-                    "N/A".to_string(),
-                    "N/A".to_string(),
-                    "N/A".to_string(),
-                ),
-            }
-        }
-
-        let (voltage_nom, voltage_min, voltage_max) = format_measurement(self.data.voltage.clone());
-        let (voltage_tol_plus, voltage_tol_minus, voltage_tol_plus_p, voltage_tol_minus_p) =
-            format_tol(self.data.voltage.clone());
-
-        let (current_nom, current_min, current_max) = format_measurement(self.data.current.clone());
-        let (current_tol_plus, current_tol_minus, current_tol_plus_p, current_tol_minus_p) =
-            format_tol(self.data.current.clone());
-
-        let (resistance_nom, resistance_min, resistance_max) =
-            format_measurement(self.data.resistance.clone());
-        let (
-            resistance_tol_plus,
-            resistance_tol_minus,
-            resistance_tol_plus_p,
-            resistance_tol_minus_p,
-        ) = format_tol(self.data.resistance.clone());
-
-        let (power_nom, power_min, power_max) = format_measurement(self.data.power.clone());
-        let (power_tol_plus, power_tol_minus, power_tol_plus_p, power_tol_minus_p) =
-            format_tol(self.data.power.clone());
-
-        let data = vec![
-            vec![
-                "Value nom".to_string(),
-                voltage_nom,
-                current_nom,
-                resistance_nom,
-                power_nom,
-            ],
-            vec![
-                "Value max".to_string(),
-                voltage_max,
-                current_max,
-                resistance_max,
-                power_max,
+    /// Formulas of the solved quantities with the nominal values substituted, e.g.
+    /// "R = V / I = 10.00V / 2.00A = 5.00R". Empty unless exactly two values are entered.
+    fn work_steps(calc_type: CalcType, data: &OhmData) -> Vec<String> {
+        let (Ok(v), Ok(i), Ok(r), Ok(p)) =
+            (&data.voltage, &data.current, &data.resistance, &data.power)
+        else {
+            return Vec::new();
+        };
+        let (v, i, r, p) = (
+            v.get_value_nom(),
+            i.get_value_nom(),
+            r.get_value_nom(),
+            p.get_value_nom(),
+        );
+        let step = |formula: &str, substituted: String, result: &str| {
+            format!("{} = {} = {}", formula, substituted, result)
+        };
+
+        match calc_type {
+            CalcType::VcRp => vec![
+                step("R = V / I", format!("{} / {}", v, i), &r),
+                step("P = V × I", format!("{} × {}", v, i), &p),
             ],
-            vec![
-                "Value min".to_string(),
-                voltage_min,
-                current_min,
-                resistance_min,
-                power_min,
+            CalcType::VrCp => vec![
+                step("I = V / R", format!("{} / {}", v, r), &i),
+                step("P = V × I", format!("{} × {}", v, i), &p),
             ],
-            vec![
-                "Tol plus".to_string(),
-                voltage_tol_plus,
-                current_tol_plus,
-                resistance_tol_plus,
-                power_tol_plus,
+            CalcType::VpCr => vec![
+                step("I = P / V", format!("{} / {}", p, v), &i),
+                step("R = V / I", format!("{} / {}", v, i), &r),
             ],
-            vec![
-                "Tol minus".to_string(),
-                voltage_tol_minus,
-                current_tol_minus,
-                resistance_tol_minus,
-                power_tol_minus,
+            CalcType::CrVp => vec![
+                step("V = I × R", format!("{} × {}", i, r), &v),
+                step("P = V × I", format!("{} × {}", v, i), &p),
             ],
-            vec![
-                "Tol plus, %".to_string(),
-                voltage_tol_plus_p,
-                current_tol_plus_p,
-                resistance_tol_plus_p,
-                power_tol_plus_p,
+            CalcType::CpVr => vec![
+                step("V = P / I", format!("{} / {}", p, i), &v),
+                step("R = V / I", format!("{} / {}", v, i), &r),
             ],
-            vec![
-                "Tol minus, %".to_string(),
-                voltage_tol_minus_p,
-                current_tol_minus_p,
-                resistance_tol_minus_p,
-                power_tol_minus_p,
+            CalcType::RpVc => vec![
+                step("V = √(P × R)", format!("√({} × {})", p, r), &v),
+                step("I = √(P / R)", format!("√({} / {})", p, r), &i),
             ],
+            CalcType::None | CalcType::Overdetermined => Vec::new(),
+        }
+    }
+
+    /// Solves every sweep point: the swept input takes the point as its nominal value and
+    /// keeps its entered tolerance, the pair is the swept input and the other entered value.
+    /// Empty unless exactly one other value is entered.
+    fn calculating_sweep(&self) -> Vec<OhmData> {
+        let sweep = match &self.sweep.sweep {
+            Ok(sweep) if self.sweep.enabled => sweep,
+            _ => return Vec::new(),
+        };
+
+        let variable = self.sweep.variable;
+        let calc_type = self.sweep_calc_type();
+        if matches!(calc_type, CalcType::None | CalcType::Overdetermined) {
+            return Vec::new();
+        }
+
+        fn tolerance<M: Measurement>(value: &Result<M, ParserError>) -> Option<Tolerance> {
+            value.as_ref().ok().and_then(Measurement::get_tolerance)
+        }
+
+        sweep
+            .points
+            .iter()
+            .map(|&value| {
+                let mut data = self.data.clone();
+                match variable {
+                    FieldId::Voltage => {
+                        let tolerance = tolerance(&data.voltage);
+                        data.voltage = Ok(Voltage { value, tolerance });
+                    }
+                    FieldId::Current => {
+                        let tolerance = tolerance(&data.current);
+                        data.current = Ok(Current { value, tolerance });
+                    }
+                    FieldId::Resistance => {
+                        let tolerance = tolerance(&data.resistance);
+                        data.resistance = Ok(Resistance { value, tolerance });
+                    }
+                    FieldId::Power => {
+                        let tolerance = tolerance(&data.power);
+                        data.power = Ok(Power { value, tolerance });
+                    }
+                    FieldId::Area => (),
+                }
+
+                // точка без решения остаётся в таблице без рассчитанных величин, а не
+                // с величинами основного расчёта
+                Self::clear_calculated(calc_type, &mut data);
+                if Self::zero_divisor(calc_type, &data) {
+                    return data;
+                }
+
+                Self::solve(calc_type, &data)
+            })
+            .collect()
+    }
+
+    /// Calculation type of the sweep: the swept input is one of the pair.
+    fn sweep_calc_type(&self) -> CalcType {
+        let mut filled = self.filled();
+        let index = FieldId::SWEEP
+            .iter()
+            .position(|f| *f == self.sweep.variable)
+            .unwrap_or_default();
+        filled[index] = true;
+
+        calc_type_of(filled)
+    }
+
+    /// The sweep table as CSV: one row per point with the nominal values in base units, the
+    /// formula of every calculated quantity and the inputs of the row, e.g.
+    /// `voltage=2;resistance=10 1%` with the swept input at its point.
+    pub fn sweep_csv(&self) -> String {
+        fn value<M: Measurement>(value: &Result<M, ParserError>) -> String {
+            value
+                .as_ref()
+                .map(|v| v.get_nominal_value().to_string())
+                .unwrap_or_default()
+        }
+
+        let calc_type = self.sweep_calc_type();
+        let formulas = FieldId::SWEEP
+            .map(|field| calc_type.formula(field).unwrap_or_default())
+            .join(",");
+        let (a, b) = calc_type.inputs();
+
+        let mut csv = String::from(
+            "voltage_V,current_A,resistance_Ohm,power_W,\
+             voltage_formula,current_formula,resistance_formula,power_formula,inputs\n",
+        );
+        for row in &self.sweep.rows {
+            let values = [
+                value(&row.voltage),
+                value(&row.current),
+                value(&row.resistance),
+                value(&row.power),
+            ];
+            let inputs = [a, b]
+                .map(|index| {
+                    let field = FieldId::SWEEP[index];
+                    let raw = if field == self.sweep.variable {
+                        values[index].as_str()
+                    } else {
+                        self.raw(field)
+                    };
+                    format!("{}={}", field.export_name(), raw)
+                })
+                .join(";");
+
+            csv += &format!("{},{},{}\n", values.join(","), formulas, csv_field(&inputs));
+        }
+
+        csv
+    }
+
+    /// Current values of the scene, for black-box tests that drive it by messages.
+    #[cfg(feature = "test-internals")]
+    pub fn data(&self) -> &OhmData {
+        &self.data
+    }
+
+    /// Steps of the last calculation, see [`OhmLaw::work_steps`].
+    pub fn steps(&self) -> &[String] {
+        &self.steps
+    }
+
+    /// Calculates every quantity from each pair of entered values and checks that the
+    /// candidates agree within their tolerances.
+    fn calculating_overdetermined(&mut self) {
+        let filled = self.filled();
+        let mut result = OverdeterminedResult::default();
+
+        // введенные значения - кандидаты сами для себя
+        if filled[0] {
+            result.voltage.extend(self.data.voltage.clone().ok());
+        }
+        if filled[1] {
+            result.current.extend(self.data.current.clone().ok());
+        }
+        if filled[2] {
+            result.resistance.extend(self.data.resistance.clone().ok());
+        }
+        if filled[3] {
+            result.power.extend(self.data.power.clone().ok());
+        }
+
+        // пара с нулевым делителем не даёт кандидатов, остальные сравниваются
+        for pair in PAIRS {
+            let (a, b) = pair.inputs();
+            if !(filled[a] && filled[b]) || Self::zero_divisor(pair, &self.data) {
+                continue;
+            }
+
+            let solved = Self::solve(pair, &self.data);
+            let calculated = |q: usize| q != a && q != b;
+            if calculated(0) {
+                result.voltage.extend(solved.voltage.ok());
+            }
+            if calculated(1) {
+                result.current.extend(solved.current.ok());
+            }
+            if calculated(2) {
+                result.resistance.extend(solved.resistance.ok());
+            }
+            if calculated(3) {
+                result.power.extend(solved.power.ok());
+            }
+        }
+
+        result.consistent = bands_overlap(&result.voltage)
+            && bands_overlap(&result.current)
+            && bands_overlap(&result.resistance)
+            && bands_overlap(&result.power);
+
+        // единственная не введенная величина берется из первой пары
+        if !filled[0] {
+            self.data.voltage = result
+                .voltage
+                .first()
+                .copied()
+                .ok_or(ParserError::EmptyInput);
+        }
+        if !filled[1] {
+            self.data.current = result
+                .current
+                .first()
+                .copied()
+                .ok_or(ParserError::EmptyInput);
+        }
+        if !filled[2] {
+            self.data.resistance = result
+                .resistance
+                .first()
+                .copied()
+                .ok_or(ParserError::EmptyInput);
+        }
+        if !filled[3] {
+            self.data.power = result.power.first().copied().ok_or(ParserError::EmptyInput);
+        }
+
+        self.overdetermined = result;
+    }
+
+    /// Which of voltage, current, resistance and power are entered and valid.
+    fn filled(&self) -> [bool; 4] {
+        [
+            !self.data_raw.voltage.trim().is_empty() && self.data.voltage.is_ok(),
+            !self.data_raw.current.trim().is_empty() && self.data.current.is_ok(),
+            !self.data_raw.resistance.trim().is_empty() && self.data.resistance.is_ok(),
+            !self.data_raw.power.trim().is_empty() && self.data.power.is_ok(),
+        ]
+    }
+
+    /// Records which input tolerance bounds produce the bounds of every quantity.
+    fn calculating_provenance(&mut self) {
+        let voltage = ToleranceProvenance::input("Voltage");
+        let current = ToleranceProvenance::input("Current");
+        let resistance = ToleranceProvenance::input("Resistance");
+        let power = ToleranceProvenance::input("Power");
+
+        self.provenance = match self.calc_type {
+            CalcType::VcRp => OhmProvenance {
+                resistance: Some(voltage.division(&current)),
+                power: Some(voltage.multiplication(&current)),
+                voltage: Some(voltage),
+                current: Some(current),
+            },
+            CalcType::VrCp => {
+                let calc_current = voltage.division(&resistance);
+                OhmProvenance {
+                    power: Some(voltage.multiplication(&calc_current)),
+                    current: Some(calc_current),
+                    voltage: Some(voltage),
+                    resistance: Some(resistance),
+                }
+            }
+            CalcType::VpCr => {
+                let calc_current = power.division(&voltage);
+                OhmProvenance {
+                    resistance: Some(voltage.division(&calc_current)),
+                    current: Some(calc_current),
+                    voltage: Some(voltage),
+                    power: Some(power),
+                }
+            }
+            CalcType::CrVp => {
+                let calc_voltage = current.multiplication(&resistance);
+                OhmProvenance {
+                    power: Some(calc_voltage.multiplication(&current)),
+                    voltage: Some(calc_voltage),
+                    current: Some(current),
+                    resistance: Some(resistance),
+                }
+            }
+            CalcType::CpVr => {
+                let calc_voltage = power.division(&current);
+                OhmProvenance {
+                    resistance: Some(calc_voltage.division(&current)),
+                    voltage: Some(calc_voltage),
+                    current: Some(current),
+                    power: Some(power),
+                }
+            }
+            // корень монотонен: границы те же, что у P × R и P / R
+            CalcType::RpVc => OhmProvenance {
+                voltage: Some(power.multiplication(&resistance)),
+                current: Some(power.division(&resistance)),
+                resistance: Some(resistance),
+                power: Some(power),
+            },
+            CalcType::None | CalcType::Overdetermined => OhmProvenance::default(),
+        };
+    }
+
+    /// Explains which input extremes produce the values of the selected result row.
+    fn row_explanation(&self, row: usize) -> Vec<String> {
+        let Some(bound) = RESULT_ROW_BOUNDS.get(row).copied().flatten() else {
+            return vec![String::from(t(Key::NominalExplanation))];
+        };
+
+        let input_tolerance = |name: &str| -> Option<Tolerance> {
+            match name {
+                "Voltage" => self.data.voltage.as_ref().ok()?.get_tolerance(),
+                "Current" => self.data.current.as_ref().ok()?.get_tolerance(),
+                "Resistance" => self.data.resistance.as_ref().ok()?.get_tolerance(),
+                "Power" => self.data.power.as_ref().ok()?.get_tolerance(),
+                _ => None,
+            }
+        };
+        let is_negative = |name: &str| -> bool {
+            let nominal = match name {
+                "Voltage" => self
+                    .data
+                    .voltage
+                    .as_ref()
+                    .map(Measurement::get_nominal_value),
+                "Current" => self
+                    .data
+                    .current
+                    .as_ref()
+                    .map(Measurement::get_nominal_value),
+                "Resistance" => self
+                    .data
+                    .resistance
+                    .as_ref()
+                    .map(Measurement::get_nominal_value),
+                "Power" => self.data.power.as_ref().map(Measurement::get_nominal_value),
+                _ => Ok(0.0),
+            };
+            nominal.is_ok_and(|nominal| nominal < 0.0)
+        };
+
+        let quantities = [
+            ("Voltage", &self.provenance.voltage),
+            ("Current", &self.provenance.current),
+            ("Resistance", &self.provenance.resistance),
+            ("Power", &self.provenance.power),
+        ];
+
+        quantities
+            .iter()
+            .filter_map(|(quantity, provenance)| {
+                let provenance = provenance.as_ref()?;
+                // максимум отрицательного значения лежит на стороне минуса допуска
+                let side = match row {
+                    1 | 2 if is_negative(quantity) => bound.opposite(),
+                    _ => bound,
+                };
+                let terms: Vec<String> = provenance
+                    .side(side)
+                    .iter()
+                    .map(|(name, bound)| {
+                        let label = quantity_label(name);
+                        match (input_tolerance(name), bound) {
+                            (Some(tol), Bound::Plus) => {
+                                format!("{} {} +{}%", label, t(Key::At), tol.plus)
+                            }
+                            (Some(tol), Bound::Minus) => {
+                                format!("{} {} −{}%", label, t(Key::At), tol.minus)
+                            }
+                            (None, _) => format!("{} {}", label, t(Key::AtNominal)),
+                        }
+                    })
+                    .collect();
+
+                let quantity = quantity_label(quantity);
+                if terms.is_empty() {
+                    Some(format!("{}: {}", quantity, t(Key::WithoutTolerance)))
+                } else {
+                    let separator = format!(" {} ", t(Key::And));
+                    Some(format!("{}: {}", quantity, terms.join(&separator)))
+                }
+            })
+            .collect()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mut column = Column::new().push(self.view_form());
+
+        if let CalcType::Overdetermined = self.calc_type {
+            column = column.push(self.view_overdetermined());
+        }
+
+        column = column.push(self.view_sweep_input());
+        if self.sweep.enabled {
+            // в режиме развёртки таблица точек заменяет результат
+            return column.push(self.view_sweep()).into();
+        }
+
+        column = column.push(self.view_result());
+        if let Some(aux) = self.view_aux() {
+            column = column.push(aux);
+        }
+        column = column.push(eseries_check::view(
+            &self.calculated_resistance(),
+            self.standard_values,
+            Message::StandardValuesToggled,
+        ));
+        column = column.push(self.view_work());
+
+        if let Some(row) = self.selected_row {
+            let mut explanation = Column::new().spacing(2);
+            for line in self.row_explanation(row) {
+                explanation = explanation.push(Text::new(line).size(13));
+            }
+            column = column.push(Container::new(explanation).padding([5, 5]));
+        }
+
+        column.into()
+    }
+
+    /// The resistance if it is calculated rather than entered, for the standard value check.
+    fn calculated_resistance(&self) -> Vec<(String, f64)> {
+        match &self.data.resistance {
+            Ok(r) if self.data_raw.resistance.trim().is_empty() => {
+                vec![("R".to_string(), r.get_nominal_value())]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn view_overdetermined(&self) -> Element<'_, Message> {
+        fn spread<M: Measurement>(label: &str, candidates: &[M]) -> Option<String> {
+            let first = candidates.first()?;
+            let (min, max) = candidates
+                .iter()
+                .map(|c| c.get_nominal_value())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                    (min.min(v), max.max(v))
+                });
+            let spread = if min != 0.0 {
+                (max - min) / min.abs() * 100.0
+            } else {
+                0.0
+            };
+
+            Some(format!(
+                "{}: {} … {} ({} {:.2}%)",
+                label,
+                first.normalize(min),
+                first.normalize(max),
+                t(Key::Spread),
+                spread
+            ))
+        }
+
+        let result = &self.overdetermined;
+        let lines = [
+            spread(t(Key::Voltage), &result.voltage),
+            spread(t(Key::Current), &result.current),
+            spread(t(Key::Resistance), &result.resistance),
+            spread(t(Key::Power), &result.power),
         ];
+
+        let (status, status_style): (_, TextStyle) = if result.consistent {
+            (t(Key::InputsConsistent), style::success_text)
+        } else {
+            (t(Key::InputsInconsistent), style::warning_text)
+        };
+
+        let mut column = Column::new()
+            .push(Text::new(status).style(status_style).size(13))
+            .spacing(2);
+        for line in lines.into_iter().flatten() {
+            column = column.push(Text::new(line).size(13));
+        }
+
+        Container::new(column).padding([5, 0]).into()
+    }
+
+    fn view_result(&self) -> Element<'_, Message> {
+        let mut data = result_rows::table_rows(&[
+            result_rows::measurement_rows(&self.data.voltage),
+            result_rows::measurement_rows(&self.data.current),
+            result_rows::measurement_rows(&self.data.resistance),
+            result_rows::measurement_rows(&self.data.power),
+        ]);
+        if let Some(pinned) = &self.pinned {
+            data.extend(
+                pinned
+                    .table_rows(&self.snapshot())
+                    .map(|row| row.into_iter().map(Cell::from).collect()),
+            );
+        }
         let result = self.view_table(data);
 
-        Container::new(result).padding([1, 0]).into()
-    }
-
-    fn view_table(&self, data: Vec<Vec<String>>) -> Element<Message> {
-        const RULE_WIDTH: u16 = 0;
-        const COLUMN_FIRST_WIDTH: u16 = 110;
-
-        fn text_output(s: String) -> Element<'static, Message> {
-            let t = Text::new(s).width(Fill);
-
-            Container::new(t).padding(5).into()
-        }
-
-        fn row_line(
-            column1: String,
-            column2: String,
-            column3: String,
-            column4: String,
-            column5: String,
-        ) -> Element<'static, Message> {
-            Row::new()
-                .push(Rule::vertical(RULE_WIDTH))
-                .push(Container::new(text_output(column1)).width(COLUMN_FIRST_WIDTH))
-                .push(Rule::vertical(RULE_WIDTH))
-                .push(Text::new("").width(1)) // double rule line
-                .push(Rule::vertical(RULE_WIDTH))
-                .push(text_output(column2))
-                .push(Rule::vertical(RULE_WIDTH))
-                .push(text_output(column3))
-                .push(Rule::vertical(RULE_WIDTH))
-                .push(text_output(column4))
-                .push(Rule::vertical(RULE_WIDTH))
-                .push(text_output(column5))
-                .push(Rule::vertical(RULE_WIDTH))
-                .height(30)
-                .width(Fill)
+        let pin = Button::new(Text::new(t(Key::PinResult)).size(14)).on_press(Message::PinResult);
+        let mut buttons = Row::new().push(pin).spacing(5);
+        if self.pinned.is_some() {
+            let unpin = Button::new(Text::new(t(Key::Unpin)).size(14))
+                .on_press(Message::Unpin)
+                .style(button::secondary);
+            buttons = buttons.push(unpin);
+        }
+        let export = Button::new(Text::new(t(Key::ExportJson)).size(14))
+            .on_press(Message::ExportJson)
+            .style(button::secondary);
+        let transposed = checkbox(t(Key::TransposeTable), self.transposed)
+            .on_toggle(Message::TransposedToggled)
+            .size(15)
+            .text_size(13);
+        buttons = buttons
+            .push(export)
+            .push(transposed)
+            .align_y(Alignment::Center);
+
+        let rows_menu = row_menu::view(
+            result_rows::hidden_rows().ohm_law,
+            self.rows_menu,
+            Message::RowsMenuToggled,
+            Message::RowVisibilityToggled,
+        );
+
+        Column::new()
+            .push(buttons)
+            .push(rows_menu)
+            .push(Container::new(result).padding([1, 0]))
+            .into()
+    }
+
+    /// Sweep checkbox and, when it is on, the swept input and its range.
+    fn view_sweep_input(&self) -> Element<'_, Message> {
+        let toggle = checkbox(t(Key::SweepMode), self.sweep.enabled)
+            .on_toggle(Message::SweepModeToggled)
+            .size(15)
+            .text_size(13);
+        let mut column = Column::new().push(toggle).padding([5, 0]).spacing(2);
+
+        if self.sweep.enabled {
+            let variable = pick_list(
+                FieldId::SWEEP,
+                Some(self.sweep.variable),
+                Message::SweepVariableSelected,
+            )
+            .width(110);
+            let range = TextInput::new("", &self.sweep.raw)
+                .on_input(Message::InputSweepChanged)
+                .size(15);
+            let hint = match &self.sweep.sweep {
+                Err(ParserError::IncorrectInput(e)) => e.clone(),
+                _ => t(Key::ExampleSweep).to_string(),
+            };
+
+            column = column
+                .push(Row::new().push(variable).push(range).spacing(5))
+                .push(Text::new(hint).size(12).style(style::hint_text));
+        }
+
+        column.into()
+    }
+
+    /// One row per sweep point with the nominal values of all quantities.
+    fn view_sweep(&self) -> Element<'_, Message> {
+        let rows = &self.sweep.rows;
+        if rows.is_empty() {
+            let note = match self.sweep.sweep {
+                Ok(_) => t(Key::SweepNeedsOneInput),
+                Err(_) => t(Key::ExampleSweep),
+            };
+            return Text::new(note).size(13).into();
+        }
+
+        fn nominal<M: Measurement>(value: &Result<M, ParserError>) -> Cell {
+            value
+                .as_ref()
+                .map(Measurement::get_value_nom)
+                .unwrap_or_else(|_| "N/A".to_string())
                 .into()
         }
 
-        let mut elements = Vec::new();
-        // header
-        let r = row_line(
-            "".to_string(),
-            "Voltage".to_string(),
-            "Current".to_string(),
-            "Resistance".to_string(),
-            "Power".to_string(),
-        );
-        elements.push(Rule::horizontal(RULE_WIDTH).into());
-        elements.push(r);
-        elements.push(Rule::horizontal(RULE_WIDTH).into());
-        elements.push(Text::new("").height(1).into());
-        elements.push(Rule::horizontal(RULE_WIDTH).into());
-
-        // data
-        for d in data {
-            let r = row_line(
-                d[0].clone(),
-                d[1].clone(),
-                d[2].clone(),
-                d[3].clone(),
-                d[4].clone(),
+        let header = vec![
+            "#".to_string(),
+            t(Key::Voltage).to_string(),
+            t(Key::Current).to_string(),
+            t(Key::Resistance).to_string(),
+            t(Key::Power).to_string(),
+        ];
+        let table_rows = rows
+            .iter()
+            .enumerate()
+            .map(|(id, row)| {
+                TableRow::new(vec![
+                    (id + 1).to_string().into(),
+                    nominal(&row.voltage),
+                    nominal(&row.current),
+                    nominal(&row.resistance),
+                    nominal(&row.power),
+                ])
+            })
+            .collect();
+
+        let copy = Button::new(Text::new(t(Key::CopyCsv)).size(14)).on_press(Message::CopySweepCsv);
+        let mut column = Column::new().push(copy).spacing(5);
+        if matches!(&self.sweep.sweep, Ok(sweep) if sweep.truncated) {
+            column = column.push(
+                Text::new(format!("{} {}", t(Key::SweepTruncated), sweep::MAX_POINTS))
+                    .size(12)
+                    .style(style::warning_text),
             );
-            elements.push(r);
-            elements.push(Rule::horizontal(RULE_WIDTH).into());
         }
 
-        Column::from_vec(elements)
-            .padding([5, 0])
-            .width(Fill)
+        column
+            .push(Table::new(header).rows(table_rows).view())
             .into()
     }
 
-    fn view_form(&self) -> Element<Message> {
-        let under_text = match &self.data.voltage {
-            Err(ParserError::IncorrectInput(e)) => e,
-            _ => "Example: 10.5 +3% -7.6%",
-        };
+    /// "Show work" checkbox and, when it is on, the formulas of the solution.
+    fn view_work(&self) -> Element<'_, Message> {
+        let toggle = checkbox(t(Key::ShowWork), self.show_work)
+            .on_toggle(Message::ShowWorkToggled)
+            .size(15)
+            .text_size(13);
+        let mut column = Column::new().push(toggle).padding([5, 0]).spacing(2);
+
+        if self.show_work {
+            for line in self.steps() {
+                column = column.push(Text::new(line).size(13));
+            }
+        }
+
+        column.into()
+    }
+
+    /// Auxiliary rows under the result table, `None` if there is nothing to show.
+    fn view_aux(&self) -> Option<Element<'_, Message>> {
+        let aux = &self.aux;
+        let mut rows = Vec::new();
+
+        if let Some(conductance) = &aux.conductance {
+            rows.push(TableRow::new(vec![
+                t(Key::Conductance).into(),
+                conductance.get_value_nom().into(),
+                conductance.get_value_min().into(),
+                conductance.get_value_max().into(),
+            ]));
+        }
+        let margins = margins::margins();
+        if let Some(rating) = &aux.power_rating {
+            rows.push(TableRow::new(vec![
+                format!("{}, ×{}", t(Key::PowerRating), margins.derating).into(),
+                rating.get_value_nom().into(),
+                rating.get_value_min().into(),
+                rating.get_value_max().into(),
+            ]));
+        }
+        if let Some(supply) = &aux.supply_current {
+            rows.push(TableRow::new(vec![
+                format!("{}, ×{}", t(Key::SupplyCurrent), margins.headroom).into(),
+                supply.get_value_nom().into(),
+                supply.get_value_min().into(),
+                supply.get_value_max().into(),
+            ]));
+        }
+        if let Some(density) = &aux.current_density {
+            let density_style: TextStyle = match aux.density_warning {
+                Some(_) => style::warning_text,
+                None => style::hint_text,
+            };
+            let cell = |value: f64| Cell::new(format!("{:.3} A/mm²", value));
+            rows.push(TableRow::new(vec![
+                t(Key::CurrentDensity).into(),
+                cell(density.nominal).style(density_style),
+                cell(density.min),
+                cell(density.max),
+            ]));
+        }
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        let header = vec![
+            "".to_string(),
+            t(Key::ValueNom).to_string(),
+            t(Key::ValueMin).to_string(),
+            t(Key::ValueMax).to_string(),
+        ];
+        let mut column = Column::new().push(Table::new(header).rows(rows).view());
+        if let Some(warning) = &aux.density_warning {
+            column = column.push(
+                Text::new(warning.as_str())
+                    .style(style::warning_text)
+                    .size(12),
+            );
+        }
+
+        Some(Container::new(column).padding([5, 0]).into())
+    }
+
+    fn view_table(&self, data: Vec<Vec<Cell>>) -> Element<'_, Message> {
+        let header = vec![
+            "".to_string(),
+            t(Key::Voltage).to_string(),
+            t(Key::Current).to_string(),
+            t(Key::Resistance).to_string(),
+            t(Key::Power).to_string(),
+        ];
+
+        let hidden = result_rows::hidden_rows().ohm_law;
+        // номер строки остаётся её номером в полной таблице
+        let data = data
+            .into_iter()
+            .enumerate()
+            .filter(|(id, _)| !hidden.contains_index(*id));
+
+        if self.transposed {
+            // величины по строкам: выбор строки-метрики здесь невозможен
+            let header = header.into_iter().map(Cell::from).collect();
+            let rows = std::iter::once(header).chain(data.map(|(_, row)| row));
+            let mut data = table::transpose(rows.collect());
+            let header = data.remove(0).into_iter().map(|cell| cell.text).collect();
+            let rows = data.into_iter().map(TableRow::new).collect();
+
+            return Table::new(header)
+                .rows(rows)
+                .on_copy(Message::CopyCell)
+                .view();
+        }
+
+        let rows = data
+            .map(|(id, row)| {
+                let row = TableRow::new(row);
+                // строки закреплённого результата не выбираются
+                if id < RESULT_ROW_BOUNDS.len() {
+                    row.selected(self.selected_row == Some(id))
+                        .on_press(Message::ResultRowSelected(id))
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        Table::new(header)
+            .rows(rows)
+            .on_copy(Message::CopyCell)
+            .view()
+    }
+
+    fn view_form(&self) -> Element<'_, Message> {
+        let [voltage_warning, current_warning, resistance_warning, power_warning] = &self.warnings;
+
         let voltage_field = self.create_input_field(
-            "Voltage",
+            t(Key::Voltage),
             &self.data_raw.voltage,
-            |s| Message::InputVoltageChanged(s),
-            under_text,
+            FieldId::Voltage,
+            under_text(
+                &self.data_raw.voltage,
+                &self.data.voltage,
+                voltage_warning,
+                t(Key::ExampleVoltage),
+            ),
             self.fields_enable.voltage,
         );
-        let under_text = match &self.data.voltage {
-            Err(ParserError::IncorrectInput(e)) => e,
-            _ => "Example: 100m +1% -1%",
-        };
         let current_field = self.create_input_field(
-            "Current",
+            t(Key::Current),
             &self.data_raw.current,
-            |s| Message::InputCurrentChanged(s),
-            under_text,
+            FieldId::Current,
+            under_text(
+                &self.data_raw.current,
+                &self.data.current,
+                current_warning,
+                t(Key::ExampleCurrent),
+            ),
             self.fields_enable.current,
         );
-        let under_text = match &self.data.resistance {
-            Err(ParserError::IncorrectInput(e)) => e,
-            _ => "Example: 10k 5%",
-        };
         let resistance_field = self.create_input_field(
-            "Resistance",
+            t(Key::Resistance),
             &self.data_raw.resistance,
-            |s| Message::InputResistanceChanged(s),
-            under_text,
+            FieldId::Resistance,
+            under_text(
+                &self.data_raw.resistance,
+                &self.data.resistance,
+                resistance_warning,
+                t(Key::ExampleResistance),
+            ),
             self.fields_enable.resistance,
         );
-        let under_text = match &self.data.power {
-            Err(ParserError::IncorrectInput(e)) => e,
-            _ => "Example: 1k 5%",
-        };
         let power_field = self.create_input_field(
-            "Power",
+            t(Key::Power),
             &self.data_raw.power,
-            |s| Message::InputPowerChanged(s),
-            under_text,
+            FieldId::Power,
+            under_text(
+                &self.data_raw.power,
+                &self.data.power,
+                power_warning,
+                t(Key::ExamplePower),
+            ),
             self.fields_enable.power,
         );
 
+        let area_text = match &self.area {
+            Err(ParserError::IncorrectInput(e)) => e.clone(),
+            _ => String::from(t(Key::ExampleArea)),
+        };
+        let area_field = self.create_input_field(
+            t(Key::Area),
+            &self.area_raw,
+            FieldId::Area,
+            (area_text, style::hint_text),
+            true,
+        );
+
+        let measurement_mode = checkbox(t(Key::MeasurementMode), self.measurement_mode)
+            .on_toggle(Message::MeasurementModeToggled)
+            .size(15)
+            .text_size(13);
+        let frozen = checkbox(t(Key::FreezeResults), self.frozen)
+            .on_toggle(Message::FrozenToggled)
+            .size(15)
+            .text_size(13);
+
+        let calc_type = match self.frozen {
+            true => t(Key::ResultsFrozen).to_string(),
+            false => self.calc_type.description(),
+        };
+        let calc_type = Text::new(calc_type).size(13).style(style::hint_text);
+
+        let example = Button::new(Text::new(t(Key::LoadExample)).size(13))
+            .on_press(Message::LoadExample)
+            .style(button::secondary);
+
         Column::new()
+            .push(
+                Row::new()
+                    .push(Container::new(measurement_mode).padding([5, 0]).width(Fill))
+                    .push(example)
+                    .align_y(Alignment::Center),
+            )
+            .push(frozen)
+            .push(calc_type)
             .push(voltage_field)
             .push(current_field)
             .push(resistance_field)
             .push(power_field)
+            .push(area_field)
             .into()
     }
 
@@ -511,52 +2210,59 @@ impl OhmLaw {
         &self,
         label_text: &'a str,
         input_value: &'a str,
-        on_input: impl Fn(String) -> Message + 'a,
-        under_text: &'a str,
+        field_id: FieldId,
+        (under_text, under_text_style): (String, TextStyle),
         enable: bool,
     ) -> Element<'a, Message> {
-        // Константы для стилей
         const LABEL_WIDTH: u16 = 110;
-        const FIELD_HEIGHT: u16 = 30;
-        const LABEL_SIZE: u16 = 15;
-        const INPUT_SIZE: u16 = 15;
-        const UNDER_TEXT_SIZE: u16 = 12;
-        const PADDING_ROW: [u16; 2] = [0, 0];
-        const PADDING_COLUMN: [u16; 2] = [5, 0];
-        const UNDER_TEXT_PADDING: [u16; 2] = [0, LABEL_WIDTH];
-
-        // Метка
-        let label = Text::new(label_text).size(LABEL_SIZE);
-        let label = Container::new(label)
-            .align_y(Alignment::Center)
-            .width(LABEL_WIDTH)
-            .height(FIELD_HEIGHT)
-            .padding(PADDING_ROW);
-
-        // Поле ввода
-        let mut input = TextInput::new("", input_value).size(INPUT_SIZE);
-        if enable == true {
-            input = input.on_input(on_input);
-        }
-        let input = Container::new(input)
-            .align_y(Alignment::Center)
-            .width(Fill)
-            .height(FIELD_HEIGHT);
-
-        // Подсказка
-        let under_text = Text::new(under_text)
-            .size(UNDER_TEXT_SIZE)
-            .color(Color::from_rgb8(128, 128, 128));
-        let under_text = Container::new(under_text)
-            .align_y(Alignment::Center)
-            .padding(UNDER_TEXT_PADDING);
-
-        // Компоновка
-        Column::new()
-            .push(Row::new().push(label).push(input))
-            .push(under_text)
-            .padding(PADDING_COLUMN)
-            .into()
+        const TEXT_SIZE: u16 = 15;
+
+        let input = MeasurementInput::new(label_text, input_value, move |event| {
+            Message::from_input(field_id, event)
+        })
+        .id(field_id.input_id())
+        .label_width(LABEL_WIDTH)
+        .text_size(TEXT_SIZE)
+        .enabled(enable)
+        .lock(self.is_locked(field_id))
+        // у сечения нет приставок
+        .prefixes(field_id != FieldId::Area)
+        .presets(field_id.kind().map(presets::presets_of).unwrap_or_default())
+        .hint(under_text, under_text_style)
+        .error_report(self.parse_error(field_id).is_some());
+
+        Container::new(input.view()).padding([5, 0]).into()
+    }
+}
+
+/// Hint under an input field: the parse error, the plausibility warning, the parsed value in
+/// base units or, for an empty field, the example.
+fn under_text<M: Measurement + FromStr>(
+    raw: &str,
+    value: &Result<M, ParserError>,
+    warning: &Option<String>,
+    example: &str,
+) -> (String, TextStyle) {
+    match (value, warning) {
+        (Err(ParserError::IncorrectInput(e)), _) => (e.clone(), style::hint_text),
+        (_, Some(warning)) => (warning.clone(), style::warning_text),
+        // рассчитанные значения не подписываются
+        (Ok(value), None) if !raw.trim().is_empty() => (
+            value.get_value_hint() + &default_tolerance::assumed_hint::<M>(raw),
+            style::hint_text,
+        ),
+        _ => (example.to_string(), style::hint_text),
+    }
+}
+
+/// Translated display name of a quantity recorded in a `ToleranceProvenance`.
+fn quantity_label(name: &str) -> &'static str {
+    match name {
+        "Voltage" => t(Key::Voltage),
+        "Current" => t(Key::Current),
+        "Resistance" => t(Key::Resistance),
+        "Power" => t(Key::Power),
+        _ => "",
     }
 }
 
@@ -569,13 +2275,51 @@ The program performs calculations based on Ohm's Law: **U = I × R** and the pow
 - **R** — Resistance (ohms, Ω),  
 - **P** — Power (watts, W).
 
+**Load example** replaces the inputs with a worked example, 12V across a load drawing 2A, to see the results filled in.
+
+#### Formulas
+| Unknown | From the other values |
+|---------|-----------------------|
+| U       | I × R, P / I, √(P × R) |
+| I       | U / R, P / U, √(P / R) |
+| R       | U / I, U² / P, P / I²  |
+| P       | U × I, I² × R, U² / R  |
+
 #### How to Use
 1. Fill in any **two known fields** out of the four: voltage (**U**), current (**I**), resistance (**R**), or power (**P**).
-2. After filling in two fields, the remaining fields will become read-only.
+2. After filling in two fields, the remaining fields will become read-only. The `✕` button next to a field clears it and unlocks the read-only fields again. The padlock button locks a field: it becomes read-only but still takes part in the calculation and keeps its text when the other fields change.
 3. The results will be displayed in the table below.
 
+Clicking into a field selects its text, so typing replaces the value. **Enter** moves to the next editable field.
+
 If a parameter cannot be calculated, it will be marked as **N/A**.
 
+To check a measurement, enable **Measurement mode**: the fields stay editable and three or four known values can be entered. The missing value is then calculated from every pair of inputs, the spread of the candidates is shown, and a warning appears if they disagree by more than their combined tolerances.
+
+Click a row of the result table to see which input extremes produce its values, e.g. the maximum current is reached with the voltage at its plus tolerance and the resistance at its minus tolerance.
+
+**Pin result** keeps the current result for comparison: while you change the inputs, the table shows the pinned nominal values and their change in percent (**Δ, %**). **Unpin** removes them.
+
+A right click on a value of the result table copies it as shown, e.g. `1.53mA`. With **Shift** held it copies the exact number in base units instead, e.g. `0.0015319148936170214`, ready for a spreadsheet.
+
+**Export JSON** copies the result to the clipboard for other tools: the nominal value, the minimum, the maximum and the tolerance of every quantity, and the calculation type. Every value names the formula it is calculated by and the entered values it comes from. A quantity that is not calculated is `null`.
+
+**Sweep** tabulates the results over a range of one input. Choose the swept quantity, enter a range such as `1..10 step 1` (the last point never passes the stop, so `1..10 step 4` gives 1, 5 and 9) or a list such as `1, 2.2, 4.7k`, and enter one more value. The swept quantity keeps the tolerance entered in its field. The table holds at most 500 rows; **Copy CSV** copies it with the values in base units, the formulas of the calculated quantities and the inputs of every row.
+
+**Freeze results** pauses the calculation while several values are entered: the fields keep the entered text and stay editable, the results are not updated. Unfreezing calculates them once.
+
+**Show work** lists the formulas of the solution with the nominal values substituted, e.g. `R = V / I = 10.00V / 2.00A = 5.00R`.
+
+When the resistance is calculated, **Standard values** lists the nearest value of the E24, E48 and E96 series and the error it brings in, e.g. 3456R is 3.60kR in E24 (+4.17%).
+
+**⚙ Rows** above the result table hides the rows you do not use, e.g. the tolerances in percent. The choice is kept while the application runs and applies to **Export JSON** too: the values of hidden rows are left out.
+
+**Quantities as rows** turns the result table so that every quantity is a row and the values are the columns; the usual layout is the default.
+
+Under the result table the **conductance** G = 1 / R is shown in siemens. If the optional **Area, mm²** field holds the cross-section of the conductor, the **current density** J = I / A in A/mm² is shown too; it is highlighted when it exceeds the maximum density set in **Settings**. A zero resistance has no conductance.
+
+The **power rating** is the power times the derating, ×2 unless changed in **Settings**, and the **supply current** is the current times the headroom, ×1.2 by default. Pick the resistor and the supply by these rows rather than by the bare results.
+
 #### Data Input Format
 ##### Value Units
 Each input field supports values with units. To specify a unit, append the unit prefix directly to the number:  
@@ -590,15 +2334,49 @@ Supported unit prefixes:
 - **M** (mega, 10⁶),  
 - **G** (giga, 10⁹).
 
+The **10ⁿ** list next to a field sets the prefix of the entered value, replacing the one already there. Mind the case: `M` is mega, `m` is milli.
+
+The **★** list next to a field holds the presets of its quantity, saved on the **Presets** page; picking one replaces the entered text with its value.
+
+Digits can be grouped: `1_000_000`, `1 000 000` or `1,000,000`. The period is always the decimal separator, and one number uses one kind of separator.
+
 ##### Uncertainty (Error Margins)
 Input values can include error margins using the following formats:  
 - Symmetrical error: 5% (±5% from the value),  
 - Asymmetrical positive error: +5%,  
 - Asymmetrical negative error: -5%,  
-- Symmetrical error: +/-5%.
+- Symmetrical error: +/-5%, or ±5% as pasted from documents (the `−` minus sign is accepted too).
+- Absolute symmetrical error: +/-0.5 or +/-10m, in the units of the value: `12 +/-0.5` is 12 ±4.17%. The value must not be zero.
+- Standard uncertainty: s=0.05 or s=5m, one standard deviation in the units of the value. It needs a **Statistical** tolerance mode, the other modes would take it for a worst-case limit; choose **Statistical 2σ** to get the GUM combined uncertainty of the results, shown as the expanded uncertainty at k = 2; the square roots of P × R and P / R halve the relative uncertainty.
+
+A sign in front of a number without `%` belongs to the value itself: `-5` is a negative value, `+12` is the same as `12`, while `-5%` is a tolerance. For example, `-5 +3% -2%` is −5 with +3%/−2% error.
+
+A value can also be entered by its datasheet limits: `typ 12 min 11.4 max 12.6` is 12 with +5%/−5% error. The typical value is required, `min` and `max` are optional and go in any order.
+
+**Settings** can define a default tolerance for every quantity, e.g. 1% for resistances. It is applied to a value entered without a tolerance and shown under the field (\"assumed ±1%\"); an explicit `0%` is kept.
+
+##### Resistor Networks
+A resistance field accepts a small resistor network: `||` is a parallel connection, `+` a series one, and parentheses group them. `||` binds tighter than `+`, so `(10k || 10k) + 1k` and `1k + 10k || 10k` are both 6k. The series `+` needs a space after it, otherwise it is read as a tolerance (`+5%`). Tolerances are combined: `10k 1% || 15k 1%` is 6k 1%. All parallel values must be above zero. A `-` with a space after it subtracts, e.g. the lead resistance from a two-terminal measurement of a low resistance: `0.5 1% - 20m` is 480mR, the tolerance of the measurement applies to the difference. The difference must stay above zero.
+
+#### Plausibility Limits
+The **Settings** page has optional limits for the maximum voltage, current and power and the minimum resistance. An entered value outside its limit, e.g. `10M` amps typed instead of `10m`, gets a yellow warning under its field. The warning is advisory: the calculation still uses the value. The limits are off while their fields are empty.
 
 #### Error Handling in Results
 All input uncertainties are considered during calculations. The results will reflect the range of uncertainty based on the provided error margins.
+
+##### Tolerance Mode
+The selector in the sidebar sets how an error margin is understood:  
+- **Limits** (default): the margin is a hard limit that the value never leaves. A result takes the worst case: the margins of a product or a quotient add up, e.g. `10 1%` × `2 1%` is `20 2%`.  
+- **Interval**: the margins are hard limits as well, but a result is calculated from the true extremes of its inputs, e.g. `10 1%` × `2 1%` is between 19.602 and 20.402, that is `20 +2.01% -1.99%`. Adding percentages is a close approximation of this for small margins.  
+- **Statistical 1σ / 2σ / 3σ**: the margin is one standard deviation (1σ) of an independent random error. The deviations of a result are combined as the root of the sum of squares, so `10 1%` × `2 1%` is `20` with σ = 1.41%. The tables show the band scaled to the chosen coverage: at 3σ the same result is shown as ±4.24%, which holds about 99.7% of the values.
+
+A statistical band is narrower than the worst case at the same coverage, because the inputs rarely reach their extremes together.
+
+When a field does not parse, **Copy error report** next to the error copies the input, the error and its position for a bug report.
+
+A value may end with its unit, e.g. `12V`, `2mA` or `4.7kΩ` (`R` stands for ohms too). A unit that does not match the field, such as `12A` in the voltage field, gets a warning under the field; the number is still used.
+
+The number of a tolerance cannot be negative: the sign goes before it, so `+-5%` is an error. A minus tolerance above 100% is accepted with a warning under the field. A resistance or a power cannot go below zero, so such a minimum is shown as `0.00 (clamped)`.
 ");
 
     (title, text)
@@ -607,6 +2385,7 @@ All input uncertainties are considered during calculations. The results will ref
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::tolerance_mode::{self, ToleranceMode};
 
     #[test]
     fn test_calculating_vcrp() {
@@ -619,7 +2398,7 @@ mod tests {
             value: 2.0,
             tolerance: None,
         });
-        ohm_law.calc_type = CalcType::VCRP;
+        ohm_law.calc_type = CalcType::VcRp;
 
         ohm_law.calculating();
 
@@ -638,7 +2417,7 @@ mod tests {
             value: 4.0,
             tolerance: None,
         });
-        ohm_law.calc_type = CalcType::VRCP;
+        ohm_law.calc_type = CalcType::VrCp;
 
         ohm_law.calculating();
 
@@ -657,7 +2436,7 @@ mod tests {
             value: 30.0,
             tolerance: None,
         });
-        ohm_law.calc_type = CalcType::VPCR;
+        ohm_law.calc_type = CalcType::VpCr;
 
         ohm_law.calculating();
 
@@ -676,7 +2455,7 @@ mod tests {
             value: 5.0,
             tolerance: None,
         });
-        ohm_law.calc_type = CalcType::CRVP;
+        ohm_law.calc_type = CalcType::CrVp;
 
         ohm_law.calculating();
 
@@ -695,7 +2474,7 @@ mod tests {
             value: 27.0,
             tolerance: None,
         });
-        ohm_law.calc_type = CalcType::CPVR;
+        ohm_law.calc_type = CalcType::CpVr;
 
         ohm_law.calculating();
 
@@ -714,7 +2493,7 @@ mod tests {
             value: 64.0,
             tolerance: None,
         });
-        ohm_law.calc_type = CalcType::RPVC;
+        ohm_law.calc_type = CalcType::RpVc;
 
         ohm_law.calculating();
 
@@ -723,9 +2502,925 @@ mod tests {
     }
 
     #[test]
-    fn test_calculating_none() {
+    fn test_rpvc_tolerance() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputResistanceChanged("100 21%".to_string()));
+        ohm_law.update(Message::InputPowerChanged("1".to_string()));
+        // √(1 W × 121 Ω) = 11 V
+        let voltage = ohm_law.data.voltage.as_ref().unwrap();
+        assert!((voltage.max_value() - 11.0).abs() < 1e-9);
+
+        // GUM: u(P) = 2%, u(R) = 1%, u(U)/U = √(2² + 1²) / 2 %
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Statistical { coverage: 2 });
+        ohm_law.update(Message::InputResistanceChanged("100 s=1".to_string()));
+        ohm_law.update(Message::InputPowerChanged("1 s=20m".to_string()));
+        let voltage = ohm_law.data.voltage.as_ref().unwrap();
+        let relative = 5f64.sqrt() / 2.0;
+        assert!((voltage.get_tolerance().unwrap().plus - relative).abs() < 1e-9);
+        assert!((voltage.max_value() - 10.0 * (1.0 + 2.0 * relative / 100.0)).abs() < 1e-9);
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Limits);
+    }
+
+    #[test]
+    fn test_provenance_vrcp() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10 +5% -2%".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("100 +1% -3%".to_string()));
+
+        let current = ohm_law.provenance.current.clone().unwrap();
+        assert_eq!(
+            current.plus,
+            vec![("Voltage", Bound::Plus), ("Resistance", Bound::Minus)]
+        );
+        assert_eq!(
+            current.minus,
+            vec![("Voltage", Bound::Minus), ("Resistance", Bound::Plus)]
+        );
+
+        let power = ohm_law.provenance.power.clone().unwrap();
+        assert_eq!(
+            power.plus,
+            vec![("Voltage", Bound::Plus), ("Resistance", Bound::Minus)]
+        );
+
+        ohm_law.update(Message::ResultRowSelected(1));
+        assert_eq!(ohm_law.selected_row, Some(1));
+        let explanation = ohm_law.row_explanation(1);
+        assert!(explanation.contains(&"Current: Voltage at +5% and Resistance at −3%".to_string()));
+        assert!(explanation.contains(&"Voltage: Voltage at +5%".to_string()));
+
+        ohm_law.update(Message::ResultRowSelected(1));
+        assert_eq!(ohm_law.selected_row, None);
+
+        // у отрицательного напряжения максимум — на стороне минуса допуска
+        ohm_law.update(Message::InputVoltageChanged("-10 +5% -2%".to_string()));
+        let explanation = ohm_law.row_explanation(1);
+        assert!(explanation.contains(&"Voltage: Voltage at −2%".to_string()));
+        assert!(explanation.contains(&"Current: Voltage at −2% and Resistance at +1%".to_string()));
+    }
+
+    #[test]
+    fn test_overdetermined_consistent() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::MeasurementModeToggled(true));
+        ohm_law.update(Message::InputVoltageChanged("10 1%".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2 1%".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("5.02 1%".to_string()));
+
+        assert!(matches!(ohm_law.calc_type, CalcType::Overdetermined));
+        assert!(ohm_law.fields_enable.power);
+        assert!(ohm_law.overdetermined.consistent);
+        // P from V-I, V-R and I-R
+        assert_eq!(ohm_law.overdetermined.power.len(), 3);
+        // R from V-I plus the entered one
+        assert_eq!(ohm_law.overdetermined.resistance.len(), 2);
+        assert_eq!(
+            ohm_law.data.power.clone().unwrap().get_nominal_value(),
+            20.0
+        );
+    }
+
+    #[test]
+    fn test_overdetermined_without_tolerance() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::MeasurementModeToggled(true));
+        ohm_law.update(Message::InputVoltageChanged("3".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("0.1".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("30".to_string()));
+
+        assert!(ohm_law.overdetermined.consistent);
+    }
+
+    #[test]
+    fn test_overdetermined_exact_within_band() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::MeasurementModeToggled(true));
+        ohm_law.update(Message::InputVoltageChanged("5 1%".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("1".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("5".to_string()));
+        assert!(ohm_law.overdetermined.consistent);
+
+        // V = I × R = 5.1V без допуска за пределами 5V ±1%
+        ohm_law.update(Message::InputResistanceChanged("5.1".to_string()));
+        assert!(!ohm_law.overdetermined.consistent);
+    }
+
+    #[test]
+    fn test_overdetermined_zero_divisor() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::MeasurementModeToggled(true));
+        ohm_law.update(Message::InputVoltageChanged("1".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("0".to_string()));
+        ohm_law.update(Message::InputPowerChanged("1".to_string()));
+
+        assert!(matches!(ohm_law.calc_type, CalcType::Overdetermined));
+        // ток только из пары V-P: 1A
+        assert_eq!(ohm_law.overdetermined.current.len(), 1);
+        assert_eq!(
+            ohm_law.data.current.clone().unwrap().get_nominal_value(),
+            1.0
+        );
+        // R из V-P равно 1R, введено 0R
+        assert_eq!(ohm_law.overdetermined.resistance.len(), 2);
+        assert!(!ohm_law.overdetermined.consistent);
+    }
+
+    #[test]
+    fn test_zero_divisor() {
+        fn empty(data: &OhmData, field: FieldId) -> bool {
+            let value = match field {
+                FieldId::Voltage => data.voltage.as_ref().err(),
+                FieldId::Current => data.current.as_ref().err(),
+                FieldId::Resistance => data.resistance.as_ref().err(),
+                FieldId::Power => data.power.as_ref().err(),
+                FieldId::Area => None,
+            };
+            value == Some(&ParserError::EmptyInput)
+        }
+
+        // V / I, P / V, R = V / I при I = P / V = 0, P / I
+        for [(a, a_raw), (b, b_raw)] in [
+            [(FieldId::Voltage, "10"), (FieldId::Current, "0")],
+            [(FieldId::Voltage, "0"), (FieldId::Power, "5")],
+            [(FieldId::Voltage, "10"), (FieldId::Power, "0")],
+            [(FieldId::Current, "0"), (FieldId::Power, "5")],
+        ] {
+            let mut ohm_law = OhmLaw::default();
+            ohm_law.update(Message::input(a, a_raw.to_string()));
+            ohm_law.update(Message::input(b, b_raw.to_string()));
+
+            for field in FieldId::SWEEP.into_iter().filter(|f| *f != a && *f != b) {
+                assert!(
+                    empty(&ohm_law.data, field),
+                    "{:?} from {:?}, {:?}",
+                    field,
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_overdetermined_inconsistent() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::MeasurementModeToggled(true));
+        ohm_law.update(Message::InputVoltageChanged("10 1%".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2 1%".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("6 1%".to_string()));
+
+        assert!(matches!(ohm_law.calc_type, CalcType::Overdetermined));
+        assert!(!ohm_law.overdetermined.consistent);
+
+        // back to a pair clears the comparison
+        ohm_law.update(Message::InputResistanceChanged("".to_string()));
+        assert!(matches!(ohm_law.calc_type, CalcType::VcRp));
+        assert!(ohm_law.overdetermined.power.is_empty());
+    }
+
+    #[test]
+    fn test_dirty() {
+        let mut ohm_law = OhmLaw::default();
+        assert!(!ohm_law.is_dirty());
+
+        // не ввод пользователя
+        ohm_law.update(Message::MeasurementModeToggled(true));
+        ohm_law.update(Message::ResultRowSelected(0));
+        assert!(!ohm_law.is_dirty());
+
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert!(ohm_law.is_dirty());
+
+        // the calculated fields do not keep the scene dirty
+        ohm_law.update(Message::InputVoltageChanged("".to_string()));
+        ohm_law.update(Message::InputCurrentChanged(" ".to_string()));
+        assert!(!ohm_law.is_dirty());
+    }
+
+    #[test]
+    fn test_calc_type_description() {
+        assert_eq!(
+            CalcType::VrCp.description(),
+            "Inputs: Voltage, Resistance → calculating Current and Power"
+        );
+
+        let descriptions = [
+            (
+                CalcType::VcRp,
+                "Voltage, Current → calculating Resistance and Power",
+            ),
+            (
+                CalcType::VpCr,
+                "Voltage, Power → calculating Current and Resistance",
+            ),
+            (
+                CalcType::CrVp,
+                "Current, Resistance → calculating Voltage and Power",
+            ),
+            (
+                CalcType::CpVr,
+                "Current, Power → calculating Voltage and Resistance",
+            ),
+            (
+                CalcType::RpVc,
+                "Resistance, Power → calculating Voltage and Current",
+            ),
+        ];
+        for (calc_type, description) in descriptions {
+            assert_eq!(calc_type.description(), format!("Inputs: {}", description));
+        }
+
+        assert_eq!(
+            CalcType::None.description(),
+            "Fill in any two fields to calculate the other two"
+        );
+        assert_eq!(
+            CalcType::Overdetermined.description(),
+            "More than two inputs: every pair is calculated and compared"
+        );
+    }
+
+    #[test]
+    fn test_calculated_resistance() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert_eq!(ohm_law.calculated_resistance(), [("R".to_string(), 5.0)]);
+
+        // введённое сопротивление не проверяется
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("5".to_string()));
+        assert!(ohm_law.calculated_resistance().is_empty());
+    }
+
+    #[test]
+    fn test_work_steps_vcrp() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        assert!(ohm_law.steps().is_empty());
+
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert_eq!(
+            ohm_law.steps(),
+            [
+                "R = V / I = 10.00V / 2.00A = 5.00R",
+                "P = V × I = 10.00V × 2.00A = 20.00W",
+            ]
+        );
+
+        // переключатель панели не меняет расчёт
+        ohm_law.update(Message::ShowWorkToggled(true));
+        assert!(ohm_law.show_work);
+        assert_eq!(ohm_law.steps().len(), 2);
+    }
+
+    #[test]
+    fn test_prefix_selected() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("12 5%".to_string()));
+        ohm_law.update(Message::PrefixSelected(FieldId::Voltage, Dim::Kilo));
+        assert_eq!(ohm_law.data_raw.voltage, "12k 5%");
+        assert_eq!(
+            ohm_law.data.voltage.as_ref().unwrap().get_nominal_value(),
+            12e3
+        );
+
+        // другая приставка заменяет прежнюю
+        ohm_law.update(Message::PrefixSelected(FieldId::Voltage, Dim::Milli));
+        assert_eq!(ohm_law.data_raw.voltage, "12m 5%");
+
+        // поле, заблокированное типом расчёта, не меняется
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert!(matches!(ohm_law.calc_type, CalcType::VcRp));
+        let power = ohm_law.data_raw.power.clone();
+        ohm_law.update(Message::PrefixSelected(FieldId::Power, Dim::Mega));
+        assert_eq!(ohm_law.data_raw.power, power);
+    }
+
+    #[test]
+    fn test_input_events() {
+        let mut ohm_law = OhmLaw::default();
+        let mut send = |field, event| ohm_law.update(Message::from_input(field, event));
+        send(FieldId::Current, Event::Input("2".to_string()));
+        send(FieldId::Current, Event::Prefix(Dim::Milli));
+        send(FieldId::Area, Event::Input("0.5".to_string()));
+        send(FieldId::Area, Event::Clear);
+        send(FieldId::Voltage, Event::LockToggled);
+        assert_eq!(ohm_law.data_raw.current, "2m");
+        assert!(ohm_law.area_raw.is_empty());
+        assert!(ohm_law.is_locked(FieldId::Voltage));
+
+        assert!(matches!(
+            Message::from_input(FieldId::Power, Event::Submit),
+            Message::FieldSubmitted(FieldId::Power)
+        ));
+        assert!(matches!(
+            Message::from_input(FieldId::Resistance, Event::CopyErrorReport),
+            Message::CopyErrorReport(FieldId::Resistance)
+        ));
+    }
+
+    #[test]
+    fn test_preset_selected() {
+        let mut ohm_law = OhmLaw::default();
+        let usb = Preset::new("USB", Kind::Voltage, "5 5%").unwrap();
+        ohm_law.update(Message::InputVoltageChanged("12".to_string()));
+        ohm_law.update(Message::PresetSelected(FieldId::Voltage, usb.clone()));
+        assert_eq!(ohm_law.data_raw.voltage, "5 5%");
+        assert_eq!(
+            ohm_law.data.voltage.as_ref().unwrap().get_nominal_value(),
+            5.0
+        );
+
+        // пресет другого вида в поле не попадает
+        ohm_law.update(Message::PresetSelected(FieldId::Resistance, usb));
+        assert_eq!(ohm_law.data_raw.resistance, "");
+    }
+
+    #[test]
+    fn test_prefilled() {
+        let (ohm_law, notices) = OhmLaw::prefilled(vec![
+            (FieldId::Voltage, "12 5%".to_string()),
+            (FieldId::Current, "100m".to_string()),
+        ]);
+        assert!(notices.is_empty());
+        assert_eq!(ohm_law.status(), SceneStatus::Solved);
+        assert_eq!(ohm_law.data_raw.voltage, "12 5%");
+
+        // неверное значение и вычисляемое поле пропускаются
+        let (ohm_law, notices) = OhmLaw::prefilled(vec![
+            (FieldId::Voltage, "12x".to_string()),
+            (FieldId::Current, "1".to_string()),
+            (FieldId::Resistance, "10".to_string()),
+            (FieldId::Power, "5".to_string()),
+        ]);
+        assert_eq!(notices.len(), 2);
+        assert!(notices[0].starts_with("--voltage: "));
+        assert!(notices[1].starts_with("--power: "));
+        assert_eq!(ohm_law.data_raw.voltage, "");
+        assert_eq!(ohm_law.data_raw.resistance, "10");
+    }
+
+    #[test]
+    fn test_load_example() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputResistanceChanged("1k".to_string()));
+        ohm_law.update(Message::LoadExample);
+
+        // 12V / 2A = 6R, 12V × 2A = 24W
+        assert_eq!(ohm_law.status(), SceneStatus::Solved);
+        assert_eq!(ohm_law.data_raw.resistance, "");
+        assert_eq!(
+            ohm_law.data.resistance.as_ref().unwrap().get_value_nom(),
+            "6.00R"
+        );
+        assert_eq!(
+            ohm_law.data.power.as_ref().unwrap().get_value_nom(),
+            "24.00W"
+        );
+    }
+
+    #[test]
+    fn test_next_field() {
+        let mut ohm_law = OhmLaw::default();
+        assert_eq!(ohm_law.next_field(FieldId::Voltage), Some(FieldId::Current));
+        assert_eq!(ohm_law.next_field(FieldId::Power), Some(FieldId::Area));
+        assert_eq!(ohm_law.next_field(FieldId::Area), Some(FieldId::Voltage));
+
+        // поля, заблокированные типом расчёта, пропускаются
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("1k".to_string()));
+        assert!(matches!(ohm_law.calc_type, CalcType::VrCp));
+        assert_eq!(
+            ohm_law.next_field(FieldId::Voltage),
+            Some(FieldId::Resistance)
+        );
+        assert_eq!(ohm_law.next_field(FieldId::Resistance), Some(FieldId::Area));
+
+        ohm_law.update(Message::LockToggled(FieldId::Voltage));
+        assert_eq!(ohm_law.next_field(FieldId::Area), Some(FieldId::Resistance));
+    }
+
+    #[test]
+    fn test_lock_field() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::LockToggled(FieldId::Voltage));
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert!(matches!(ohm_law.calc_type, CalcType::VcRp));
+
+        // закреплённое поле не очищается
+        ohm_law.update(Message::ClearField(FieldId::Voltage));
+        assert_eq!(ohm_law.data_raw.voltage, "10");
+        assert!(matches!(ohm_law.calc_type, CalcType::VcRp));
+
+        // the locked value stays in the next pair
+        ohm_law.update(Message::ClearField(FieldId::Current));
+        assert!(matches!(ohm_law.calc_type, CalcType::None));
+        ohm_law.update(Message::InputResistanceChanged("5".to_string()));
+        assert!(matches!(ohm_law.calc_type, CalcType::VrCp));
+        assert_eq!(ohm_law.data_raw.voltage, "10");
+
+        // a locked field blocked by the pair keeps its text
+        ohm_law.update(Message::ClearField(FieldId::Resistance));
+        ohm_law.update(Message::InputPowerChanged("5x".to_string()));
+        ohm_law.update(Message::LockToggled(FieldId::Power));
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert!(matches!(ohm_law.calc_type, CalcType::VcRp));
+        assert!(!ohm_law.fields_enable.power);
+        assert_eq!(ohm_law.data_raw.power, "5x");
+
+        ohm_law.update(Message::LockToggled(FieldId::Voltage));
+        ohm_law.update(Message::ClearField(FieldId::Voltage));
+        assert!(ohm_law.data_raw.voltage.is_empty());
+        assert_eq!(ohm_law.locked, [FieldId::Power]);
+    }
+
+    #[test]
+    fn test_sweep() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputResistanceChanged("10 1%".to_string()));
+        ohm_law.update(Message::SweepModeToggled(true));
+        ohm_law.update(Message::InputSweepChanged("1..3 step 1".to_string()));
+        assert_eq!(ohm_law.sweep.rows.len(), 3);
+
+        let row = &ohm_law.sweep.rows[2];
+        assert_eq!(row.voltage.as_ref().unwrap().get_value_nom(), "3.00V");
+        assert_eq!(row.current.as_ref().unwrap().get_value_nom(), "300.00mA");
+        assert_eq!(row.power.as_ref().unwrap().get_value_nom(), "900.00mW");
+        // the tolerance of the fixed input is kept
+        assert_eq!(
+            row.current.as_ref().unwrap().get_tol_percent_plus(),
+            "1.00%"
+        );
+
+        assert_eq!(
+            ohm_law.sweep_csv(),
+            "voltage_V,current_A,resistance_Ohm,power_W,\
+             voltage_formula,current_formula,resistance_formula,power_formula,inputs\n\
+             1,0.1,10,0.1,,I = V / R,,P = V × I,voltage=1;resistance=10 1%\n\
+             2,0.2,10,0.4,,I = V / R,,P = V × I,voltage=2;resistance=10 1%\n\
+             3,0.3,10,0.8999999999999999,,I = V / R,,P = V × I,voltage=3;resistance=10 1%\n"
+        );
+
+        // переменная развёртки должна быть второй величиной пары
+        ohm_law.update(Message::SweepVariableSelected(FieldId::Resistance));
+        assert!(ohm_law.sweep.rows.is_empty());
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert_eq!(ohm_law.sweep.rows.len(), 3);
+        assert_eq!(
+            ohm_law.sweep.rows[0]
+                .voltage
+                .as_ref()
+                .unwrap()
+                .get_value_nom(),
+            "2.00V"
+        );
+
+        // ввод с запятой берётся в кавычки
+        ohm_law.update(Message::InputCurrentChanged("1,000m".to_string()));
+        assert!(ohm_law
+            .sweep_csv()
+            .ends_with(",V = I × R,,,P = V × I,\"current=1,000m;resistance=3\"\n"));
+
+        ohm_law.update(Message::SweepModeToggled(false));
+        assert!(ohm_law.sweep.rows.is_empty());
+    }
+
+    #[test]
+    fn test_sweep_zero_divisor() {
         let mut ohm_law = OhmLaw::default();
-        ohm_law.calc_type = CalcType::None;
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::SweepModeToggled(true));
+        ohm_law.update(Message::SweepVariableSelected(FieldId::Current));
+        ohm_law.update(Message::InputSweepChanged("0..2 step 1".to_string()));
+        assert_eq!(ohm_law.sweep.rows.len(), 3);
+
+        // при I = 0 сопротивление не определено, строка остаётся с пустыми R и P
+        let row = &ohm_law.sweep.rows[0];
+        assert_eq!(row.current.as_ref().unwrap().get_nominal_value(), 0.0);
+        assert_eq!(row.resistance, Err(ParserError::EmptyInput));
+        assert_eq!(row.power, Err(ParserError::EmptyInput));
+        let row = &ohm_law.sweep.rows[2];
+        assert_eq!(row.resistance.as_ref().unwrap().get_value_nom(), "5.00R");
+
+        let csv = ohm_law.sweep_csv();
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("10,0,,,,,R = V / I,P = V × I,voltage=10;current=0")
+        );
+    }
+
+    #[test]
+    fn test_zero_resistance_with_power() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputResistanceChanged("0".to_string()));
+        ohm_law.update(Message::InputPowerChanged("1".to_string()));
+
+        assert!(matches!(ohm_law.calc_type, CalcType::RpVc));
+        assert_eq!(ohm_law.data.voltage, Err(ParserError::EmptyInput));
+        assert_eq!(ohm_law.data.current, Err(ParserError::EmptyInput));
+
+        ohm_law.update(Message::InputResistanceChanged("-5".to_string()));
+        assert_eq!(ohm_law.data.voltage, Err(ParserError::EmptyInput));
+
+        // точки развёртки с R <= 0 не берут V и I из основного расчёта
+        ohm_law.update(Message::InputResistanceChanged("4".to_string()));
+        ohm_law.update(Message::SweepModeToggled(true));
+        ohm_law.update(Message::SweepVariableSelected(FieldId::Resistance));
+        ohm_law.update(Message::InputSweepChanged("-4..4 step 4".to_string()));
+        let rows = &ohm_law.sweep.rows;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].voltage, Err(ParserError::EmptyInput));
+        assert_eq!(rows[1].current, Err(ParserError::EmptyInput));
+        assert_eq!(rows[2].voltage.as_ref().unwrap().get_value_nom(), "2.00V");
+    }
+
+    #[test]
+    fn test_disabled_field_reset() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputCurrentChanged("2x".to_string()));
+        assert!(matches!(
+            ohm_law.data.current,
+            Err(ParserError::IncorrectInput(_))
+        ));
+
+        // при R = 0 ток не рассчитывается: прежняя ошибка не должна остаться под полем
+        ohm_law.update(Message::InputVoltageChanged("12".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("0".to_string()));
+        assert!(matches!(ohm_law.calc_type, CalcType::VrCp));
+        assert!(!ohm_law.fields_enable.current);
+        assert!(ohm_law.data_raw.current.is_empty());
+        assert_eq!(ohm_law.data.current, Err(ParserError::EmptyInput));
+        assert_eq!(ohm_law.data.power, Err(ParserError::EmptyInput));
+        assert_eq!(ohm_law.parse_error(FieldId::Current), None);
+    }
+
+    #[test]
+    fn test_clear_field() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        ohm_law.update(Message::InputAreaChanged("1.5".to_string()));
+        assert!(!ohm_law.fields_enable.resistance);
+        assert!(!ohm_law.fields_enable.power);
+
+        ohm_law.update(Message::ClearField(FieldId::Voltage));
+        assert!(ohm_law.data_raw.voltage.is_empty());
+        assert!(matches!(ohm_law.data.voltage, Err(ParserError::EmptyInput)));
+        assert!(matches!(ohm_law.calc_type, CalcType::None));
+        // заблокированные поля снова доступны
+        assert!(ohm_law.fields_enable.resistance);
+        assert!(ohm_law.fields_enable.power);
+        // остальные поля не затронуты
+        assert_eq!(ohm_law.data_raw.current, "2");
+        assert_eq!(ohm_law.status(), SceneStatus::Incomplete);
+
+        ohm_law.update(Message::ClearField(FieldId::Area));
+        assert!(ohm_law.area_raw.is_empty());
+        assert!(matches!(ohm_law.area, Err(ParserError::EmptyInput)));
+    }
+
+    #[test]
+    fn test_status() {
+        let mut ohm_law = OhmLaw::default();
+        assert_eq!(ohm_law.status(), SceneStatus::Empty);
+
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        assert_eq!(ohm_law.status(), SceneStatus::Incomplete);
+
+        ohm_law.update(Message::InputCurrentChanged("2x".to_string()));
+        assert_eq!(ohm_law.status(), SceneStatus::Error);
+
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert_eq!(ohm_law.status(), SceneStatus::Solved);
+    }
+
+    #[test]
+    fn test_under_text() {
+        let example = "10.5";
+        let text = |raw: &str, value: &Result<Voltage, ParserError>| {
+            under_text(raw, value, &None, example).0
+        };
+
+        assert_eq!(text("", &Err(ParserError::EmptyInput)), example);
+        assert_eq!(
+            text("1x", &Err(ParserError::IncorrectInput("error".to_string()))),
+            "error"
+        );
+        assert_eq!(text("12k", &"12k".parse::<Voltage>()), "= 12000 V");
+        // calculated value, the field is empty
+        assert_eq!(text("", &"12k".parse::<Voltage>()), example);
+
+        default_tolerance::set_default_tolerances(default_tolerance::DefaultTolerances {
+            voltage: Some(2.0),
+            ..default_tolerance::DefaultTolerances::OFF
+        });
+        assert_eq!(
+            text("12k", &"12k".parse::<Voltage>()),
+            "= 12000 V (assumed ±2%)"
+        );
+        assert_eq!(text("12k 0%", &"12k 0%".parse::<Voltage>()), "= 12000 V");
+        default_tolerance::set_default_tolerances(default_tolerance::DefaultTolerances::OFF);
+
+        let warning = Some("warning".to_string());
+        let (text, color) = under_text("12k", &"12k".parse::<Voltage>(), &warning, example);
+        assert_eq!(text, "warning");
+        let theme = iced::Theme::Light;
+        assert_eq!(color(&theme).color, style::warning_text(&theme).color);
+    }
+
+    #[test]
+    fn test_current_density() {
+        let current = "10 5%".parse::<Current>().unwrap();
+        assert_eq!(
+            current_density(&current, 2.5),
+            Some(CurrentDensity {
+                nominal: 4.0,
+                min: 3.8,
+                max: 4.2,
+            })
+        );
+        // направление тока не важно
+        let current = "-10".parse::<Current>().unwrap();
+        assert_eq!(current_density(&current, 2.0).unwrap().nominal, 5.0);
+        assert_eq!(current_density(&current, 0.0), None);
+
+        assert_eq!(parse_area(""), Err(ParserError::EmptyInput));
+        assert_eq!(parse_area("0.75"), Ok(0.75));
+        assert!(matches!(
+            parse_area("0"),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_aux_outputs() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("1k".to_string()));
+        let conductance = ohm_law.aux.conductance.unwrap();
+        assert_eq!(conductance.get_value_nom(), "1.00mS");
+        // без сечения плотность не считается
+        assert_eq!(ohm_law.aux.current_density, None);
+
+        ohm_law.update(Message::InputAreaChanged("0.5".to_string()));
+        assert_eq!(ohm_law.aux.current_density.unwrap().nominal, 0.02);
+        assert_eq!(ohm_law.aux.density_warning, None);
+
+        plausibility::set_limits(plausibility::Limits {
+            max_current_density: Some(0.01),
+            ..plausibility::Limits::OFF
+        });
+        ohm_law.update(Message::InputAreaChanged("0.5".to_string()));
+        assert!(ohm_law.aux.density_warning.is_some());
+        ohm_law.update(Message::InputAreaChanged("5".to_string()));
+        assert_eq!(ohm_law.aux.density_warning, None);
+        plausibility::set_limits(plausibility::Limits::OFF);
+
+        ohm_law.update(Message::InputAreaChanged("-1".to_string()));
+        assert_eq!(ohm_law.aux.current_density, None);
+        assert_eq!(ohm_law.status(), SceneStatus::Error);
+
+        // R = 0: current cannot be calculated, no conductance either
+        ohm_law.update(Message::InputAreaChanged("".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("0".to_string()));
+        assert_eq!(ohm_law.aux.conductance, None);
+    }
+
+    #[test]
+    fn test_power_rating() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("100".to_string()));
+        // 1 Вт при запасе по умолчанию ×2
+        assert_eq!(ohm_law.aux.power_rating.unwrap().get_value_nom(), "2.00W");
+        assert_eq!(
+            ohm_law.aux.supply_current.unwrap().get_value_nom(),
+            "120.00mA"
+        );
+
+        margins::set_margins(margins::Margins {
+            derating: 3.0,
+            headroom: 1.0,
+        });
+        ohm_law.recalculate();
+        assert_eq!(ohm_law.aux.power_rating.unwrap().get_value_nom(), "3.00W");
+        assert_eq!(
+            ohm_law.aux.supply_current.unwrap().get_value_nom(),
+            "100.00mA"
+        );
+
+        margins::set_margins(margins::Margins::DEFAULT);
+    }
+
+    #[test]
+    fn test_plausibility_warning() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("10M".to_string()));
+        assert_eq!(ohm_law.warnings, [None, None, None, None]);
+
+        plausibility::set_limits(plausibility::Limits {
+            max_current: Some(10.0),
+            max_power: Some(100.0),
+            ..plausibility::Limits::OFF
+        });
+        ohm_law.update(Message::InputCurrentChanged("10M".to_string()));
+        assert!(ohm_law.warnings[1].is_some());
+        // расчётная мощность не проверяется, расчёт не блокируется
+        assert!(ohm_law.warnings[3].is_none());
+        assert!(ohm_law.data.power.is_ok());
+
+        ohm_law.update(Message::InputCurrentChanged("10m".to_string()));
+        assert!(ohm_law.warnings[1].is_none());
+
+        plausibility::set_limits(plausibility::Limits::OFF);
+    }
+
+    #[test]
+    fn test_frozen() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2".to_string()));
+        assert!(!ohm_law.fields_enable.resistance);
+
+        ohm_law.update(Message::FrozenToggled(true));
+        assert!(ohm_law.fields_enable.resistance);
+        ohm_law.update(Message::InputVoltageChanged("20".to_string()));
+        ohm_law.update(Message::ClearField(FieldId::Current));
+        ohm_law.update(Message::InputResistanceChanged("4".to_string()));
+        ohm_law.recalculate();
+
+        // ввод сохранён, расчёт прежний
+        assert_eq!(ohm_law.data_raw.voltage, "20");
+        assert_eq!(ohm_law.data_raw.current, "");
+        assert_eq!(ohm_law.data_raw.resistance, "4");
+        assert_eq!(ohm_law.data.voltage.as_ref().unwrap().value, 10.0);
+        assert_eq!(ohm_law.data.resistance.as_ref().unwrap().value, 5.0);
+        assert!(matches!(ohm_law.calc_type, CalcType::VcRp));
+
+        ohm_law.update(Message::FrozenToggled(false));
+        assert!(matches!(ohm_law.calc_type, CalcType::VrCp));
+        assert_eq!(ohm_law.data.current.as_ref().unwrap().value, 5.0);
+        assert!(!ohm_law.fields_enable.current);
+    }
+
+    #[test]
+    fn test_unit_mismatch() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("12A".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2mA".to_string()));
+
+        assert_eq!(ohm_law.data.voltage.as_ref().unwrap().value, 12.0);
+        assert_eq!(
+            ohm_law.warnings[0].as_deref(),
+            Some("unit 'A' doesn't match Voltage")
+        );
+        assert_eq!(ohm_law.warnings[1], None);
+        assert!(ohm_law.data.resistance.is_ok());
+    }
+
+    #[test]
+    fn test_result_json() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10 5%".to_string()));
+        ohm_law.update(Message::InputResistanceChanged("1k".to_string()));
+
+        let result: serde_json::Value = serde_json::from_str(&ohm_law.result_json(true)).unwrap();
+        assert_eq!(result["calc_type"], "VRCP");
+        assert_eq!(result["voltage"]["nominal"], 10.0);
+        assert_eq!(result["voltage"]["max"], 10.5);
+        assert_eq!(result["resistance"]["nominal"], 1000.0);
+        let current = &result["current"];
+        assert!((current["nominal"].as_f64().unwrap() - 0.01).abs() < 1e-12);
+        assert!(current["min"].as_f64().unwrap() < current["max"].as_f64().unwrap());
+        assert!(current["tolerance"].is_object());
+        assert!((result["power"]["nominal"].as_f64().unwrap() - 0.1).abs() < 1e-12);
+
+        // нерешённые величины сериализуются как null
+        ohm_law.update(Message::InputResistanceChanged(String::new()));
+        let result: serde_json::Value = serde_json::from_str(&ohm_law.result_json(true)).unwrap();
+        assert_eq!(result["calc_type"], "None");
+        assert_eq!(result["voltage"]["nominal"], 10.0);
+        assert!(result["current"].is_null());
+        assert!(result["resistance"].is_null());
+        assert!(result["power"].is_null());
+        // введённое значение ссылается на себя
+        assert_eq!(
+            result["voltage"]["provenance"]["formula"],
+            serde_json::Value::Null
+        );
+        assert_eq!(result["voltage"]["provenance"]["inputs"][0]["raw"], "10 5%");
+    }
+
+    #[test]
+    fn test_error_report() {
+        let mut ohm_law = OhmLaw::default();
+        assert_eq!(ohm_law.error_report(FieldId::Voltage), None);
+
+        ohm_law.update(Message::InputVoltageChanged("12 5x".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2m".to_string()));
+        let report = ohm_law.error_report(FieldId::Voltage).unwrap();
+        assert!(report.contains("field: voltage\ninput: \"12 5x\"\n"));
+        assert!(report.ends_with("error: unexpected character 'x' at position 4\n"));
+        assert_eq!(ohm_law.error_report(FieldId::Current), None);
+
+        ohm_law.update(Message::InputAreaChanged("x".to_string()));
+        assert!(ohm_law
+            .error_report(FieldId::Area)
+            .unwrap()
+            .contains("field: area"));
+    }
+
+    #[test]
+    fn test_hidden_rows() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10 5%".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2m".to_string()));
+        ohm_law.update(Message::ResultRowSelected(5));
+
+        ohm_law.update(Message::RowVisibilityToggled(ResultRow::TolPlusPercent));
+        ohm_law.update(Message::RowVisibilityToggled(ResultRow::TolMinusPercent));
+        ohm_law.update(Message::RowVisibilityToggled(ResultRow::ValueMax));
+        assert_eq!(ohm_law.selected_row, None);
+
+        // выбор сохраняется в настройках и действует в новой сцене
+        let hidden = result_rows::hidden_rows().ohm_law;
+        assert!(hidden.contains(ResultRow::TolPlusPercent));
+        assert!(!hidden.contains(ResultRow::TolPlus));
+        assert!(result_rows::hidden_rows().voltage_divider.is_empty());
+
+        let result: serde_json::Value = serde_json::from_str(&ohm_law.result_json(false)).unwrap();
+        let resistance = result["resistance"].as_object().unwrap();
+        assert!(!resistance.contains_key("tolerance"));
+        assert!(!resistance.contains_key("max"));
+        assert_eq!(resistance["min"], 4750.0);
+        assert_eq!(resistance["provenance"]["formula"], "R = V / I");
+
+        let result: serde_json::Value = serde_json::from_str(&ohm_law.result_json(true)).unwrap();
+        assert_eq!(result["resistance"]["tolerance"]["plus"], 5.0);
+
+        // снятие с одной процентной строки возвращает допуск в экспорт
+        ohm_law.update(Message::RowVisibilityToggled(ResultRow::TolPlusPercent));
+        let result: serde_json::Value = serde_json::from_str(&ohm_law.result_json(false)).unwrap();
+        assert!(result["resistance"]["tolerance"].is_object());
+
+        result_rows::set_hidden_rows(result_rows::HiddenRows::NONE);
+    }
+
+    #[test]
+    fn test_result_json_fixture() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10 5%".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2m".to_string()));
+
+        let result: serde_json::Value = serde_json::from_str(&ohm_law.result_json(true)).unwrap();
+        let fixture: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/ohm_law_result.json")).unwrap();
+        assert_eq!(result, fixture);
+    }
+
+    #[test]
+    fn test_diagnostic_report() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10 5%".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2m".to_string()));
+
+        let report: serde_json::Value = serde_json::from_str(&ohm_law.diagnostic_report()).unwrap();
+        let mut fixture: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/ohm_law_report.json")).unwrap();
+        fixture["version"] = env!("CARGO_PKG_VERSION").into();
+
+        assert_eq!(report, fixture);
+    }
+
+    #[test]
+    fn test_pin_result() {
+        let mut ohm_law = OhmLaw::default();
+        ohm_law.update(Message::InputVoltageChanged("10".to_string()));
+        ohm_law.update(Message::InputCurrentChanged("2m".to_string()));
+        ohm_law.update(Message::PinResult);
+
+        ohm_law.update(Message::InputVoltageChanged("12".to_string()));
+        let delta = ohm_law.pinned.unwrap().delta(&ohm_law.snapshot());
+        assert!((delta[0].unwrap() - 20.0).abs() < 1e-9);
+        assert_eq!(delta[1], Some(0.0));
+        assert!((delta[2].unwrap() - 20.0).abs() < 1e-9);
+        assert!((delta[3].unwrap() - 20.0).abs() < 1e-9);
+
+        ohm_law.update(Message::Unpin);
+        assert!(ohm_law.pinned.is_none());
+    }
+
+    #[test]
+    fn test_calculating_none() {
+        let mut ohm_law = OhmLaw {
+            calc_type: CalcType::None,
+            ..Default::default()
+        };
 
         ohm_law.calculating();
 
@@ -9,18 +9,149 @@
 //! - `"5%"` is parsed as `TolPlusMinus(5.0)`
 //! - `"+5%"` is parsed as `TolPlus(5.0)`
 //! - `"-5%"` is parsed as `TolMinus(5.0)`
+//! - `"+/-0.5"` is parsed as `AbsTolPlusMinus(0.5)`, an absolute tolerance in base units
+//! - `"s=0.05"` is parsed as `StdDev(0.05)`, a standard uncertainty in base units
 //! - `"10m"` is parsed as `NumberSuffix(10.0, Dim::Milli)`
+//!
+//! A leading sign belongs to the nominal value unless the number is followed by `%`:
+//! `"-5"` is `Number(-5.0)`, `"+12"` is `Number(12.0)`, while `"-5%"` is `TolMinus(5.0)`.
+//!
+//! A value can also be given by its datasheet limits: `"typ 12 min 11.4 max 12.6"` is parsed
+//! as `Number(12.0)`, `TolPlus(5.0)`, `TolMinus(5.0)`.
+//!
+//! On failure [`parse_blocks`] reports where the input went wrong and why, e.g. `"12 5x"`
+//! fails with an unexpected character `x` at offset 4.
+//!
+//! Input longer than [`max_input_len`] is rejected by [`check_length`] before any parser
+//! runs, so a paste of a whole document can't stall the interface.
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt;
 
 use crate::types::Dim;
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{char, space1},
-    multi::separated_list1,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, one_of},
+    combinator::not,
+    error::ErrorKind,
     number::complete::double,
     IResult,
 };
 
+/// What went wrong at the failing position.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorCategory {
+    /// A specific character is missing, e.g. the `%` of a tolerance
+    Expected(char),
+    ExpectedNumber,
+    UnexpectedCharacter(char),
+    UnexpectedEnd,
+    /// A datasheet limit keyword is expected: `typ`, `min` or `max`
+    ExpectedLimit,
+    MissingTyp,
+    /// `min` or `max` is given relative to a zero `typ`, e.g. `typ 0 max 1`
+    ZeroTyp,
+    /// The number of a tolerance is negative, e.g. `+-5%` or a `min` limit above `typ`
+    NegativeTolerance,
+    /// Digits of one number are grouped by different separators, e.g. `1,000_000`
+    MixedGrouping,
+    /// Digits grouped by a space are followed by a prefix or a unit, e.g. `12 100m`
+    AmbiguousGrouping,
+    /// The input is longer than the limit of characters, see [`max_input_len`]
+    TooLong(usize),
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorCategory::Expected(c) => write!(f, "expected '{}'", c),
+            ErrorCategory::ExpectedNumber => write!(f, "expected a number"),
+            ErrorCategory::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+            ErrorCategory::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ErrorCategory::ExpectedLimit => write!(f, "expected 'typ', 'min' or 'max'"),
+            ErrorCategory::MissingTyp => write!(f, "the 'typ' value is missing"),
+            ErrorCategory::ZeroTyp => write!(f, "limits need a 'typ' value other than zero"),
+            ErrorCategory::NegativeTolerance => write!(f, "a tolerance cannot be negative"),
+            ErrorCategory::MixedGrouping => write!(f, "mixed digit group separators"),
+            ErrorCategory::AmbiguousGrouping => {
+                write!(f, "a number grouped by spaces cannot take a prefix or unit")
+            }
+            ErrorCategory::TooLong(limit) => {
+                write!(f, "the input is longer than {} characters", limit)
+            }
+        }
+    }
+}
+
+/// Parse error with the byte offset of the failing position in the whole input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub category: ErrorCategory,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.category, self.offset)
+    }
+}
+
+/// Error type of the block parsers. It keeps the remaining input at the failing position,
+/// the alternatives of [`alt`] keep the error that got furthest into the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockError<'a> {
+    input: &'a str,
+    category: ErrorCategory,
+}
+
+impl<'a> BlockError<'a> {
+    fn unexpected(input: &'a str) -> Self {
+        let category = match input.chars().next() {
+            Some(c) => ErrorCategory::UnexpectedCharacter(c),
+            None => ErrorCategory::UnexpectedEnd,
+        };
+
+        Self { input, category }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for BlockError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::Float if !input.is_empty() => Self {
+                input,
+                category: ErrorCategory::ExpectedNumber,
+            },
+            _ => Self::unexpected(input),
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Self {
+            input,
+            category: ErrorCategory::Expected(c),
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        // меньше остаток входа - дальше продвинулся разбор,
+        // при равенстве берём последнюю альтернативу: это разбор простого числа
+        if other.input.len() <= self.input.len() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+type BlockResult<'a> = IResult<&'a str, Block, BlockError<'a>>;
+
 /// Enum for various data types that can be parsed.
 #[derive(Debug, PartialEq)]
 pub enum Block {
@@ -30,56 +161,127 @@ pub enum Block {
     TolPlus(f64),
     /// A simple number (e.g., "5%") treated as both positive and negative tolerance
     TolPlusMinus(f64),
+    /// A symmetric tolerance in base units (e.g., "+/-0.5", "+/-10m"), made relative to the
+    /// nominal value when the quantity is built
+    AbsTolPlusMinus(f64),
+    /// A standard uncertainty in base units (e.g., "s=0.05", "s=5m"): one standard deviation,
+    /// the tolerance of the statistical mode
+    StdDev(f64),
     /// A simple number (e.g., "5.0")
     Number(f64),
     /// A number with a suffix (e.g., "5k", "10m")
     NumberSuffix((f64, Dim)),
 }
 
+/// Parser for the number of a tolerance followed by `%`. The sign of a tolerance goes before
+/// the number, so a negative number (`+-5%`) fails the whole block instead of trying the
+/// other alternatives.
+fn tolerance_number(input: &str) -> IResult<&str, f64, BlockError<'_>> {
+    let (rest, number) = double(input)?;
+    let (rest, _) = char('%')(rest)?;
+
+    if number < 0.0 {
+        return Err(nom::Err::Failure(BlockError {
+            input,
+            category: ErrorCategory::NegativeTolerance,
+        }));
+    }
+
+    Ok((rest, number))
+}
+
 /// Parser for a string in the format "-float%"
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// use your_crate::percentage_minus_parser;
 /// assert_eq!(percentage_minus_parser("-5%"), Ok(("", Block::TolMinus(5.0))));
 /// ```
-fn percentage_minus_parser(input: &str) -> IResult<&str, Block> {
-    let (input, _) = tag("-")(input)?;
-    let (input, number) = double(input)?;
-    let (input, _) = tag("%")(input)?;
+fn percentage_minus_parser(input: &str) -> BlockResult<'_> {
+    let (input, _) = char('-')(input)?;
+    let (input, number) = tolerance_number(input)?;
 
-    Ok((input, Block::TolMinus(number.abs())))
+    Ok((input, Block::TolMinus(number)))
 }
 
 /// Parser for a string in the format "+/-float%"
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// use your_crate::percentage_plus_minus_parser;
 /// assert_eq!(percentage_plus_minus_parser("+/-5%"), Ok(("", Block::TolPlusMinus(5.0))));
 /// ```
-fn percentage_plus_minus_parser(input: &str) -> IResult<&str, Block> {
+fn percentage_plus_minus_parser(input: &str) -> BlockResult<'_> {
     let (input, _) = tag("+/-")(input)?;
-    let (input, number) = double(input)?;
-    let (input, _) = tag("%")(input)?;
+    let (input, number) = tolerance_number(input)?;
 
     Ok((input, Block::TolPlusMinus(number)))
 }
 
+/// Parser for a string in the format "+/-float" with an optional suffix (e.g., "+/-10m").
+/// The value is returned in base units.
+///
+/// # Example
+///
+/// ```ignore
+/// use your_crate::absolute_plus_minus_parser;
+/// assert_eq!(absolute_plus_minus_parser("+/-0.5"), Ok(("", Block::AbsTolPlusMinus(0.5))));
+/// ```
+fn absolute_plus_minus_parser(input: &str) -> BlockResult<'_> {
+    let (rest, _) = tag("+/-")(input)?;
+    let (rest, number) = absolute_number(input, rest)?;
+
+    Ok((rest, Block::AbsTolPlusMinus(number)))
+}
+
+/// Parser for a string in the format "s=float" with an optional suffix (e.g., "s=5m").
+/// The value is returned in base units.
+///
+/// # Example
+///
+/// ```ignore
+/// use your_crate::std_dev_parser;
+/// assert_eq!(std_dev_parser("s=0.05"), Ok(("", Block::StdDev(0.05))));
+/// ```
+fn std_dev_parser(input: &str) -> BlockResult<'_> {
+    let (rest, _) = tag("s=")(input)?;
+    let (rest, number) = absolute_number(input, rest)?;
+
+    Ok((rest, Block::StdDev(number)))
+}
+
+/// Number of an absolute tolerance after its tag, in base units. A negative number fails
+/// the whole block starting at `input`.
+fn absolute_number<'a>(input: &'a str, rest: &'a str) -> IResult<&'a str, f64, BlockError<'a>> {
+    let (rest, number) = match alt((double_suffix_parser, double_parser))(rest)? {
+        (rest, Block::Number(n)) => (rest, n),
+        (rest, Block::NumberSuffix((n, s))) => (rest, n * s.coefficient()),
+        _ => unreachable!("the number parsers return numbers only"),
+    };
+
+    if number < 0.0 {
+        return Err(nom::Err::Failure(BlockError {
+            input,
+            category: ErrorCategory::NegativeTolerance,
+        }));
+    }
+
+    Ok((rest, number))
+}
+
 /// Parser for a string in the format "float%" (e.g., "5%").
 /// Returns a block with `TolPlusMinus` where the value is both the positive and negative tolerance.
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// use your_crate::percentage_plus_parser2;
 /// assert_eq!(percentage_plus_parser2("5%"), Ok(("", Block::TolPlusMinus(5.0))));
 /// ```
-fn percentage_plus_minus_parser2(input: &str) -> IResult<&str, Block> {
-    let (input, number) = double(input)?;
-    let (input, _) = tag("%")(input)?;
+fn percentage_plus_minus_parser2(input: &str) -> BlockResult<'_> {
+    let (input, number) = tolerance_number(input)?;
 
     Ok((input, Block::TolPlusMinus(number)))
 }
@@ -88,14 +290,13 @@ fn percentage_plus_minus_parser2(input: &str) -> IResult<&str, Block> {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// use your_crate::percentage_plus_parser;
 /// assert_eq!(percentage_plus_parser("+5%"), Ok(("", Block::TolPlus(5.0))));
 /// ```
-fn percentage_plus_parser(input: &str) -> IResult<&str, Block> {
-    let (input, _) = tag("+")(input)?;
-    let (input, number) = double(input)?;
-    let (input, _) = tag("%")(input)?;
+fn percentage_plus_parser(input: &str) -> BlockResult<'_> {
+    let (input, _) = char('+')(input)?;
+    let (input, number) = tolerance_number(input)?;
 
     Ok((input, Block::TolPlus(number)))
 }
@@ -104,12 +305,14 @@ fn percentage_plus_parser(input: &str) -> IResult<&str, Block> {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// use your_crate::double_parser;
 /// assert_eq!(double_parser("5.67"), Ok(("", Block::Number(5.67))));
 /// ```
-fn double_parser(input: &str) -> IResult<&str, Block> {
+fn double_parser(input: &str) -> BlockResult<'_> {
     let (input, number) = double(input)?;
+    // "-5%" - допуск, а не отрицательное число
+    let (input, _) = not(char('%'))(input)?;
     Ok((input, Block::Number(number)))
 }
 
@@ -117,23 +320,15 @@ fn double_parser(input: &str) -> IResult<&str, Block> {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// use your_crate::double_suffix_parser;
 /// assert_eq!(double_suffix_parser("5k"), Ok(("", Block::NumberSuffix((5.0, Dim::Kilo)))));
 /// ```
-fn double_suffix_parser(input: &str) -> IResult<&str, Block> {
+fn double_suffix_parser(input: &str) -> BlockResult<'_> {
     let (input, number) = double(input)?;
 
-    let (input, suffix) = alt((
-        char('p'), // p -> Pico
-        char('n'), // n -> Nano
-        char('u'), // u -> Micro
-        char('m'), // m -> Milli
-        char('k'), // k -> Kilo
-        char('M'), // M -> Mega
-        char('G'), // G -> Giga
-        char('T'), // T -> Tera
-    ))(input)?;
+    // p -> Pico, n -> Nano, u -> Micro, m -> Milli, k -> Kilo, M -> Mega, G -> Giga, T -> Tera
+    let (input, suffix) = one_of("pnumkMGT")(input)?;
 
     let suffix: Dim = suffix.into();
     let result = Block::NumberSuffix((number, suffix));
@@ -141,38 +336,564 @@ fn double_suffix_parser(input: &str) -> IResult<&str, Block> {
     Ok((input, result))
 }
 
-/// Parser that tries multiple parsers in sequence
+/// Parser that tries multiple parsers in sequence.
+///
+/// The tolerance parsers go first and only match when `%` follows the number, so a signed
+/// number without `%` falls through to the nominal value parsers.
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// use your_crate::try_parsers;
 /// assert_eq!(try_parsers("5%"), Ok(("", Block::TolPlusMinus(5.0))));
 /// ```
-fn try_parsers(input: &str) -> IResult<&str, Block> {
+fn try_parsers(input: &str) -> BlockResult<'_> {
     alt((
         percentage_plus_parser,
         percentage_minus_parser,
         percentage_plus_minus_parser,
+        absolute_plus_minus_parser,
+        std_dev_parser,
         percentage_plus_minus_parser2,
         double_suffix_parser,
         double_parser,
     ))(input)
 }
 
-/// Parser that splits a string into blocks and applies parsers to each block
+/// Parser that splits a string into whitespace separated blocks and applies parsers to each
+/// block. Every block must be followed by whitespace or the end of the input. Any run of
+/// Unicode whitespace separates the blocks, including tabs and non-breaking spaces pasted
+/// from documents, and whitespace at both ends is skipped.
 ///
 /// # Example
 ///
 /// ```rust
-/// use your_crate::parse_blocks;
+/// use ecw::parser::{parse_blocks, Block};
+/// use ecw::types::Dim;
+///
 /// assert_eq!(
 ///     parse_blocks("5% 77m"),
-///     Ok(("", vec![Block::TolPlusMinus(5.0), Block::NumberSuffix((77.0, Dim::Milli))]))
+///     Ok(vec![Block::TolPlusMinus(5.0), Block::NumberSuffix((77.0, Dim::Milli))])
 /// );
 /// ```
-pub fn parse_blocks(input: &str) -> IResult<&str, Vec<Block>> {
-    separated_list1(space1, try_parsers)(input)
+pub fn parse_blocks(input: &str) -> Result<Vec<Block>, ParseError> {
+    let trimmed = input.trim_start();
+    if LIMITS
+        .iter()
+        .any(|(keyword, _)| trimmed.starts_with(keyword))
+    {
+        return parse_limits(input);
+    }
+
+    parse_sequence(input, try_parsers)
+}
+
+/// Applies `parser` to every whitespace separated item of `input`.
+fn parse_sequence<'a, T>(
+    input: &'a str,
+    parser: impl Fn(&'a str) -> IResult<&'a str, T, BlockError<'a>>,
+) -> Result<Vec<T>, ParseError> {
+    let offset = |rest: &str| input.len() - rest.len();
+
+    let mut items = Vec::new();
+    let mut rest = input.trim_start();
+    loop {
+        let (remaining, item) = parser(rest).map_err(|e| match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError {
+                offset: offset(e.input),
+                category: e.category,
+            },
+            nom::Err::Incomplete(_) => ParseError {
+                offset: input.len(),
+                category: ErrorCategory::UnexpectedEnd,
+            },
+        })?;
+        items.push(item);
+
+        if remaining.is_empty() {
+            return Ok(items);
+        }
+        if !remaining.starts_with(char::is_whitespace) {
+            let error = BlockError::unexpected(remaining);
+            return Err(ParseError {
+                offset: offset(error.input),
+                category: error.category,
+            });
+        }
+
+        rest = remaining.trim_start();
+        if rest.is_empty() {
+            return Ok(items);
+        }
+    }
+}
+
+/// Datasheet limits of a value: `typ`, `min` and `max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Limit {
+    Typ,
+    Min,
+    Max,
+}
+
+const LIMITS: [(&str, Limit); 3] = [
+    ("typ", Limit::Typ),
+    ("min", Limit::Min),
+    ("max", Limit::Max),
+];
+
+/// Parser for a limit in the format "keyword value" (e.g., "min 11.4", "typ 10k").
+/// The value is returned in base units.
+///
+/// # Example
+///
+/// ```ignore
+/// use your_crate::limit_parser;
+/// assert_eq!(limit_parser("max 1k"), Ok(("", (Limit::Max, 1000.0))));
+/// ```
+fn limit_parser(input: &str) -> IResult<&str, (Limit, f64), BlockError<'_>> {
+    let Some(&(keyword, limit)) = LIMITS
+        .iter()
+        .find(|(keyword, _)| input.starts_with(keyword))
+    else {
+        return Err(nom::Err::Error(BlockError {
+            input,
+            category: ErrorCategory::ExpectedLimit,
+        }));
+    };
+
+    let (input, _) = take_while1(char::is_whitespace)(&input[keyword.len()..])?;
+    let (input, value) = match alt((double_suffix_parser, double_parser))(input)? {
+        (input, Block::Number(n)) => (input, n),
+        (input, Block::NumberSuffix((n, s))) => (input, n * s.coefficient()),
+        _ => unreachable!("the number parsers return numbers only"),
+    };
+
+    Ok((input, (limit, value)))
+}
+
+/// Parses a value given by its datasheet limits, e.g. `"typ 12 min 11.4 max 12.6"`, into
+/// blocks of the usual format: the typical value is the nominal, `min` and `max` become the
+/// minus and plus tolerances relative to it (`12 +5% -5%`). The limits go in any order,
+/// `typ` is required.
+fn parse_limits(input: &str) -> Result<Vec<Block>, ParseError> {
+    let limits = parse_sequence(input, limit_parser)?;
+    let value = |limit| {
+        limits
+            .iter()
+            .rev()
+            .find_map(|&(l, value)| (l == limit).then_some(value))
+    };
+
+    let Some(typ) = value(Limit::Typ) else {
+        return Err(ParseError {
+            offset: input.len(),
+            category: ErrorCategory::MissingTyp,
+        });
+    };
+
+    // допуски считаются в процентах от `typ`
+    if typ == 0.0 && (value(Limit::Max).is_some() || value(Limit::Min).is_some()) {
+        return Err(ParseError {
+            offset: input.len(),
+            category: ErrorCategory::ZeroTyp,
+        });
+    }
+
+    let mut blocks = vec![Block::Number(typ)];
+    if let Some(max) = value(Limit::Max) {
+        blocks.push(Block::TolPlus((max - typ) / typ.abs() * 100.0));
+    }
+    if let Some(min) = value(Limit::Min) {
+        blocks.push(Block::TolMinus((typ - min) / typ.abs() * 100.0));
+    }
+
+    // `max` ниже `typ` или `min` выше
+    let negative = blocks.iter().any(|block| match block {
+        Block::TolPlus(t) | Block::TolMinus(t) => *t < 0.0,
+        _ => false,
+    });
+    if negative {
+        return Err(ParseError {
+            offset: input.len(),
+            category: ErrorCategory::NegativeTolerance,
+        });
+    }
+
+    Ok(blocks)
+}
+
+/// Replaces the Unicode signs that come with text pasted from documents by their ASCII forms:
+/// `−` (U+2212 minus) by `-` and `±` by `+/-`. Error offsets of the parsers refer to the
+/// normalized text.
+///
+/// # Example
+///
+/// ```rust
+/// # use ecw::parser::normalize_signs;
+/// assert_eq!(normalize_signs("12 ±5%"), "12 +/-5%");
+/// ```
+pub fn normalize_signs(input: &str) -> Cow<'_, str> {
+    if input.contains(['\u{2212}', '\u{b1}']) {
+        Cow::Owned(input.replace('\u{2212}', "-").replace('\u{b1}', "+/-"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Default of [`max_input_len`].
+pub const MAX_INPUT_LEN: usize = 256;
+
+thread_local! {
+    static INPUT_LIMIT: Cell<usize> = const { Cell::new(MAX_INPUT_LEN) };
+}
+
+/// Sets the longest input in characters for the current thread, at least one.
+pub fn set_max_input_len(chars: usize) {
+    INPUT_LIMIT.with(|l| l.set(chars.max(1)));
+}
+
+/// Returns the longest input in characters of the current thread.
+pub fn max_input_len() -> usize {
+    INPUT_LIMIT.with(|l| l.get())
+}
+
+/// Rejects input over [`max_input_len`] characters; the offset is that of the first
+/// character past the limit.
+///
+/// # Example
+///
+/// ```rust
+/// # use ecw::parser::{check_length, MAX_INPUT_LEN};
+/// assert!(check_length("4.7k 5%").is_ok());
+/// assert!(check_length(&"1".repeat(MAX_INPUT_LEN + 1)).is_err());
+/// ```
+pub fn check_length(input: &str) -> Result<(), ParseError> {
+    let limit = max_input_len();
+
+    match input.char_indices().nth(limit) {
+        Some((offset, _)) => Err(ParseError {
+            offset,
+            category: ErrorCategory::TooLong(limit),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Cuts `input` to [`max_input_len`] characters, `true` if it was longer.
+pub fn truncate_input(input: &mut String) -> bool {
+    match input.char_indices().nth(max_input_len()) {
+        Some((end, _)) => {
+            input.truncate(end);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Digit group separators: the underscore, the comma and the spaces, thin and no-break ones
+/// included.
+const GROUP_SEPARATORS: [char; 6] = ['_', ',', ' ', '\u{a0}', '\u{2009}', '\u{202f}'];
+
+/// Removes the digit group separators from the numbers of `input`, so `"1 000 000"`,
+/// `"1,000,000"` and `"1_000_000"` all reach the parsers as `"1000000"`.
+///
+/// The period is the decimal separator in every interface language, so a comma between
+/// digits can only group them. An underscore may stand between any digits, a comma or a
+/// space only before a group of three digits that follows a group of at most three (the
+/// first one) or exactly three. A space before a group followed by `%` is not a separator:
+/// `"10 100%"` is a value with its tolerance. A space before a group followed by a prefix or
+/// a unit is an error, `"12 100m"` may as well be a typo in `"12.100m"`. The fractional part
+/// is never grouped. All the separators of one number must be the same; a number with
+/// different ones is an error, the offset refers to the separator that differs.
+///
+/// # Example
+///
+/// ```rust
+/// # use ecw::parser::strip_grouping;
+/// assert_eq!(strip_grouping("1,000.5k 5%").unwrap(), "1000.5k 5%");
+/// assert!(strip_grouping("1,000_000").is_err());
+/// ```
+pub fn strip_grouping(input: &str) -> Result<Cow<'_, str>, ParseError> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let digits_from = |i: usize| {
+        chars[i.min(chars.len())..]
+            .iter()
+            .take_while(|(_, c)| c.is_ascii_digit())
+            .count()
+    };
+
+    // байтовые смещения удаляемых разделителей
+    let mut separators = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let previous = i.checked_sub(1).map(|p| chars[p].1);
+        if !chars[i].1.is_ascii_digit() || previous.is_some_and(|c| c.is_ascii_digit()) {
+            i += 1;
+            continue;
+        }
+
+        let fraction = previous == Some('.');
+        let mut group = digits_from(i);
+        let mut first = true;
+        let mut separator = None;
+        let mut end = i + group;
+        while let Some(&(offset, c)) = chars.get(end) {
+            let next = digits_from(end + 1);
+            if fraction || next == 0 || !GROUP_SEPARATORS.contains(&c) {
+                break;
+            }
+
+            let before_percent = chars.get(end + 1 + next).is_some_and(|(_, c)| *c == '%');
+            let groups = c == '_'
+                || (next == 3
+                    && if first { group <= 3 } else { group == 3 }
+                    && !(c != ',' && before_percent));
+            if !groups {
+                break;
+            }
+            let suffix = chars
+                .get(end + 1 + next)
+                .is_some_and(|(_, c)| c.is_alphabetic());
+            if !matches!(c, '_' | ',') && suffix {
+                return Err(ParseError {
+                    offset,
+                    category: ErrorCategory::AmbiguousGrouping,
+                });
+            }
+            if separator.is_some_and(|s| s != c) {
+                return Err(ParseError {
+                    offset,
+                    category: ErrorCategory::MixedGrouping,
+                });
+            }
+
+            separator = Some(c);
+            separators.push(offset);
+            group = next;
+            first = false;
+            end += 1 + next;
+        }
+
+        i = end;
+    }
+
+    if separators.is_empty() {
+        return Ok(Cow::Borrowed(input));
+    }
+
+    Ok(Cow::Owned(
+        input
+            .char_indices()
+            .filter(|(offset, _)| !separators.contains(offset))
+            .map(|(_, c)| c)
+            .collect(),
+    ))
+}
+
+/// Unit letters a value may end with. `R` and `Ω` both stand for ohms.
+pub const UNITS: [char; 5] = ['V', 'A', 'R', 'Ω', 'W'];
+
+/// Removes the unit letters that follow a number or its prefix, so `"12V"` and `"4.7kΩ 5%"`
+/// reach the parsers as `"12"` and `"4.7k 5%"`. The first removed letter is returned for the
+/// caller to compare with the unit of the field: the parsers accept a number with any unit.
+///
+/// # Example
+///
+/// ```rust
+/// # use ecw::parser::strip_units;
+/// assert_eq!(strip_units("10kR || 10k"), ("10k || 10k".into(), Some('R')));
+/// assert_eq!(strip_units("12 5%"), ("12 5%".into(), None));
+/// ```
+pub fn strip_units(input: &str) -> (Cow<'_, str>, Option<char>) {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+
+    // буква единицы стоит после цифры или приставки и заканчивает слово
+    let units: Vec<(usize, char)> = chars
+        .iter()
+        .enumerate()
+        .filter(|(i, (_, c))| {
+            let previous = i.checked_sub(1).map(|p| chars[p].1);
+            let next = chars.get(i + 1).map(|(_, c)| *c);
+
+            UNITS.contains(c)
+                && previous.is_some_and(|p| p.is_ascii_digit() || "pnumkMGT".contains(p))
+                && next.is_none_or(|n| n.is_whitespace() || "|+)".contains(n))
+        })
+        .map(|(_, unit)| *unit)
+        .collect();
+
+    match units.first() {
+        None => (Cow::Borrowed(input), None),
+        Some(&(_, unit)) => (
+            Cow::Owned(
+                input
+                    .char_indices()
+                    .filter(|item| !units.contains(item))
+                    .map(|(_, c)| c)
+                    .collect(),
+            ),
+            Some(unit),
+        ),
+    }
+}
+
+/// Resistor network expression: `+` is a series connection, `||` a parallel one and binds
+/// tighter, parentheses group, e.g. `"(10k || 10k) + 1k"`. Every operand is a value with its
+/// tolerance blocks (`"10k 1% || 15k"`). `-` subtracts on the level of `+`, e.g. the lead
+/// resistance from a two-terminal measurement, `"0.5 - 0.02"`.
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+    Value(Vec<Block>),
+    Series(Box<Expr>, Box<Expr>),
+    Difference(Box<Expr>, Box<Expr>),
+    Parallel(Box<Expr>, Box<Expr>),
+}
+
+/// Parses a resistor network expression. The series `+` and the `-` must be followed by
+/// whitespace or `(`, otherwise they are the sign of a tolerance (`+5%`, `-3%`) or of a
+/// value (`+12`). Error offsets are counted from the start of the whole input.
+pub fn parse_expr(input: &str) -> Result<Expr, ParseError> {
+    let mut parser = ExprParser { input, pos: 0 };
+
+    let expr = parser.series()?;
+    parser.skip_whitespace();
+    if parser.pos < input.len() {
+        // лишняя закрывающая скобка
+        return Err(parser.unexpected());
+    }
+
+    Ok(expr)
+}
+
+/// Recursive descent over the expression grammar:
+///
+/// ```text
+/// series   := parallel (("+" | "-") parallel)*
+/// parallel := operand ("||" operand)*
+/// operand  := "(" series ")" | blocks
+/// ```
+struct ExprParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> ExprParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn unexpected(&self) -> ParseError {
+        let error = BlockError::unexpected(self.rest());
+
+        ParseError {
+            offset: self.pos,
+            category: error.category,
+        }
+    }
+
+    fn series(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parallel()?;
+
+        loop {
+            self.skip_whitespace();
+            let subtract = match self.rest().chars().next() {
+                _ if !is_series_operator(self.rest()) => return Ok(expr),
+                Some('-') => true,
+                _ => false,
+            };
+            self.pos += 1;
+
+            let rhs = self.parallel()?;
+            expr = if subtract {
+                Expr::Difference(Box::new(expr), Box::new(rhs))
+            } else {
+                Expr::Series(Box::new(expr), Box::new(rhs))
+            };
+        }
+    }
+
+    fn parallel(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.operand()?;
+
+        loop {
+            self.skip_whitespace();
+            if !self.rest().starts_with("||") {
+                return Ok(expr);
+            }
+            self.pos += 2;
+
+            let rhs = self.operand()?;
+            expr = Expr::Parallel(Box::new(expr), Box::new(rhs));
+        }
+    }
+
+    fn operand(&mut self) -> Result<Expr, ParseError> {
+        self.skip_whitespace();
+        if !self.rest().starts_with('(') {
+            return self.value();
+        }
+        self.pos += 1;
+
+        let expr = self.series()?;
+        self.skip_whitespace();
+        if !self.rest().starts_with(')') {
+            return Err(ParseError {
+                offset: self.pos,
+                category: ErrorCategory::Expected(')'),
+            });
+        }
+        self.pos += 1;
+
+        Ok(expr)
+    }
+
+    fn value(&mut self) -> Result<Expr, ParseError> {
+        let rest = self.rest();
+
+        let mut end = rest.len();
+        let mut after_whitespace = true;
+        for (i, c) in rest.char_indices() {
+            let operator = rest[i..].starts_with("||")
+                || c == '('
+                || c == ')'
+                || (after_whitespace && is_series_operator(&rest[i..]));
+            if operator {
+                end = i;
+                break;
+            }
+            after_whitespace = c.is_whitespace();
+        }
+
+        let segment = rest[..end].trim_end();
+        if segment.is_empty() {
+            return Err(ParseError {
+                offset: self.pos,
+                category: ErrorCategory::ExpectedNumber,
+            });
+        }
+
+        let blocks = parse_blocks(segment).map_err(|e| ParseError {
+            offset: self.pos + e.offset,
+            ..e
+        })?;
+        self.pos += end;
+
+        Ok(Expr::Value(blocks))
+    }
+}
+
+/// `+` or `-` followed by whitespace, `(` or the end of the input.
+fn is_series_operator(input: &str) -> bool {
+    let mut chars = input.chars();
+
+    matches!(chars.next(), Some('+' | '-'))
+        && chars.next().is_none_or(|c| c.is_whitespace() || c == '(')
 }
 
 #[cfg(test)]
@@ -195,6 +916,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_absolute_plus_minus_parser() {
+        assert_eq!(
+            absolute_plus_minus_parser("+/-0.5"),
+            Ok(("", Block::AbsTolPlusMinus(0.5)))
+        );
+        assert_eq!(
+            absolute_plus_minus_parser("+/-10m"),
+            Ok(("", Block::AbsTolPlusMinus(0.01)))
+        );
+        assert_eq!(
+            parse_blocks("12 +/-0.5"),
+            Ok(vec![Block::Number(12.0), Block::AbsTolPlusMinus(0.5)])
+        );
+        assert_eq!(
+            parse_blocks("12 +/-5%"),
+            Ok(vec![Block::Number(12.0), Block::TolPlusMinus(5.0)])
+        );
+        assert_eq!(
+            parse_blocks("12 +/--0.5").unwrap_err().category,
+            ErrorCategory::NegativeTolerance
+        );
+    }
+
+    #[test]
+    fn test_std_dev_parser() {
+        assert_eq!(std_dev_parser("s=0.05"), Ok(("", Block::StdDev(0.05))));
+        assert_eq!(std_dev_parser("s=5m"), Ok(("", Block::StdDev(0.005))));
+        assert_eq!(
+            parse_blocks("12.00 s=0.05"),
+            Ok(vec![Block::Number(12.0), Block::StdDev(0.05)])
+        );
+        assert_eq!(
+            parse_blocks("12 s=-1").unwrap_err().category,
+            ErrorCategory::NegativeTolerance
+        );
+        assert!(parse_blocks("12 s=").is_err());
+    }
+
     #[test]
     fn test_percentage_plus_parser() {
         assert_eq!(percentage_plus_parser("+5%"), Ok(("", Block::TolPlus(5.0))));
@@ -232,13 +992,10 @@ mod tests {
         let result = parse_blocks(input);
         assert_eq!(
             result,
-            Ok((
-                "",
-                vec![
-                    Block::TolPlusMinus(5.0),
-                    Block::NumberSuffix((77.0, Dim::Milli))
-                ]
-            ))
+            Ok(vec![
+                Block::TolPlusMinus(5.0),
+                Block::NumberSuffix((77.0, Dim::Milli))
+            ])
         );
     }
 
@@ -248,15 +1005,331 @@ mod tests {
         let result = parse_blocks(input);
         assert_eq!(
             result,
-            Ok((
-                "",
-                vec![
-                    Block::NumberSuffix((10.0, Dim::Milli)),
-                    Block::TolPlus(5.0),
-                    Block::TolMinus(5.0),
-                    Block::TolPlusMinus(5.0),
-                ]
+            Ok(vec![
+                Block::NumberSuffix((10.0, Dim::Milli)),
+                Block::TolPlus(5.0),
+                Block::TolMinus(5.0),
+                Block::TolPlusMinus(5.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_signed_nominal() {
+        assert_eq!(parse_blocks("-5"), Ok(vec![Block::Number(-5.0)]));
+        assert_eq!(parse_blocks("+12"), Ok(vec![Block::Number(12.0)]));
+        assert_eq!(parse_blocks("-5%"), Ok(vec![Block::TolMinus(5.0)]));
+        assert_eq!(
+            parse_blocks("-5 -10%"),
+            Ok(vec![Block::Number(-5.0), Block::TolMinus(10.0)])
+        );
+        assert_eq!(
+            parse_blocks("-5 +3% -2%"),
+            Ok(vec![
+                Block::Number(-5.0),
+                Block::TolPlus(3.0),
+                Block::TolMinus(2.0)
+            ])
+        );
+        assert_eq!(
+            parse_blocks("-4.7k"),
+            Ok(vec![Block::NumberSuffix((-4.7, Dim::Kilo))])
+        );
+        assert!(double_parser("-5%").is_err());
+    }
+
+    fn value(value: f64, dim: Dim) -> Box<Expr> {
+        Box::new(Expr::Value(vec![Block::NumberSuffix((value, dim))]))
+    }
+
+    #[test]
+    fn test_strip_units() {
+        assert_eq!(strip_units("12V"), ("12".into(), Some('V')));
+        assert_eq!(strip_units("2mA 5%"), ("2m 5%".into(), Some('A')));
+        assert_eq!(
+            strip_units("(1kΩ || 1kΩ) + 10R"),
+            ("(1k || 1k) + 10".into(), Some('Ω'))
+        );
+        assert_eq!(
+            strip_units("typ 10W max 12W"),
+            ("typ 10 max 12".into(), Some('W'))
+        );
+
+        // буква без числа перед ней - не единица
+        assert_eq!(strip_units("V"), ("V".into(), None));
+        assert_eq!(strip_units("12Vx"), ("12Vx".into(), None));
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(
+            parse_expr("10k 1% || 15k"),
+            Ok(Expr::Parallel(
+                Box::new(Expr::Value(vec![
+                    Block::NumberSuffix((10.0, Dim::Kilo)),
+                    Block::TolPlusMinus(1.0)
+                ])),
+                value(15.0, Dim::Kilo),
             ))
         );
+        assert_eq!(
+            parse_expr("5 +5%"),
+            Ok(Expr::Value(vec![Block::Number(5.0), Block::TolPlus(5.0)]))
+        );
+    }
+
+    #[test]
+    fn test_parse_expr_precedence() {
+        // || связывает сильнее, чем +
+        assert_eq!(
+            parse_expr("1k + 10k || 10k"),
+            Ok(Expr::Series(
+                value(1.0, Dim::Kilo),
+                Box::new(Expr::Parallel(
+                    value(10.0, Dim::Kilo),
+                    value(10.0, Dim::Kilo)
+                )),
+            ))
+        );
+        assert_eq!(
+            parse_expr("(1k + 10k) || 10k"),
+            Ok(Expr::Parallel(
+                Box::new(Expr::Series(value(1.0, Dim::Kilo), value(10.0, Dim::Kilo))),
+                value(10.0, Dim::Kilo),
+            ))
+        );
+        // минус допуска без пробела после него не вычитание
+        assert_eq!(
+            parse_expr("500m -5% - 20m"),
+            Ok(Expr::Difference(
+                Box::new(Expr::Value(vec![
+                    Block::NumberSuffix((500.0, Dim::Milli)),
+                    Block::TolMinus(5.0)
+                ])),
+                value(20.0, Dim::Milli),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_expr_errors() {
+        assert_eq!(
+            parse_expr("10k || 5x"),
+            Err(ParseError {
+                offset: 8,
+                category: ErrorCategory::UnexpectedCharacter('x'),
+            })
+        );
+        assert_eq!(
+            parse_expr("10k ||"),
+            Err(ParseError {
+                offset: 6,
+                category: ErrorCategory::ExpectedNumber,
+            })
+        );
+        assert_eq!(
+            parse_expr("(10k + 1k"),
+            Err(ParseError {
+                offset: 9,
+                category: ErrorCategory::Expected(')'),
+            })
+        );
+        assert_eq!(
+            parse_expr("10k)"),
+            Err(ParseError {
+                offset: 3,
+                category: ErrorCategory::UnexpectedCharacter(')'),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_blocks_whitespace() {
+        let expected = Ok(vec![
+            Block::NumberSuffix((10.0, Dim::Kilo)),
+            Block::TolPlus(5.0),
+            Block::TolMinus(2.0),
+        ]);
+
+        assert_eq!(parse_blocks(" 10k  +5%   -2% "), expected);
+        assert_eq!(parse_blocks("10k\t+5%\t\t-2%"), expected);
+        assert_eq!(parse_blocks("10k\u{a0}+5%\u{a0}\u{a0}-2%\u{a0}"), expected);
+        assert_eq!(parse_blocks("\u{a0}10k \t\u{a0}+5%\u{202f}-2%\n"), expected);
+    }
+
+    #[test]
+    fn test_normalize_signs() {
+        assert_eq!(normalize_signs("12 ±5%"), "12 +/-5%");
+        assert_eq!(normalize_signs("−5%"), "-5%");
+        assert_eq!(normalize_signs("+/−5%"), "+/-5%");
+        assert!(matches!(normalize_signs("12 5%"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_input_length() {
+        let long = "1".repeat(MAX_INPUT_LEN);
+        assert!(check_length(&long).is_ok());
+        // смещение считается в байтах, предел — в символах
+        let error = check_length(&"Ω".repeat(MAX_INPUT_LEN + 1)).unwrap_err();
+        assert_eq!(error.offset, 2 * MAX_INPUT_LEN);
+        assert_eq!(error.category, ErrorCategory::TooLong(MAX_INPUT_LEN));
+
+        let mut pasted = "±".repeat(MAX_INPUT_LEN * 4);
+        assert!(truncate_input(&mut pasted));
+        assert_eq!(pasted.chars().count(), MAX_INPUT_LEN);
+        assert!(!truncate_input(&mut pasted));
+
+        set_max_input_len(4);
+        assert!(check_length("12 5%").is_err());
+        set_max_input_len(0);
+        assert_eq!(max_input_len(), 1);
+        set_max_input_len(MAX_INPUT_LEN);
+    }
+
+    #[test]
+    fn test_strip_grouping() {
+        assert_eq!(strip_grouping("1_000_000").unwrap(), "1000000");
+        assert_eq!(strip_grouping("1 000 000").unwrap(), "1000000");
+        assert_eq!(
+            strip_grouping("1\u{2009}000\u{2009}000").unwrap(),
+            "1000000"
+        );
+        assert_eq!(strip_grouping("1,000,000").unwrap(), "1000000");
+        assert_eq!(strip_grouping("12,345.678_9").unwrap(), "12345.678_9");
+        assert_eq!(strip_grouping("1_0000 +2_5%").unwrap(), "10000 +25%");
+        assert_eq!(strip_grouping("10 100").unwrap(), "10100");
+        assert_eq!(strip_grouping("4,700k 5%").unwrap(), "4700k 5%");
+        assert_eq!(strip_grouping("1 000.5 5%").unwrap(), "1000.5 5%");
+
+        // не группы: значение с допуском, неполная группа, запятая не десятичная
+        assert!(matches!(strip_grouping("10 100%"), Ok(Cow::Borrowed(_))));
+        assert!(matches!(strip_grouping("10 5%"), Ok(Cow::Borrowed(_))));
+        assert!(matches!(strip_grouping("1,5"), Ok(Cow::Borrowed(_))));
+        assert!(matches!(strip_grouping("1000,000"), Ok(Cow::Borrowed(_))));
+        assert_eq!(strip_grouping("1,000 +1,000%").unwrap(), "1000 +1000%");
+
+        assert_eq!(
+            strip_grouping("1,000_000"),
+            Err(ParseError {
+                offset: 5,
+                category: ErrorCategory::MixedGrouping,
+            })
+        );
+        assert_eq!(
+            strip_grouping("5 1,000 000").unwrap_err().category,
+            ErrorCategory::MixedGrouping
+        );
+
+        // приставка или единица после группы через пробел
+        assert_eq!(
+            strip_grouping("12 100m"),
+            Err(ParseError {
+                offset: 2,
+                category: ErrorCategory::AmbiguousGrouping,
+            })
+        );
+        for input in ["4 700k 5%", "1 000 000V", "1\u{202f}000R"] {
+            assert_eq!(
+                strip_grouping(input).unwrap_err().category,
+                ErrorCategory::AmbiguousGrouping
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_blocks_error_offset() {
+        assert_eq!(
+            parse_blocks("12 5x"),
+            Err(ParseError {
+                offset: 4,
+                category: ErrorCategory::UnexpectedCharacter('x'),
+            })
+        );
+        // "+/-5" без процентов - абсолютный допуск
+        assert_eq!(
+            parse_blocks("12 +/-5x"),
+            Err(ParseError {
+                offset: 7,
+                category: ErrorCategory::UnexpectedCharacter('x'),
+            })
+        );
+        assert_eq!(
+            parse_blocks("x5"),
+            Err(ParseError {
+                offset: 0,
+                category: ErrorCategory::ExpectedNumber,
+            })
+        );
+        assert_eq!(
+            parse_blocks("12 5x").unwrap_err().to_string(),
+            "unexpected character 'x' at position 4"
+        );
+    }
+
+    #[test]
+    fn test_parse_blocks_negative_tolerance() {
+        for (input, offset) in [
+            ("10 +-5%", 4),
+            ("10 --5%", 4),
+            ("10 +/--5%", 6),
+            ("10 5% +-1%", 7),
+        ] {
+            assert_eq!(
+                parse_blocks(input),
+                Err(ParseError {
+                    offset,
+                    category: ErrorCategory::NegativeTolerance,
+                }),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            parse_blocks("typ 12 min 13").unwrap_err().category,
+            ErrorCategory::NegativeTolerance
+        );
+        // знак номинала допустим
+        assert_eq!(
+            parse_blocks("-10 -5%"),
+            Ok(vec![Block::Number(-10.0), Block::TolMinus(5.0)])
+        );
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(
+            parse_blocks("typ 10m max 12m"),
+            Ok(vec![Block::Number(0.01), Block::TolPlus(20.0)])
+        );
+        assert_eq!(limit_parser("min 1k"), Ok(("", (Limit::Min, 1000.0))));
+        assert_eq!(
+            parse_blocks("typ 12 5%"),
+            Err(ParseError {
+                offset: 7,
+                category: ErrorCategory::ExpectedLimit,
+            })
+        );
+        assert_eq!(
+            parse_blocks("typ12"),
+            Err(ParseError {
+                offset: 3,
+                category: ErrorCategory::UnexpectedCharacter('1'),
+            })
+        );
+        assert_eq!(
+            parse_blocks("min 1 max 2"),
+            Err(ParseError {
+                offset: 11,
+                category: ErrorCategory::MissingTyp,
+            })
+        );
+        assert_eq!(
+            parse_blocks("typ 0 max 1"),
+            Err(ParseError {
+                offset: 11,
+                category: ErrorCategory::ZeroTyp,
+            })
+        );
+        assert_eq!(parse_blocks("typ 0"), Ok(vec![Block::Number(0.0)]));
     }
 }
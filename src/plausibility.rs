@@ -0,0 +1,232 @@
+//! # Plausibility Limits
+//!
+//! Optional limits that flag fat-finger entries, e.g. `10M` amps instead of `10m`. The check
+//! is advisory: a value outside its limit is still used in the calculation, the scene only
+//! shows a warning under the field. All limits are off by default.
+//!
+//! Like the interface language, the limits are kept per thread.
+
+use std::cell::Cell;
+
+use serde::Serialize;
+
+use crate::i18n::{t, Key};
+use crate::parser;
+use crate::types::Measurement;
+
+thread_local! {
+    static LIMITS: Cell<Limits> = const { Cell::new(Limits::OFF) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Limits {
+    pub max_voltage: Option<f64>,
+    pub max_current: Option<f64>,
+    pub max_power: Option<f64>,
+    pub min_resistance: Option<f64>,
+    /// Current density in a conductor, A/mm².
+    pub max_current_density: Option<f64>,
+}
+
+impl Limits {
+    pub const OFF: Limits = Limits {
+        max_voltage: None,
+        max_current: None,
+        max_power: None,
+        min_resistance: None,
+        max_current_density: None,
+    };
+}
+
+/// Sets the plausibility limits for the current thread.
+pub fn set_limits(limits: Limits) {
+    LIMITS.with(|l| l.set(limits));
+}
+
+/// Returns the plausibility limits of the current thread.
+pub fn limits() -> Limits {
+    LIMITS.with(|l| l.get())
+}
+
+/// Checks the nominal value of `value` against the limit for its quantity and returns a
+/// warning if it is outside. Voltage, current and power are compared by magnitude, as the
+/// sign only gives the direction.
+pub fn check(value: &impl Measurement, limits: &Limits) -> Option<String> {
+    let nominal = value.get_nominal_value();
+
+    let (outside, limit, key) = match value.get_unit() {
+        "V" => (
+            nominal.abs() > limits.max_voltage?,
+            limits.max_voltage?,
+            Key::AboveLimit,
+        ),
+        "A" => (
+            nominal.abs() > limits.max_current?,
+            limits.max_current?,
+            Key::AboveLimit,
+        ),
+        "W" => (
+            nominal.abs() > limits.max_power?,
+            limits.max_power?,
+            Key::AboveLimit,
+        ),
+        "R" => (
+            nominal < limits.min_resistance?,
+            limits.min_resistance?,
+            Key::BelowLimit,
+        ),
+        _ => return None,
+    };
+
+    outside.then(|| {
+        format!(
+            "{} {} {}",
+            value.get_value_nom(),
+            t(key),
+            value.normalize(limit)
+        )
+    })
+}
+
+/// Warns about a minus tolerance above 100%: the minimum of the value has the opposite sign,
+/// which is impossible for a resistance or a power. Unlike the limits, always on.
+pub fn check_tolerance(value: &impl Measurement) -> Option<String> {
+    let tol = value.get_tolerance()?;
+
+    (tol.minus > 100.0).then(|| format!("-{:.2}%: {}", tol.minus, t(Key::MinusToleranceAbove100)))
+}
+
+/// Warns about a unit letter in `raw` that is not the unit of the parsed `value`, e.g. `12A`
+/// in a voltage field: the number is still used, but the value was likely meant for another
+/// field. Unlike the limits, always on.
+pub fn check_unit(raw: &str, value: &impl Measurement) -> Option<String> {
+    let unit = parser::strip_units(raw).1?;
+    let expected = value.get_unit();
+    let quantity = match expected {
+        "V" => Key::Voltage,
+        "A" => Key::Current,
+        "R" => Key::Resistance,
+        "W" => Key::Power,
+        _ => return None,
+    };
+
+    let typed = match unit {
+        'Ω' => 'R',
+        unit => unit,
+    };
+    (!expected.starts_with(typed)).then(|| {
+        format!(
+            "{} '{}' {} {}",
+            t(Key::Unit),
+            unit,
+            t(Key::UnitMismatch),
+            t(quantity)
+        )
+    })
+}
+
+/// Checks a current density in A/mm² against its limit and returns a warning if it is above.
+pub fn check_current_density(density: f64, limits: &Limits) -> Option<String> {
+    let limit = limits.max_current_density?;
+
+    (density > limit).then(|| {
+        format!(
+            "{:.2} A/mm² {} {:.2} A/mm²",
+            density,
+            t(Key::AboveLimit),
+            limit
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
+
+    const LIMITS: Limits = Limits {
+        max_voltage: Some(1000.0),
+        max_current: Some(10.0),
+        max_power: Some(100.0),
+        min_resistance: Some(1e-3),
+        max_current_density: Some(10.0),
+    };
+
+    #[test]
+    fn test_check_off() {
+        let current = "10M".parse::<Current>().unwrap();
+
+        assert_eq!(check(&current, &Limits::OFF), None);
+        assert_eq!(check(&current, &limits()), None);
+    }
+
+    #[test]
+    fn test_check_voltage() {
+        assert_eq!(check(&"230".parse::<Voltage>().unwrap(), &LIMITS), None);
+        assert_eq!(check(&"-230".parse::<Voltage>().unwrap(), &LIMITS), None);
+        assert!(check(&"-2k".parse::<Voltage>().unwrap(), &LIMITS).is_some());
+    }
+
+    #[test]
+    fn test_check_current() {
+        assert_eq!(check(&"10m".parse::<Current>().unwrap(), &LIMITS), None);
+        assert_eq!(
+            check(&"10M".parse::<Current>().unwrap(), &LIMITS),
+            Some("10.00MA above the plausibility limit 10.00A".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_power() {
+        assert_eq!(check(&"100".parse::<Power>().unwrap(), &LIMITS), None);
+        assert!(check(&"1k".parse::<Power>().unwrap(), &LIMITS).is_some());
+    }
+
+    #[test]
+    fn test_check_resistance() {
+        assert_eq!(check(&"1m".parse::<Resistance>().unwrap(), &LIMITS), None);
+        assert_eq!(
+            check(&"10u".parse::<Resistance>().unwrap(), &LIMITS),
+            Some("10.00uR below the plausibility limit 1.00mR".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_tolerance() {
+        assert_eq!(check_tolerance(&"10".parse::<Resistance>().unwrap()), None);
+        assert_eq!(
+            check_tolerance(&"10 100%".parse::<Resistance>().unwrap()),
+            None
+        );
+        assert_eq!(
+            check_tolerance(&"10 +5% -150%".parse::<Resistance>().unwrap()),
+            Some("-150.00%: minus tolerance above 100%, the minimum changes sign".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_unit() {
+        let voltage = "12A".parse::<Voltage>().unwrap();
+        assert_eq!(voltage.value, 12.0);
+        assert_eq!(
+            check_unit("12A", &voltage),
+            Some("unit 'A' doesn't match Voltage".to_string())
+        );
+
+        assert_eq!(check_unit("12V", &voltage), None);
+        assert_eq!(check_unit("12", &voltage), None);
+        let resistance = "4.7kΩ".parse::<Resistance>().unwrap();
+        assert_eq!(check_unit("4.7kΩ", &resistance), None);
+        assert!(check_unit("4.7kW", &resistance).is_some());
+    }
+
+    #[test]
+    fn test_check_current_density() {
+        assert_eq!(check_current_density(10.0, &LIMITS), None);
+        assert_eq!(
+            check_current_density(12.5, &LIMITS),
+            Some("12.50 A/mm² above the plausibility limit 10.00 A/mm²".to_string())
+        );
+        assert_eq!(check_current_density(1e6, &Limits::OFF), None);
+    }
+}
@@ -0,0 +1,523 @@
+//! # Presets
+//!
+//! Named values the user keeps at hand, e.g. "USB" as `5 5%` or "LiFePO4 cell" as `3.2`.
+//! A preset carries the kind of its quantity and is offered only next to the fields of that
+//! kind, where picking it replaces the entered text with the preset value.
+//!
+//! Like the settings, the library is kept per thread. It moves between machines as JSON:
+//!
+//! ```json
+//! {
+//!   "version": "0.1.0",
+//!   "presets": [{ "name": "USB", "kind": "voltage", "value": "5 5%" }]
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::fmt;
+use std::str::FromStr;
+
+use iced::widget::{button, checkbox, pick_list, Button, Column, Row, Text, TextInput};
+use iced::{Alignment, Element, Fill};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{t, Key};
+use crate::plausibility;
+use crate::style;
+use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
+use crate::types::{Measurement, ParserError};
+
+thread_local! {
+    static LIBRARY: RefCell<Vec<Preset>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Kind of the quantity of a preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    #[default]
+    Voltage,
+    Current,
+    Resistance,
+    Power,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 4] = [Kind::Voltage, Kind::Current, Kind::Resistance, Kind::Power];
+
+    /// Parses `value` as a quantity of this kind. The error is the parser message or the
+    /// unit mismatch, e.g. for `3.2A` as a voltage.
+    fn check(self, value: &str) -> Result<(), String> {
+        fn parse<M>(value: &str) -> Result<(), String>
+        where
+            M: Measurement + FromStr<Err = ParserError>,
+        {
+            match value.parse::<M>() {
+                Ok(parsed) => match plausibility::check_unit(value, &parsed) {
+                    Some(mismatch) => Err(mismatch),
+                    None => Ok(()),
+                },
+                Err(ParserError::EmptyInput) => Err(t(Key::PresetValueEmpty).to_string()),
+                Err(ParserError::IncorrectInput(e)) => Err(e),
+            }
+        }
+
+        match self {
+            Kind::Voltage => parse::<Voltage>(value),
+            Kind::Current => parse::<Current>(value),
+            Kind::Resistance => parse::<Resistance>(value),
+            Kind::Power => parse::<Power>(value),
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            Kind::Voltage => Key::Voltage,
+            Kind::Current => Key::Current,
+            Kind::Resistance => Key::Resistance,
+            Kind::Power => Key::Power,
+        };
+        write!(f, "{}", t(key))
+    }
+}
+
+/// A named value, `value` is the text put into a field as if typed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub kind: Kind,
+    pub value: String,
+}
+
+impl Preset {
+    /// A preset with the trimmed `name` and `value`. Fails if the name is empty or the
+    /// value doesn't parse as a quantity of `kind`.
+    pub fn new(name: &str, kind: Kind, value: &str) -> Result<Preset, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(t(Key::PresetNameEmpty).to_string());
+        }
+        // лишние пробелы между числом и допуском не нужны в поле
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        kind.check(&value)?;
+
+        Ok(Preset {
+            name: name.to_string(),
+            kind,
+            value,
+        })
+    }
+
+    /// Text to put into a field of `kind`, `None` if the preset is of another kind.
+    pub fn insert_into(&self, kind: Kind) -> Option<&str> {
+        (self.kind == kind).then_some(self.value.as_str())
+    }
+}
+
+impl fmt::Display for Preset {
+    /// The name and the value, as listed next to a field.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
+    }
+}
+
+/// Presets of the current thread in the order they were added.
+pub fn presets() -> Vec<Preset> {
+    LIBRARY.with(|l| l.borrow().clone())
+}
+
+/// Presets of `kind`, the ones to offer next to a field of that kind.
+pub fn presets_of(kind: Kind) -> Vec<Preset> {
+    LIBRARY.with(|l| {
+        l.borrow()
+            .iter()
+            .filter(|preset| preset.kind == kind)
+            .cloned()
+            .collect()
+    })
+}
+
+/// Replaces the library of the current thread.
+pub fn set_presets(presets: Vec<Preset>) {
+    LIBRARY.with(|l| *l.borrow_mut() = presets);
+}
+
+/// Adds `preset`; a preset with the same name is replaced in place. Returns `true` if one
+/// was replaced.
+pub fn add(preset: Preset) -> bool {
+    LIBRARY.with(|l| {
+        let mut library = l.borrow_mut();
+        match library.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => {
+                *existing = preset;
+                true
+            }
+            None => {
+                library.push(preset);
+                false
+            }
+        }
+    })
+}
+
+pub fn remove(name: &str) {
+    LIBRARY.with(|l| l.borrow_mut().retain(|p| p.name != name));
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Library {
+    version: String,
+    presets: Vec<Preset>,
+}
+
+/// The library as JSON to import on another machine.
+pub fn export_json() -> String {
+    let library = Library {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        presets: presets(),
+    };
+
+    serde_json::to_string_pretty(&library).unwrap_or_default()
+}
+
+/// What to do with an imported preset named like one already in the library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    Keep,
+    Replace,
+}
+
+/// Counts of an import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub replaced: usize,
+    /// Presets skipped as the library already had one with the same name.
+    pub kept: usize,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}, {}: {}, {}: {}",
+            t(Key::PresetsAdded),
+            self.added,
+            t(Key::PresetsReplaced),
+            self.replaced,
+            t(Key::PresetsKept),
+            self.kept
+        )
+    }
+}
+
+/// Imports the presets of [`export_json`]. Every preset is checked as by [`Preset::new`]
+/// before any is added, so a bad file leaves the library as it was.
+pub fn import_json(json: &str, on_conflict: OnConflict) -> Result<ImportSummary, String> {
+    let library: Library = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let imported = library
+        .presets
+        .iter()
+        .map(|p| {
+            Preset::new(&p.name, p.kind, &p.value).map_err(|e| format!("\"{}\": {}", p.name, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut summary = ImportSummary::default();
+    for preset in imported {
+        let exists = LIBRARY.with(|l| l.borrow().iter().any(|p| p.name == preset.name));
+        match (exists, on_conflict) {
+            (true, OnConflict::Keep) => summary.kept += 1,
+            (true, OnConflict::Replace) => {
+                add(preset);
+                summary.replaced += 1;
+            }
+            (false, _) => {
+                add(preset);
+                summary.added += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// The presets manager: the list of the library and the form of a new preset.
+#[derive(Debug, Clone, Default)]
+pub struct Presets {
+    name: String,
+    kind: Kind,
+    value: String,
+    error: Option<String>,
+    replace_on_import: bool,
+    // итог последнего импорта или его ошибка
+    import_status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    NameChanged(String),
+    KindSelected(Kind),
+    ValueChanged(String),
+    Add,
+    /// Fills the form with the preset of the name to edit it.
+    Edit(String),
+    Remove(String),
+    ReplaceOnImportToggled(bool),
+    /// Handled by the application: copies [`export_json`] to the clipboard.
+    Export,
+    /// Handled by the application: reads the clipboard into [`Message::Imported`].
+    Import,
+    Imported(Option<String>),
+}
+
+impl Presets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(&self) -> String {
+        String::from(t(Key::Presets))
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::NameChanged(s) => self.name = s,
+            Message::KindSelected(kind) => self.kind = kind,
+            Message::ValueChanged(s) => self.value = s,
+            Message::Add => match Preset::new(&self.name, self.kind, &self.value) {
+                Ok(preset) => {
+                    add(preset);
+                    self.name.clear();
+                    self.value.clear();
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e),
+            },
+            Message::Edit(name) => {
+                if let Some(preset) = presets().into_iter().find(|p| p.name == name) {
+                    self.name = preset.name;
+                    self.kind = preset.kind;
+                    self.value = preset.value;
+                    self.error = None;
+                }
+            }
+            Message::Remove(name) => remove(&name),
+            Message::ReplaceOnImportToggled(replace) => self.replace_on_import = replace,
+            Message::Export | Message::Import => (),
+            Message::Imported(json) => {
+                let on_conflict = if self.replace_on_import {
+                    OnConflict::Replace
+                } else {
+                    OnConflict::Keep
+                };
+                let result = match json {
+                    Some(json) => import_json(&json, on_conflict),
+                    None => Err(t(Key::ClipboardEmpty).to_string()),
+                };
+                self.import_status = Some(match result {
+                    Ok(summary) => summary.to_string(),
+                    Err(e) => format!("{}: {}", t(Key::ImportFailed), e),
+                });
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let list = presets()
+            .into_iter()
+            .fold(Column::new().spacing(2), |list, preset| {
+                list.push(
+                    Row::new()
+                        .push(
+                            Button::new(Text::new(preset.name.clone()).size(14))
+                                .on_press(Message::Edit(preset.name.clone()))
+                                .style(button::text)
+                                .width(Fill),
+                        )
+                        .push(Text::new(preset.kind.to_string()).size(14).width(120))
+                        .push(Text::new(preset.value.clone()).size(14).width(Fill))
+                        .push(
+                            Button::new(Text::new("✕").size(12))
+                                .on_press(Message::Remove(preset.name))
+                                .style(button::text)
+                                .width(30),
+                        )
+                        .align_y(Alignment::Center),
+                )
+            });
+
+        let form = Row::new()
+            .push(
+                TextInput::new(t(Key::PresetName), &self.name)
+                    .on_input(Message::NameChanged)
+                    .on_submit(Message::Add)
+                    .size(15)
+                    .width(Fill),
+            )
+            .push(pick_list(Kind::ALL, Some(self.kind), Message::KindSelected).text_size(15))
+            .push(
+                TextInput::new(t(Key::PresetValue), &self.value)
+                    .on_input(Message::ValueChanged)
+                    .on_submit(Message::Add)
+                    .size(15)
+                    .width(Fill),
+            )
+            .push(button(Text::new(t(Key::AddPreset)).size(15)).on_press(Message::Add))
+            .spacing(5)
+            .align_y(Alignment::Center);
+        let form_hint = match &self.error {
+            Some(e) => Text::new(e.clone()).style(style::warning_text),
+            None => Text::new(t(Key::PresetsHint)).style(style::hint_text),
+        };
+
+        let exchange = Row::new()
+            .push(button(Text::new(t(Key::ExportPresets)).size(14)).on_press(Message::Export))
+            .push(button(Text::new(t(Key::ImportPresets)).size(14)).on_press(Message::Import))
+            .push(
+                checkbox(t(Key::ReplaceOnImport), self.replace_on_import)
+                    .on_toggle(Message::ReplaceOnImportToggled)
+                    .size(16)
+                    .text_size(14),
+            )
+            .spacing(10)
+            .align_y(Alignment::Center);
+        let import_status = Text::new(self.import_status.clone().unwrap_or_default())
+            .size(12)
+            .style(style::hint_text);
+
+        Column::new()
+            .push(Text::new(t(Key::Presets)).size(18))
+            .push(form)
+            .push(form_hint.size(12))
+            .push(list)
+            .push(exchange)
+            .push(import_status)
+            .spacing(5)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usb() -> Preset {
+        Preset::new("USB", Kind::Voltage, "5 5%").unwrap()
+    }
+
+    #[test]
+    fn test_preset_kind() {
+        assert_eq!(usb().value, "5 5%");
+        assert!(Preset::new("0603", Kind::Resistance, "100 1%").is_ok());
+
+        // значение проверяется по виду величины
+        assert!(Preset::new("cell", Kind::Voltage, "3.2x").is_err());
+        assert!(Preset::new("cell", Kind::Voltage, "3.2A").is_err());
+        assert!(Preset::new("cell", Kind::Voltage, "").is_err());
+        assert!(Preset::new("  ", Kind::Voltage, "3.2").is_err());
+
+        assert_eq!(usb().insert_into(Kind::Voltage), Some("5 5%"));
+        assert_eq!(usb().insert_into(Kind::Resistance), None);
+    }
+
+    #[test]
+    fn test_insertion_format() {
+        let preset = Preset::new(" LiFePO4 cell ", Kind::Voltage, "  3.2   +5% -10% ").unwrap();
+        assert_eq!(preset.name, "LiFePO4 cell");
+        assert_eq!(preset.insert_into(Kind::Voltage), Some("3.2 +5% -10%"));
+        assert_eq!(preset.to_string(), "LiFePO4 cell: 3.2 +5% -10%");
+    }
+
+    #[test]
+    fn test_library() {
+        assert!(!add(usb()));
+        add(Preset::new("Load", Kind::Resistance, "10").unwrap());
+        assert_eq!(presets_of(Kind::Voltage), vec![usb()]);
+
+        // то же имя заменяет пресет на месте
+        assert!(add(Preset::new("USB", Kind::Voltage, "5.1").unwrap()));
+        assert_eq!(presets().len(), 2);
+        assert_eq!(presets()[0].value, "5.1");
+
+        remove("USB");
+        assert_eq!(presets().len(), 1);
+
+        set_presets(Vec::new());
+    }
+
+    #[test]
+    fn test_import_conflict() {
+        add(usb());
+        let json = export_json();
+        assert!(json.contains(r#""kind": "voltage""#));
+
+        add(Preset::new("Load", Kind::Resistance, "10").unwrap());
+        let other = Preset::new("USB", Kind::Voltage, "5.1").unwrap();
+        set_presets(vec![other.clone()]);
+
+        // имя уже есть: пресет библиотеки остаётся
+        assert_eq!(
+            import_json(&json, OnConflict::Keep),
+            Ok(ImportSummary {
+                kept: 1,
+                ..Default::default()
+            })
+        );
+        assert_eq!(presets(), vec![other]);
+
+        assert_eq!(
+            import_json(&json, OnConflict::Replace),
+            Ok(ImportSummary {
+                replaced: 1,
+                ..Default::default()
+            })
+        );
+        assert_eq!(presets(), vec![usb()]);
+
+        set_presets(Vec::new());
+    }
+
+    #[test]
+    fn test_import_invalid() {
+        add(usb());
+        let json = r#"{"version": "0.1.0", "presets": [
+            {"name": "Load", "kind": "resistance", "value": "10"},
+            {"name": "Bad", "kind": "current", "value": "5V"}
+        ]}"#;
+
+        // ни один пресет плохого файла не добавляется
+        let error = import_json(json, OnConflict::Replace).unwrap_err();
+        assert!(error.starts_with("\"Bad\""));
+        assert!(import_json("{", OnConflict::Keep).is_err());
+        assert_eq!(presets(), vec![usb()]);
+
+        set_presets(Vec::new());
+    }
+
+    #[test]
+    fn test_manager() {
+        let mut manager = Presets::new();
+        manager.update(Message::NameChanged("USB".to_string()));
+        manager.update(Message::ValueChanged("5x".to_string()));
+        manager.update(Message::Add);
+        assert!(manager.error.is_some());
+        assert!(presets().is_empty());
+
+        manager.update(Message::ValueChanged("5 5%".to_string()));
+        manager.update(Message::Add);
+        assert_eq!(manager.error, None);
+        assert_eq!(presets(), vec![usb()]);
+        assert!(manager.name.is_empty());
+
+        manager.update(Message::Edit("USB".to_string()));
+        assert_eq!(manager.value, "5 5%");
+
+        manager.update(Message::Imported(None));
+        assert!(manager.import_status.is_some());
+
+        set_presets(Vec::new());
+    }
+}
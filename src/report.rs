@@ -0,0 +1,117 @@
+//! # Diagnostic Report
+//!
+//! A JSON snapshot of a scene for bug reports: raw inputs, parse results and computed
+//! outputs with their tolerance bounds. The report is built from the scene state only, so
+//! the same state always gives the same text.
+//!
+//! ```json
+//! {
+//!   "version": "0.1.0",
+//!   "scene": "ohm_law",
+//!   "state": { ... }
+//! }
+//! ```
+
+use serde::Serialize;
+
+use crate::types::{Measurement, ParserError, Tolerance};
+
+#[derive(Debug, Serialize)]
+struct Report<T: Serialize> {
+    version: &'static str,
+    scene: &'static str,
+    state: T,
+}
+
+/// A computed value with its tolerance band.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bounds {
+    pub nominal: f64,
+    pub min: f64,
+    pub max: f64,
+    pub tolerance: Option<Tolerance>,
+}
+
+impl Bounds {
+    pub fn of<M: Measurement>(measurement: &M) -> Self {
+        Self {
+            nominal: measurement.get_nominal_value(),
+            min: measurement.min_value(),
+            max: measurement.max_value(),
+            tolerance: measurement.get_tolerance(),
+        }
+    }
+}
+
+/// Bounds of a computed output, the error variant is kept as is.
+pub fn output<M: Measurement>(data: &Result<M, ParserError>) -> Result<Bounds, ParserError> {
+    data.as_ref().map(Bounds::of).map_err(Clone::clone)
+}
+
+/// Text of a failed parse to paste into a bug report: the version, the field, the input as
+/// entered and the parser message, which names the kind of the error and its position.
+pub fn parse_error(field: &str, raw: &str, message: &str) -> String {
+    format!(
+        "ecw {}\nfield: {}\ninput: {:?}\nerror: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        field,
+        raw,
+        message
+    )
+}
+
+/// Pretty-printed report of a scene.
+pub fn to_json<T: Serialize>(scene: &'static str, state: T) -> String {
+    let report = Report {
+        version: env!("CARGO_PKG_VERSION"),
+        scene,
+        state,
+    };
+
+    // сериализация структур без отображений с нестроковыми ключами не падает
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::voltage::Voltage;
+
+    #[test]
+    fn test_parse_error() {
+        let raw = "12 5x";
+        let Err(ParserError::IncorrectInput(message)) = raw.parse::<Voltage>() else {
+            panic!("{} must not parse", raw);
+        };
+
+        assert_eq!(
+            parse_error("voltage", raw, &message),
+            format!(
+                "ecw {}\nfield: voltage\ninput: \"12 5x\"\n\
+                 error: unexpected character 'x' at position 4\n",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn test_output() {
+        let voltage = "10 +10% -5%".parse::<Voltage>();
+        let bounds = output(&voltage).unwrap();
+        assert_eq!(bounds.nominal, 10.0);
+        assert!((bounds.max - 11.0).abs() < 1e-9);
+        assert!((bounds.min - 9.5).abs() < 1e-9);
+
+        let empty: Result<Voltage, ParserError> = Err(ParserError::EmptyInput);
+        assert_eq!(output(&empty), Err(ParserError::EmptyInput));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json("help", ());
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["scene"], "help");
+        assert_eq!(value["version"], env!("CARGO_PKG_VERSION"));
+        assert!(value["state"].is_null());
+    }
+}
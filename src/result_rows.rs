@@ -0,0 +1,288 @@
+//! # Result Rows
+//!
+//! The rows of the result tables of the Ohm law and the voltage divider scenes, e.g. the
+//! nominal value or the plus tolerance in percent, and the ones the user hid. The hidden rows
+//! are kept per scene and survive switching scenes; exports leave them out unless asked to
+//! include them.
+//!
+//! Like the interface language, the choice is kept per thread for the running session only;
+//! nothing is written to disk, so it is lost on restart.
+
+use std::cell::Cell;
+use std::iter;
+
+use serde::{Serialize, Serializer};
+
+use crate::i18n::{t, Key};
+use crate::types::tolerance_mode::tolerance_mode;
+use crate::types::{Measurement, ParserError};
+use crate::widgets::table;
+
+thread_local! {
+    static HIDDEN: Cell<HiddenRows> = const { Cell::new(HiddenRows::NONE) };
+}
+
+/// A row of a result table, in the order of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultRow {
+    ValueNom,
+    ValueMax,
+    ValueMin,
+    TolPlus,
+    TolMinus,
+    TolPlusPercent,
+    TolMinusPercent,
+}
+
+impl ResultRow {
+    pub const ALL: [ResultRow; 7] = [
+        ResultRow::ValueNom,
+        ResultRow::ValueMax,
+        ResultRow::ValueMin,
+        ResultRow::TolPlus,
+        ResultRow::TolMinus,
+        ResultRow::TolPlusPercent,
+        ResultRow::TolMinusPercent,
+    ];
+
+    /// The row at `index` of a result table, `None` past the result rows, e.g. for the
+    /// pinned result.
+    pub fn at(index: usize) -> Option<ResultRow> {
+        Self::ALL.get(index).copied()
+    }
+
+    pub fn label(self) -> &'static str {
+        t(match self {
+            ResultRow::ValueNom => Key::ValueNom,
+            ResultRow::ValueMax => Key::ValueMax,
+            ResultRow::ValueMin => Key::ValueMin,
+            ResultRow::TolPlus => Key::TolPlus,
+            ResultRow::TolMinus => Key::TolMinus,
+            ResultRow::TolPlusPercent => Key::TolPlusPercent,
+            ResultRow::TolMinusPercent => Key::TolMinusPercent,
+        })
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A set of result rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RowSet(u8);
+
+impl RowSet {
+    pub const EMPTY: RowSet = RowSet(0);
+
+    pub fn contains(self, row: ResultRow) -> bool {
+        self.0 & row.bit() != 0
+    }
+
+    /// The set with `row` added if it is not in it and removed otherwise.
+    pub fn toggled(self, row: ResultRow) -> RowSet {
+        RowSet(self.0 ^ row.bit())
+    }
+
+    /// `true` if the row at `index` of a result table is in the set.
+    pub fn contains_index(self, index: usize) -> bool {
+        ResultRow::at(index).is_some_and(|row| self.contains(row))
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Serialize for RowSet {
+    /// The rows of the set, e.g. `["tol_plus", "tol_minus"]`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(ResultRow::ALL.iter().filter(|row| self.contains(**row)))
+    }
+}
+
+/// Hidden rows of the result table of every scene.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct HiddenRows {
+    pub ohm_law: RowSet,
+    pub voltage_divider: RowSet,
+}
+
+impl HiddenRows {
+    pub const NONE: HiddenRows = HiddenRows {
+        ohm_law: RowSet::EMPTY,
+        voltage_divider: RowSet::EMPTY,
+    };
+}
+
+/// The seven result rows of a quantity in the order of [`ResultRow::ALL`], "N/A" in every
+/// row if there is no value. A cell with a number carries it in base units with full
+/// precision too, e.g. "0.0126" for "12.60mV", to copy into a spreadsheet.
+pub fn measurement_rows(m: &Result<impl Measurement, ParserError>) -> [table::Cell; 7] {
+    let Ok(m) = m else {
+        return std::array::from_fn(|_| table::Cell::new("N/A"));
+    };
+    let display = [
+        m.get_value_nom(),
+        m.get_value_max(),
+        m.get_value_min(),
+        m.get_tol_value_plus(),
+        m.get_tol_value_minus(),
+        m.get_tol_percent_plus(),
+        m.get_tol_percent_minus(),
+    ];
+    let exact = exact_rows(m);
+
+    std::array::from_fn(|index| match &exact[index] {
+        Some(exact) => table::Cell::new(display[index].clone()).exact(exact.to_string()),
+        None => table::Cell::new(display[index].clone()),
+    })
+}
+
+/// Numbers behind the rows of [`measurement_rows`], `None` where the row is "N/A".
+fn exact_rows(m: &impl Measurement) -> [Option<f64>; 7] {
+    let nominal = m.get_nominal_value();
+    let Some(tol) = m.get_tolerance() else {
+        return [Some(nominal), None, None, None, None, None, None];
+    };
+    let coverage = tolerance_mode().coverage();
+    // ограниченный нулём минимум показан как ноль
+    let min = if m.is_min_clamped() {
+        0.0
+    } else {
+        m.min_value()
+    };
+
+    [
+        Some(nominal),
+        Some(m.max_value()),
+        Some(min),
+        Some(nominal.abs() * coverage * tol.plus / 100.0),
+        Some(-nominal.abs() * coverage * tol.minus / 100.0),
+        Some(coverage * tol.plus),
+        Some(-coverage * tol.minus),
+    ]
+}
+
+/// Rows of a result table: the label of every row followed by the row of each column, e.g.
+/// of the voltage, current, resistance and power.
+pub fn table_rows(columns: &[[table::Cell; 7]]) -> Vec<Vec<table::Cell>> {
+    ResultRow::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            iter::once(table::Cell::new(row.label()))
+                .chain(columns.iter().map(|column| column[index].clone()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Sets the hidden rows for the current thread.
+pub fn set_hidden_rows(hidden: HiddenRows) {
+    HIDDEN.with(|h| h.set(hidden));
+}
+
+/// Returns the hidden rows of the current thread.
+pub fn hidden_rows() -> HiddenRows {
+    HIDDEN.with(|h| h.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{resistance::Resistance, voltage::Voltage};
+
+    #[test]
+    fn test_row_set() {
+        let set = RowSet::EMPTY
+            .toggled(ResultRow::TolPlusPercent)
+            .toggled(ResultRow::TolMinusPercent);
+        assert!(set.contains(ResultRow::TolPlusPercent));
+        assert!(!set.contains(ResultRow::ValueNom));
+        assert!(set.contains_index(6));
+        assert!(!set.contains_index(7));
+        assert_eq!(
+            serde_json::to_string(&set).unwrap(),
+            r#"["tol_plus_percent","tol_minus_percent"]"#
+        );
+
+        let set = set
+            .toggled(ResultRow::TolPlusPercent)
+            .toggled(ResultRow::TolMinusPercent);
+        assert!(set.is_empty());
+    }
+
+    fn texts(cells: &[table::Cell]) -> Vec<&str> {
+        cells.iter().map(|cell| cell.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_measurement_rows() {
+        let voltage = "12 +5% -2%".parse::<Voltage>();
+        assert_eq!(
+            texts(&measurement_rows(&voltage)),
+            [
+                "12.00V",
+                "12.60V",
+                "11.76V",
+                "600.00mV",
+                "-240.00mV",
+                "5.00%",
+                "-2.00%"
+            ]
+        );
+        let none: Result<Voltage, _> = Err(ParserError::EmptyInput);
+        assert_eq!(texts(&measurement_rows(&none)), ["N/A"; 7]);
+
+        let rows = table_rows(&[measurement_rows(&voltage), measurement_rows(&none)]);
+        assert_eq!(rows.len(), 7);
+        assert_eq!(
+            texts(&rows[2]),
+            [ResultRow::ValueMin.label(), "11.76V", "N/A"]
+        );
+    }
+
+    #[test]
+    fn test_exact_rows() {
+        let current = Ok(Voltage {
+            value: 7.2,
+            tolerance: None,
+        } / "4.7k".parse::<Resistance>().unwrap());
+        let rows = measurement_rows(&current);
+        assert_eq!(rows[0].text, "1.53mA");
+        assert_eq!(rows[0].exact.as_deref(), Some("0.0015319148936170214"));
+        // без допуска строкам границ нечего копировать, кроме "N/A"
+        assert_eq!(rows[1].copy().text(true), "N/A");
+
+        let voltage = "12 +5% -2%".parse::<Voltage>();
+        let exact: Vec<_> = measurement_rows(&voltage)
+            .iter()
+            .map(|cell| cell.copy().text(true).parse::<f64>().unwrap())
+            .collect();
+        let expected = [12.0, 12.6, 11.76, 0.6, -0.24, 5.0, -2.0];
+        for (exact, expected) in exact.iter().zip(expected) {
+            assert!((exact - expected).abs() < 1e-9, "{} != {}", exact, expected);
+        }
+
+        // минимум, ограниченный нулём
+        let resistance = "10 +5% -150%".parse::<Resistance>();
+        assert_eq!(measurement_rows(&resistance)[2].exact.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn test_hidden_rows() {
+        assert_eq!(hidden_rows(), HiddenRows::NONE);
+
+        let hidden = HiddenRows {
+            ohm_law: RowSet::EMPTY.toggled(ResultRow::TolPlus),
+            ..HiddenRows::NONE
+        };
+        set_hidden_rows(hidden);
+        assert_eq!(hidden_rows(), hidden);
+        assert!(hidden_rows().voltage_divider.is_empty());
+
+        set_hidden_rows(HiddenRows::NONE);
+    }
+}
@@ -0,0 +1,188 @@
+//! # Self-Check
+//!
+//! Canonical inputs parsed and formatted back, and reference Ohm's law cases, compared with
+//! the text they gave when written. A mismatch points at the float formatting or rounding
+//! of the platform rather than at the input of the user, which is hard to tell from a bug
+//! report alone.
+//!
+//! Debug builds run the check at startup unless it is turned off on the settings page or
+//! with `--self-check off`; the settings page also runs it on demand. The unit tests run
+//! the same lists, so the expected values can't drift from the ones checked at runtime.
+
+use std::cell::Cell;
+use std::str::FromStr;
+
+use crate::types::tolerance_mode::{set_tolerance_mode, tolerance_mode, ToleranceMode};
+use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
+use crate::types::{default_tolerance, Measurement, ParserError};
+
+thread_local! {
+    static AT_STARTUP: Cell<bool> = const { Cell::new(true) };
+}
+
+/// Sets whether debug builds run the check at startup.
+pub fn set_at_startup(on: bool) {
+    AT_STARTUP.with(|s| s.set(on));
+}
+
+pub fn at_startup() -> bool {
+    AT_STARTUP.with(|s| s.get())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    Voltage,
+    Current,
+    Resistance,
+    Power,
+}
+
+/// An input and its nominal value and tolerances as shown in the result table.
+#[derive(Debug, Clone, Copy)]
+pub struct FormatCase {
+    pub quantity: Quantity,
+    pub input: &'static str,
+    pub expected: &'static str,
+}
+
+/// A voltage over a resistance and the current and power they give.
+#[derive(Debug, Clone, Copy)]
+pub struct OhmCase {
+    pub voltage: &'static str,
+    pub resistance: &'static str,
+    pub current: &'static str,
+    pub power: &'static str,
+}
+
+pub const FORMAT_CASES: &[FormatCase] = &[
+    FormatCase {
+        quantity: Quantity::Resistance,
+        input: "4.7k 5%",
+        expected: "4.70kR 5.00% -5.00%",
+    },
+    FormatCase {
+        quantity: Quantity::Resistance,
+        input: "100m",
+        expected: "100.00mR N/A N/A",
+    },
+    FormatCase {
+        quantity: Quantity::Voltage,
+        input: "3.3 +10% -5%",
+        expected: "3.30V 10.00% -5.00%",
+    },
+    // округление на границе приставки
+    FormatCase {
+        quantity: Quantity::Voltage,
+        input: "999.999",
+        expected: "1000.00V N/A N/A",
+    },
+    // половина последнего знака округляется к чётному
+    FormatCase {
+        quantity: Quantity::Current,
+        input: "-12.5m 0.125%",
+        expected: "-12.50mA 0.12% -0.12%",
+    },
+    FormatCase {
+        quantity: Quantity::Power,
+        input: "0.25",
+        expected: "250.00mW N/A N/A",
+    },
+];
+
+pub const OHM_CASES: &[OhmCase] = &[
+    OhmCase {
+        voltage: "12 5%",
+        resistance: "1k 1%",
+        current: "12.00mA 6.00% -6.00%",
+        power: "144.00mW 11.00% -11.00%",
+    },
+    OhmCase {
+        voltage: "5",
+        resistance: "3",
+        current: "1.67A N/A N/A",
+        power: "8.33W N/A N/A",
+    },
+];
+
+fn format<M: Measurement>(measurement: &M) -> String {
+    format!(
+        "{} {} {}",
+        measurement.get_value_nom(),
+        measurement.get_tol_percent_plus(),
+        measurement.get_tol_percent_minus()
+    )
+}
+
+fn parse<M>(input: &str) -> Result<M, String>
+where
+    M: Measurement + FromStr<Err = ParserError>,
+{
+    input
+        .parse::<M>()
+        .map_err(|e| format!("{:?} does not parse: {:?}", input, e))
+}
+
+fn check_format(case: &FormatCase) -> Result<String, String> {
+    match case.quantity {
+        Quantity::Voltage => parse::<Voltage>(case.input).map(|v| format(&v)),
+        Quantity::Current => parse::<Current>(case.input).map(|v| format(&v)),
+        Quantity::Resistance => parse::<Resistance>(case.input).map(|v| format(&v)),
+        Quantity::Power => parse::<Power>(case.input).map(|v| format(&v)),
+    }
+}
+
+/// Runs every case and returns a line per mismatch, empty if all agree. The cases are run
+/// in the limits mode without default tolerances, the settings in force are restored.
+pub fn run() -> Vec<String> {
+    let mode = tolerance_mode();
+    let defaults = default_tolerance::default_tolerances();
+    set_tolerance_mode(ToleranceMode::Limits);
+    default_tolerance::set_default_tolerances(default_tolerance::DefaultTolerances::OFF);
+
+    let mut mismatches = Vec::new();
+    let mut compare = |what: String, actual: Result<String, String>, expected: &str| match actual {
+        Ok(actual) if actual == expected => (),
+        Ok(actual) => mismatches.push(format!(
+            "{}: expected \"{}\", got \"{}\"",
+            what, expected, actual
+        )),
+        Err(e) => mismatches.push(format!("{}: {}", what, e)),
+    };
+
+    for case in FORMAT_CASES {
+        compare(case.input.to_string(), check_format(case), case.expected);
+    }
+    for case in OHM_CASES {
+        let inputs = parse::<Voltage>(case.voltage)
+            .and_then(|v| parse::<Resistance>(case.resistance).map(|r| (v, r)));
+        let what = format!("{} / {}", case.voltage, case.resistance);
+        let current = inputs.clone().map(|(v, r)| format(&(v / r)));
+        let power = inputs.map(|(v, r)| format(&(v * (v / r))));
+        compare(format!("I = {}", what), current, case.current);
+        compare(format!("P = {}", what), power, case.power);
+    }
+
+    set_tolerance_mode(mode);
+    default_tolerance::set_default_tolerances(defaults);
+
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cases() {
+        assert_eq!(run(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_settings_restored() {
+        set_tolerance_mode(ToleranceMode::Statistical { coverage: 2 });
+        assert!(run().is_empty());
+        assert_eq!(tolerance_mode(), ToleranceMode::Statistical { coverage: 2 });
+
+        set_tolerance_mode(ToleranceMode::Limits);
+    }
+}
@@ -0,0 +1,737 @@
+use iced::widget::{button, checkbox, Column, Container, Row, Text, TextInput};
+use iced::{Alignment, Element, Fill};
+use serde::Serialize;
+
+use crate::debounce;
+use crate::i18n::{t, Key};
+use crate::margins::{self, Margins};
+use crate::parser;
+use crate::plausibility;
+use crate::report;
+use crate::result_rows::{self, HiddenRows};
+use crate::self_check;
+use crate::style;
+use crate::types::default_tolerance::{self, DefaultTolerances};
+use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
+use crate::types::{Measurement, ParserError};
+use crate::voltage_divider;
+use crate::widgets::table;
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    max_voltage: LimitInput,
+    max_current: LimitInput,
+    max_power: LimitInput,
+    min_resistance: LimitInput,
+    max_current_density: LimitInput,
+    default_voltage: LimitInput,
+    default_current: LimitInput,
+    default_resistance: LimitInput,
+    default_power: LimitInput,
+    debounce: LimitInput,
+    max_input_len: LimitInput,
+    derating: LimitInput,
+    headroom: LimitInput,
+    divider_legs: LimitInput,
+    // итог последней самопроверки по кнопке
+    self_check: Option<Vec<String>>,
+}
+
+/// Entered text of a limit and the error if it does not parse. An empty field turns the
+/// limit off.
+#[derive(Debug, Clone, Default)]
+struct LimitInput {
+    raw: String,
+    error: Option<String>,
+}
+
+impl LimitInput {
+    fn new(limit: Option<f64>, input: impl Fn(f64) -> String) -> Self {
+        Self {
+            raw: limit.map(input).unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    /// Parses `raw` as a `M` and returns its nominal value, or `None` if it is empty.
+    /// On a parse error the previous `limit` is kept.
+    fn update<M>(&mut self, raw: String, limit: Option<f64>) -> Option<f64>
+    where
+        M: Measurement + std::str::FromStr<Err = ParserError>,
+    {
+        self.raw = raw;
+        self.error = None;
+
+        match self.raw.parse::<M>() {
+            Ok(value) => Some(value.get_nominal_value()),
+            Err(ParserError::EmptyInput) => None,
+            Err(ParserError::IncorrectInput(e)) => {
+                self.error = Some(e);
+                limit
+            }
+        }
+    }
+
+    /// Parses `raw` as a percentage ("1" or "1%"), or `None` if it is empty. On a parse
+    /// error the previous `percent` is kept.
+    fn update_percent(&mut self, raw: String, percent: Option<f64>) -> Option<f64> {
+        self.raw = raw;
+        self.error = None;
+
+        let input = self.raw.trim();
+        if input.is_empty() {
+            return None;
+        }
+
+        match input.trim_end_matches('%').trim().parse::<f64>() {
+            Ok(value) if value >= 0.0 && value.is_finite() => Some(value),
+            _ => {
+                self.error = Some(t(Key::IncorrectPercent).to_string());
+                percent
+            }
+        }
+    }
+
+    /// Parses `raw` as a factor of at least one, `1.0` (no margin) if it is empty. On a parse
+    /// error the previous `factor` is kept.
+    fn update_factor(&mut self, raw: String, factor: f64) -> f64 {
+        self.raw = raw;
+        self.error = None;
+
+        if self.raw.trim().is_empty() {
+            return 1.0;
+        }
+
+        margins::parse_factor(&self.raw).unwrap_or_else(|| {
+            self.error = Some(t(Key::IncorrectFactor).to_string());
+            factor
+        })
+    }
+
+    /// Parses `raw` as a leg count of a new divider, see [`voltage_divider::parse_leg_count`].
+    /// On a parse error the previous `legs` is kept.
+    fn update_legs(&mut self, raw: String, legs: usize) -> usize {
+        self.raw = raw;
+        self.error = None;
+
+        voltage_divider::parse_leg_count(&self.raw).unwrap_or_else(|| {
+            self.error = Some(t(Key::IncorrectLegCount).to_string());
+            legs
+        })
+    }
+
+    /// Parses `raw` as a whole number of characters above zero, [`parser::MAX_INPUT_LEN`] if
+    /// it is empty. On a parse error the previous `chars` is kept.
+    fn update_chars(&mut self, raw: String, chars: usize) -> usize {
+        self.raw = raw;
+        self.error = None;
+
+        let input = self.raw.trim();
+        if input.is_empty() {
+            return parser::MAX_INPUT_LEN;
+        }
+
+        match input.parse::<usize>() {
+            Ok(value) if value > 0 => value,
+            _ => {
+                self.error = Some(t(Key::IncorrectInputLength).to_string());
+                chars
+            }
+        }
+    }
+
+    /// Parses `raw` as whole milliseconds, `0` if it is empty. On a parse error the previous
+    /// `ms` is kept.
+    fn update_ms(&mut self, raw: String, ms: u64) -> u64 {
+        self.raw = raw;
+        self.error = None;
+
+        let input = self.raw.trim();
+        if input.is_empty() {
+            return 0;
+        }
+
+        match input.trim_end_matches("ms").trim().parse::<u64>() {
+            Ok(value) if value <= debounce::MAX_INTERVAL_MS => value,
+            _ => {
+                self.error = Some(t(Key::IncorrectInterval).to_string());
+                ms
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    MaxVoltage(String),
+    MaxCurrent(String),
+    MaxPower(String),
+    MinResistance(String),
+    MaxCurrentDensity(String),
+    DefaultVoltage(String),
+    DefaultCurrent(String),
+    DefaultResistance(String),
+    DefaultPower(String),
+    Debounce(String),
+    MaxInputLength(String),
+    Derating(String),
+    Headroom(String),
+    DividerLegs(String),
+    MonospaceValues(bool),
+    SelfCheckAtStartup(bool),
+    RunSelfCheck,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        let limits = plausibility::limits();
+        let defaults = default_tolerance::default_tolerances();
+        let percent = |value: f64| format!("{}%", value);
+        let margins = margins::margins();
+        // множитель 1 — запаса нет, поле пустое
+        let factor =
+            |value: f64| LimitInput::new((value != 1.0).then_some(value), |v| v.to_string());
+
+        Self {
+            max_voltage: LimitInput::new(limits.max_voltage, |value| {
+                Voltage {
+                    value,
+                    tolerance: None,
+                }
+                .get_value_input()
+            }),
+            max_current: LimitInput::new(limits.max_current, |value| {
+                Current {
+                    value,
+                    tolerance: None,
+                }
+                .get_value_input()
+            }),
+            max_power: LimitInput::new(limits.max_power, |value| {
+                Power {
+                    value,
+                    tolerance: None,
+                }
+                .get_value_input()
+            }),
+            min_resistance: LimitInput::new(limits.min_resistance, |value| {
+                Resistance {
+                    value,
+                    tolerance: None,
+                }
+                .get_value_input()
+            }),
+            // плотность тока вводится как ток через 1 мм²
+            max_current_density: LimitInput::new(limits.max_current_density, |value| {
+                Current {
+                    value,
+                    tolerance: None,
+                }
+                .get_value_input()
+            }),
+            default_voltage: LimitInput::new(defaults.voltage, percent),
+            default_current: LimitInput::new(defaults.current, percent),
+            default_resistance: LimitInput::new(defaults.resistance, percent),
+            default_power: LimitInput::new(defaults.power, percent),
+            debounce: LimitInput {
+                raw: match debounce::interval_ms() {
+                    0 => String::new(),
+                    ms => ms.to_string(),
+                },
+                error: None,
+            },
+            max_input_len: LimitInput {
+                raw: match parser::max_input_len() {
+                    parser::MAX_INPUT_LEN => String::new(),
+                    chars => chars.to_string(),
+                },
+                error: None,
+            },
+            derating: factor(margins.derating),
+            headroom: factor(margins.headroom),
+            // два плеча по умолчанию — поле пустое
+            divider_legs: LimitInput {
+                raw: match voltage_divider::default_legs() {
+                    2 => String::new(),
+                    legs => legs.to_string(),
+                },
+                error: None,
+            },
+            self_check: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        String::from(t(Key::Settings))
+    }
+
+    pub fn update(&mut self, message: Message) {
+        let mut limits = plausibility::limits();
+        let mut defaults = default_tolerance::default_tolerances();
+        let mut margins = margins::margins();
+
+        match message {
+            Message::MaxVoltage(s) => {
+                limits.max_voltage = self.max_voltage.update::<Voltage>(s, limits.max_voltage);
+            }
+            Message::MaxCurrent(s) => {
+                limits.max_current = self.max_current.update::<Current>(s, limits.max_current);
+            }
+            Message::MaxPower(s) => {
+                limits.max_power = self.max_power.update::<Power>(s, limits.max_power);
+            }
+            Message::MinResistance(s) => {
+                limits.min_resistance = self
+                    .min_resistance
+                    .update::<Resistance>(s, limits.min_resistance);
+            }
+            Message::MaxCurrentDensity(s) => {
+                limits.max_current_density = self
+                    .max_current_density
+                    .update::<Current>(s, limits.max_current_density);
+            }
+            Message::DefaultVoltage(s) => {
+                defaults.voltage = self.default_voltage.update_percent(s, defaults.voltage);
+            }
+            Message::DefaultCurrent(s) => {
+                defaults.current = self.default_current.update_percent(s, defaults.current);
+            }
+            Message::DefaultResistance(s) => {
+                defaults.resistance = self
+                    .default_resistance
+                    .update_percent(s, defaults.resistance);
+            }
+            Message::DefaultPower(s) => {
+                defaults.power = self.default_power.update_percent(s, defaults.power);
+            }
+            Message::Debounce(s) => {
+                debounce::set_interval_ms(self.debounce.update_ms(s, debounce::interval_ms()));
+            }
+            Message::MaxInputLength(s) => {
+                parser::set_max_input_len(
+                    self.max_input_len.update_chars(s, parser::max_input_len()),
+                );
+            }
+            Message::Derating(s) => {
+                margins.derating = self.derating.update_factor(s, margins.derating);
+            }
+            Message::Headroom(s) => {
+                margins.headroom = self.headroom.update_factor(s, margins.headroom);
+            }
+            Message::DividerLegs(s) => {
+                voltage_divider::set_default_legs(
+                    self.divider_legs
+                        .update_legs(s, voltage_divider::default_legs()),
+                );
+            }
+            Message::MonospaceValues(on) => table::set_monospace_values(on),
+            Message::SelfCheckAtStartup(on) => self_check::set_at_startup(on),
+            Message::RunSelfCheck => self.self_check = Some(self_check::run()),
+        }
+
+        plausibility::set_limits(limits);
+        default_tolerance::set_default_tolerances(defaults);
+        margins::set_margins(margins);
+    }
+
+    /// Settings in force, for the diagnostic report.
+    pub fn report() -> String {
+        #[derive(Serialize)]
+        struct State {
+            limits: plausibility::Limits,
+            default_tolerances: DefaultTolerances,
+            debounce_ms: u64,
+            max_input_len: usize,
+            margins: Margins,
+            hidden_rows: HiddenRows,
+            divider_legs: usize,
+            monospace_values: bool,
+            self_check_at_startup: bool,
+        }
+
+        report::to_json(
+            "settings",
+            State {
+                limits: plausibility::limits(),
+                default_tolerances: default_tolerance::default_tolerances(),
+                debounce_ms: debounce::interval_ms(),
+                max_input_len: parser::max_input_len(),
+                margins: margins::margins(),
+                hidden_rows: result_rows::hidden_rows(),
+                divider_legs: voltage_divider::default_legs(),
+                monospace_values: table::monospace_values(),
+                self_check_at_startup: self_check::at_startup(),
+            },
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        Column::new()
+            .push(Text::new(t(Key::PlausibilityLimits)).size(18))
+            .push(
+                Text::new(t(Key::PlausibilityLimitsHint))
+                    .size(12)
+                    .style(style::hint_text),
+            )
+            .push(field(
+                t(Key::MaxVoltage),
+                &self.max_voltage,
+                Message::MaxVoltage,
+                Key::LimitOffHint,
+            ))
+            .push(field(
+                t(Key::MaxCurrent),
+                &self.max_current,
+                Message::MaxCurrent,
+                Key::LimitOffHint,
+            ))
+            .push(field(
+                t(Key::MaxPower),
+                &self.max_power,
+                Message::MaxPower,
+                Key::LimitOffHint,
+            ))
+            .push(field(
+                t(Key::MinResistance),
+                &self.min_resistance,
+                Message::MinResistance,
+                Key::LimitOffHint,
+            ))
+            .push(field(
+                t(Key::MaxCurrentDensity),
+                &self.max_current_density,
+                Message::MaxCurrentDensity,
+                Key::LimitOffHint,
+            ))
+            .push(Text::new(t(Key::DefaultTolerances)).size(18))
+            .push(
+                Text::new(t(Key::DefaultTolerancesHint))
+                    .size(12)
+                    .style(style::hint_text),
+            )
+            .push(field(
+                t(Key::Voltage),
+                &self.default_voltage,
+                Message::DefaultVoltage,
+                Key::DefaultToleranceOffHint,
+            ))
+            .push(field(
+                t(Key::Current),
+                &self.default_current,
+                Message::DefaultCurrent,
+                Key::DefaultToleranceOffHint,
+            ))
+            .push(field(
+                t(Key::Resistance),
+                &self.default_resistance,
+                Message::DefaultResistance,
+                Key::DefaultToleranceOffHint,
+            ))
+            .push(field(
+                t(Key::Power),
+                &self.default_power,
+                Message::DefaultPower,
+                Key::DefaultToleranceOffHint,
+            ))
+            .push(Text::new(t(Key::InputDebounce)).size(18))
+            .push(
+                Text::new(t(Key::InputDebounceHint))
+                    .size(12)
+                    .style(style::hint_text),
+            )
+            .push(field(
+                t(Key::DebounceInterval),
+                &self.debounce,
+                Message::Debounce,
+                Key::DebounceOffHint,
+            ))
+            .push(field(
+                t(Key::MaxInputLength),
+                &self.max_input_len,
+                Message::MaxInputLength,
+                Key::MaxInputLengthHint,
+            ))
+            .push(Text::new(t(Key::SafetyMargins)).size(18))
+            .push(
+                Text::new(t(Key::SafetyMarginsHint))
+                    .size(12)
+                    .style(style::hint_text),
+            )
+            .push(field(
+                t(Key::Derating),
+                &self.derating,
+                Message::Derating,
+                Key::MarginOffHint,
+            ))
+            .push(field(
+                t(Key::Headroom),
+                &self.headroom,
+                Message::Headroom,
+                Key::MarginOffHint,
+            ))
+            .push(Text::new(t(Key::ResultTables)).size(18))
+            .push(
+                Text::new(t(Key::MonospaceValuesHint))
+                    .size(12)
+                    .style(style::hint_text),
+            )
+            .push(
+                checkbox(t(Key::MonospaceValues), table::monospace_values())
+                    .on_toggle(Message::MonospaceValues)
+                    .size(16)
+                    .text_size(15),
+            )
+            .push(Text::new(t(Key::VoltageDivider)).size(18))
+            .push(field(
+                t(Key::DefaultLegs),
+                &self.divider_legs,
+                Message::DividerLegs,
+                Key::DefaultLegsHint,
+            ))
+            .push(Text::new(t(Key::SelfCheck)).size(18))
+            .push(
+                Text::new(t(Key::SelfCheckHint))
+                    .size(12)
+                    .style(style::hint_text),
+            )
+            .push(
+                Row::new()
+                    .push(
+                        checkbox(t(Key::SelfCheckAtStartup), self_check::at_startup())
+                            .on_toggle(Message::SelfCheckAtStartup)
+                            .size(16)
+                            .text_size(15)
+                            .width(Fill),
+                    )
+                    .push(
+                        button(Text::new(t(Key::RunSelfCheck)).size(14))
+                            .on_press(Message::RunSelfCheck),
+                    )
+                    .align_y(Alignment::Center),
+            )
+            .push(self.view_self_check())
+            .spacing(5)
+            .into()
+    }
+
+    /// Result of the last run: a line per mismatch, or a note that all values match.
+    fn view_self_check(&self) -> Element<'_, Message> {
+        let Some(mismatches) = &self.self_check else {
+            return Column::new().into();
+        };
+        if mismatches.is_empty() {
+            return Text::new(t(Key::SelfCheckPassed))
+                .size(12)
+                .style(style::hint_text)
+                .into();
+        }
+
+        mismatches
+            .iter()
+            .fold(Column::new(), |column, mismatch| {
+                column.push(
+                    Text::new(mismatch.as_str())
+                        .size(12)
+                        .style(style::warning_text),
+                )
+            })
+            .into()
+    }
+}
+
+fn field<'a>(
+    label: &'a str,
+    input: &'a LimitInput,
+    on_input: impl Fn(String) -> Message + 'a,
+    hint: Key,
+) -> Element<'a, Message> {
+    const LABEL_WIDTH: u16 = 110;
+
+    let under_text = match &input.error {
+        Some(e) => e.as_str(),
+        None => t(hint),
+    };
+    let label = Container::new(Text::new(label).size(15))
+        .align_y(Alignment::Center)
+        .width(LABEL_WIDTH)
+        .height(30);
+    let under_text = Container::new(Text::new(under_text).size(12).style(style::hint_text))
+        .padding([0, LABEL_WIDTH]);
+
+    Column::new()
+        .push(
+            Row::new()
+                .push(label)
+                .push(TextInput::new("", &input.raw).size(15).on_input(on_input)),
+        )
+        .push(under_text)
+        .width(Fill)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_update() {
+        let mut settings = Settings::new();
+        assert_eq!(plausibility::limits(), plausibility::Limits::OFF);
+
+        settings.update(Message::MaxCurrent("10".to_string()));
+        settings.update(Message::MinResistance("1m".to_string()));
+        assert_eq!(plausibility::limits().max_current, Some(10.0));
+        assert_eq!(plausibility::limits().min_resistance, Some(1e-3));
+
+        // ошибка ввода не сбрасывает предел
+        settings.update(Message::MaxCurrent("10x".to_string()));
+        assert!(settings.max_current.error.is_some());
+        assert_eq!(plausibility::limits().max_current, Some(10.0));
+
+        settings.update(Message::MaxCurrent("".to_string()));
+        assert_eq!(plausibility::limits().max_current, None);
+
+        // the page opens with the limits in force
+        assert_eq!(Settings::new().min_resistance.raw, "1m");
+
+        plausibility::set_limits(plausibility::Limits::OFF);
+    }
+
+    #[test]
+    fn test_default_tolerances_update() {
+        let mut settings = Settings::new();
+
+        settings.update(Message::DefaultResistance("1%".to_string()));
+        settings.update(Message::DefaultVoltage("0.5".to_string()));
+        let defaults = default_tolerance::default_tolerances();
+        assert_eq!(defaults.resistance, Some(1.0));
+        assert_eq!(defaults.voltage, Some(0.5));
+        assert_eq!(defaults.current, None);
+        assert_eq!(
+            "10k".parse::<Resistance>().unwrap().get_value_max(),
+            "10.10kR"
+        );
+
+        settings.update(Message::DefaultResistance("-1%".to_string()));
+        assert!(settings.default_resistance.error.is_some());
+        assert_eq!(
+            default_tolerance::default_tolerances().resistance,
+            Some(1.0)
+        );
+
+        assert_eq!(Settings::new().default_voltage.raw, "0.5%");
+
+        settings.update(Message::DefaultResistance("".to_string()));
+        assert_eq!(default_tolerance::default_tolerances().resistance, None);
+
+        default_tolerance::set_default_tolerances(DefaultTolerances::OFF);
+    }
+
+    #[test]
+    fn test_debounce_update() {
+        let mut settings = Settings::new();
+        assert_eq!(settings.debounce.raw, "150");
+
+        settings.update(Message::Debounce("50".to_string()));
+        assert_eq!(debounce::interval_ms(), 50);
+        assert_eq!(Settings::new().debounce.raw, "50");
+
+        // больше предела или дробное — ошибка, интервал не меняется
+        settings.update(Message::Debounce("5000".to_string()));
+        assert!(settings.debounce.error.is_some());
+        settings.update(Message::Debounce("2.5".to_string()));
+        assert_eq!(debounce::interval_ms(), 50);
+
+        settings.update(Message::Debounce("".to_string()));
+        assert!(!debounce::is_on());
+    }
+
+    #[test]
+    fn test_max_input_len_update() {
+        let mut settings = Settings::new();
+        assert_eq!(settings.max_input_len.raw, "");
+
+        settings.update(Message::MaxInputLength("64".to_string()));
+        assert_eq!(parser::max_input_len(), 64);
+        assert_eq!(Settings::new().max_input_len.raw, "64");
+
+        for raw in ["0", "-1", "1.5"] {
+            settings.update(Message::MaxInputLength(raw.to_string()));
+            assert!(settings.max_input_len.error.is_some(), "{}", raw);
+            assert_eq!(parser::max_input_len(), 64);
+        }
+
+        settings.update(Message::MaxInputLength("".to_string()));
+        assert_eq!(parser::max_input_len(), parser::MAX_INPUT_LEN);
+    }
+
+    #[test]
+    fn test_margins_update() {
+        let mut settings = Settings::new();
+        assert_eq!(settings.derating.raw, "2");
+        assert_eq!(settings.headroom.raw, "1.2");
+
+        settings.update(Message::Derating("1.5x".to_string()));
+        assert_eq!(margins::margins().derating, 1.5);
+
+        settings.update(Message::Headroom("0.8".to_string()));
+        assert!(settings.headroom.error.is_some());
+        assert_eq!(margins::margins().headroom, 1.2);
+
+        // пустое поле — без запаса
+        settings.update(Message::Headroom("".to_string()));
+        assert_eq!(margins::margins().headroom, 1.0);
+        assert_eq!(Settings::new().headroom.raw, "");
+
+        margins::set_margins(Margins::DEFAULT);
+    }
+
+    #[test]
+    fn test_divider_legs_update() {
+        let mut settings = Settings::new();
+        assert_eq!(settings.divider_legs.raw, "");
+
+        settings.update(Message::DividerLegs("3".to_string()));
+        assert_eq!(voltage_divider::default_legs(), 3);
+        assert_eq!(Settings::new().divider_legs.raw, "3");
+
+        for raw in ["0", "65", "2.5", "two"] {
+            settings.update(Message::DividerLegs(raw.to_string()));
+            assert!(settings.divider_legs.error.is_some(), "{}", raw);
+            assert_eq!(voltage_divider::default_legs(), 3);
+        }
+
+        settings.update(Message::DividerLegs("".to_string()));
+        assert_eq!(voltage_divider::default_legs(), 2);
+    }
+
+    #[test]
+    fn test_monospace_values() {
+        let mut settings = Settings::new();
+        settings.update(Message::MonospaceValues(false));
+        assert!(!table::monospace_values());
+        assert!(Settings::report().contains(r#""monospace_values": false"#));
+
+        settings.update(Message::MonospaceValues(true));
+        assert!(table::monospace_values());
+    }
+
+    #[test]
+    fn test_self_check() {
+        let mut settings = Settings::new();
+        settings.update(Message::SelfCheckAtStartup(false));
+        assert!(!self_check::at_startup());
+        assert!(Settings::report().contains(r#""self_check_at_startup": false"#));
+
+        settings.update(Message::RunSelfCheck);
+        assert_eq!(settings.self_check, Some(Vec::new()));
+
+        self_check::set_at_startup(true);
+    }
+}
@@ -0,0 +1,127 @@
+//! # Pinned Results
+//!
+//! A snapshot of the four quantities of a result, pinned to compare the live result
+//! against while the inputs are tweaked. The comparison is shown as two extra table rows:
+//! the pinned nominal values and their change in percent.
+
+use crate::i18n::{t, Key};
+use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
+use crate::types::{Measurement, ParserError};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Snapshot {
+    pub voltage: Option<Voltage>,
+    pub current: Option<Current>,
+    pub resistance: Option<Resistance>,
+    pub power: Option<Power>,
+}
+
+impl Snapshot {
+    pub fn capture(
+        voltage: &Result<Voltage, ParserError>,
+        current: &Result<Current, ParserError>,
+        resistance: &Result<Resistance, ParserError>,
+        power: &Result<Power, ParserError>,
+    ) -> Self {
+        Self {
+            voltage: voltage.as_ref().ok().copied(),
+            current: current.as_ref().ok().copied(),
+            resistance: resistance.as_ref().ok().copied(),
+            power: power.as_ref().ok().copied(),
+        }
+    }
+
+    /// Change of every nominal value from the pinned `self` to `live`, in percent, in
+    /// voltage, current, resistance, power order.
+    pub fn delta(&self, live: &Snapshot) -> [Option<f64>; 4] {
+        fn nominal<M: Measurement>(m: &Option<M>) -> Option<f64> {
+            m.as_ref().map(|m| m.get_nominal_value())
+        }
+
+        [
+            delta_percent(nominal(&self.voltage), nominal(&live.voltage)),
+            delta_percent(nominal(&self.current), nominal(&live.current)),
+            delta_percent(nominal(&self.resistance), nominal(&live.resistance)),
+            delta_percent(nominal(&self.power), nominal(&live.power)),
+        ]
+    }
+
+    /// Result table rows: the pinned nominal values and the delta to `live`.
+    pub fn table_rows(&self, live: &Snapshot) -> [Vec<String>; 2] {
+        fn format_nom<M: Measurement>(m: &Option<M>) -> String {
+            m.as_ref()
+                .map(|m| m.get_value_nom())
+                .unwrap_or_else(|| "N/A".to_string())
+        }
+
+        let mut pinned = vec![t(Key::Pinned).to_string()];
+        pinned.push(format_nom(&self.voltage));
+        pinned.push(format_nom(&self.current));
+        pinned.push(format_nom(&self.resistance));
+        pinned.push(format_nom(&self.power));
+
+        let mut delta = vec![t(Key::Delta).to_string()];
+        delta.extend(self.delta(live).iter().map(|d| match d {
+            Some(d) => format!("{:+.2}%", d),
+            None => "N/A".to_string(),
+        }));
+
+        [pinned, delta]
+    }
+}
+
+/// Relative change from `pinned` to `live` in percent, `None` if either is missing or the
+/// pinned value is zero.
+fn delta_percent(pinned: Option<f64>, live: Option<f64>) -> Option<f64> {
+    let (pinned, live) = (pinned?, live?);
+    if pinned == 0.0 {
+        return None;
+    }
+
+    Some((live - pinned) / pinned.abs() * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(voltage: &str, current: &str, resistance: &str, power: &str) -> Snapshot {
+        Snapshot::capture(
+            &voltage.parse(),
+            &current.parse(),
+            &resistance.parse(),
+            &power.parse(),
+        )
+    }
+
+    #[test]
+    fn test_delta() {
+        let pinned = snapshot("10", "2m", "5k", "20m");
+        let live = snapshot("12", "2m", "4k", "");
+
+        let delta = pinned.delta(&live);
+        assert!((delta[0].unwrap() - 20.0).abs() < 1e-9);
+        assert_eq!(delta[1], Some(0.0));
+        assert!((delta[2].unwrap() + 20.0).abs() < 1e-9);
+        assert_eq!(delta[3], None);
+
+        // нулевое закреплённое значение
+        let pinned = snapshot("0", "-2m", "", "");
+        let live = snapshot("1", "-1m", "1k", "");
+        let delta = pinned.delta(&live);
+        assert_eq!(delta[0], None);
+        assert!((delta[1].unwrap() - 50.0).abs() < 1e-9);
+        assert_eq!(delta[2], None);
+    }
+
+    #[test]
+    fn test_table_rows() {
+        let pinned = snapshot("10", "", "", "");
+        let live = snapshot("11", "", "", "");
+
+        let [pinned, delta] = pinned.table_rows(&live);
+        assert_eq!(pinned[1], "10.00V");
+        assert_eq!(delta[1], "+10.00%");
+        assert_eq!(delta[2], "N/A");
+    }
+}
@@ -0,0 +1,16 @@
+//! # Scene Status
+//!
+//! A short summary of the state of a scene for the sidebar: a warning badge reminds of a
+//! calculation left with a parse error, a check mark shows a complete result.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneStatus {
+    /// Nothing entered yet.
+    Empty,
+    /// Some input, but not enough to calculate.
+    Incomplete,
+    /// At least one field does not parse.
+    Error,
+    /// All inputs are valid and the result is calculated.
+    Solved,
+}
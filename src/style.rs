@@ -0,0 +1,184 @@
+//! # Styles
+//!
+//! Semantic styles of the interface. Every color is derived from the extended palette of the
+//! active theme, so a view names the purpose of a color (a hint, a warning) and a palette
+//! change is made here only.
+
+use iced::widget::{container, text};
+use iced::{Border, Color, Theme};
+
+/// Style function of a text, e.g. [`hint_text`].
+pub type TextStyle = fn(&Theme) -> text::Style;
+
+const RULE_WIDTH: f32 = 1.0;
+
+pub fn sidebar_container(theme: &Theme) -> container::Style {
+    let pair = theme.extended_palette().background.strong;
+
+    container::Style::default()
+        .background(pair.color)
+        .color(pair.text)
+}
+
+pub fn content_container(theme: &Theme) -> container::Style {
+    let pair = theme.extended_palette().background.weak;
+
+    container::Style::default()
+        .background(pair.color)
+        .color(pair.text)
+}
+
+/// A dialog on top of the content, e.g. the exit confirmation.
+pub fn dialog_container(theme: &Theme) -> container::Style {
+    let pair = theme.extended_palette().background.base;
+
+    container::Style::default()
+        .background(pair.color)
+        .color(pair.text)
+}
+
+/// Shade over the content behind a dialog.
+pub fn backdrop_container(theme: &Theme) -> container::Style {
+    let palette = theme.extended_palette();
+    // затемнение: самый тёмный цвет палитры
+    let shade = if palette.is_dark {
+        palette.background.base.color
+    } else {
+        palette.background.base.text
+    };
+
+    container::Style::default().background(Color { a: 0.6, ..shade })
+}
+
+/// Secondary text: hints under the fields, examples, notes.
+pub fn hint_text(theme: &Theme) -> text::Style {
+    let background = theme.extended_palette().background.base;
+
+    text::Style {
+        color: Some(mix(background.color, background.text, 0.6)),
+    }
+}
+
+/// Something the user should look at: an implausible value, a current over the budget.
+pub fn warning_text(theme: &Theme) -> text::Style {
+    let palette = theme.extended_palette();
+
+    text::Style {
+        // сдвиг к цвету текста сохраняет читаемость и на тёмной теме
+        color: Some(mix(
+            palette.danger.base.color,
+            palette.background.base.text,
+            0.5,
+        )),
+    }
+}
+
+pub fn success_text(theme: &Theme) -> text::Style {
+    let palette = theme.extended_palette();
+
+    text::Style {
+        color: Some(mix(
+            palette.success.base.color,
+            palette.background.base.text,
+            0.5,
+        )),
+    }
+}
+
+/// Background of a load against its rating, e.g. the power of a resistor over its rated
+/// power: the success color at `0.0` turning into the danger color at `1.0` and above. Mixed
+/// halfway into the content background so the text on it stays readable.
+pub fn heat_color(ratio: f64, theme: &Theme) -> Color {
+    let palette = theme.extended_palette();
+    let share = if ratio.is_nan() {
+        0.0
+    } else {
+        ratio.clamp(0.0, 1.0) as f32
+    };
+    let heat = mix(palette.success.base.color, palette.danger.base.color, share);
+
+    mix(palette.background.weak.color, heat, 0.5)
+}
+
+/// Lines between the cells of a table.
+pub fn table_rule(theme: &Theme) -> Border {
+    Border {
+        color: theme.extended_palette().background.strong.color,
+        width: RULE_WIDTH,
+        radius: 0.0.into(),
+    }
+}
+
+/// Linear mix of two colors, `factor` is the share of `b`.
+fn mix(a: Color, b: Color, factor: f32) -> Color {
+    Color {
+        r: a.r + (b.r - a.r) * factor,
+        g: a.g + (b.g - a.g) * factor,
+        b: a.b + (b.b - a.b) * factor,
+        a: a.a + (b.a - a.a) * factor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Relative luminance of an sRGB color.
+    fn luminance(color: Color) -> f32 {
+        let [r, g, b, _] = color.into_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    fn assert_contrast(foreground: Color, background: Color, min: f32) {
+        let difference = (luminance(foreground) - luminance(background)).abs();
+        assert!(
+            difference >= min,
+            "{:?} on {:?}: {}",
+            foreground,
+            background,
+            difference
+        );
+    }
+
+    #[test]
+    fn test_contrast() {
+        for theme in [Theme::Light, Theme::Dark] {
+            for style in [sidebar_container, content_container, dialog_container] {
+                let style = style(&theme);
+                let Some(iced::Background::Color(background)) = style.background else {
+                    panic!("{:?}: no background", theme);
+                };
+                assert_contrast(style.text_color.unwrap(), background, 0.3);
+            }
+
+            // подсказки и предупреждения лежат на фоне содержимого
+            let content = theme.extended_palette().background.weak.color;
+            for style in [hint_text as TextStyle, warning_text, success_text] {
+                assert_contrast(style(&theme).color.unwrap(), content, 0.15);
+            }
+            assert_contrast(table_rule(&theme).color, content, 0.05);
+        }
+    }
+
+    #[test]
+    fn test_heat_color() {
+        for theme in [Theme::Light, Theme::Dark] {
+            let cold = heat_color(0.0, &theme);
+            let hot = heat_color(1.0, &theme);
+            assert_ne!(cold, hot);
+            // за пределами шкалы цвет не меняется
+            assert_eq!(heat_color(-0.5, &theme), cold);
+            assert_eq!(heat_color(3.0, &theme), hot);
+            assert_eq!(heat_color(f64::NAN, &theme), cold);
+
+            let half = heat_color(0.5, &theme);
+            assert!((half.r - (cold.r + hot.r) / 2.0).abs() < 1e-6);
+            assert!(hot.r > cold.r && hot.g < cold.g);
+
+            let text = theme.extended_palette().background.base.text;
+            for ratio in [0.0, 0.5, 1.0] {
+                assert_contrast(text, heat_color(ratio, &theme), 0.15);
+            }
+        }
+    }
+}
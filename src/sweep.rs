@@ -0,0 +1,154 @@
+//! # Sweep
+//!
+//! Points of a sweep over one input, given as a range `"1..10 step 1"` or as a list
+//! `"1, 2.2, 4.7"`. The numbers take the usual suffixes, e.g. `"1k..10k step 500"`. A range
+//! goes from the start towards the stop and never passes it: `"1..10 step 4"` is 1, 5 and 9.
+//! At most [`MAX_POINTS`] points are kept.
+
+use crate::i18n::{t, Key};
+use crate::parser::{self, Block};
+use crate::types::ParserError;
+
+/// Row cap of the sweep table.
+pub const MAX_POINTS: usize = 500;
+
+// запас на погрешность деления: 0.1..0.3 step 0.1 включает 0.3
+const STEP_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sweep {
+    pub points: Vec<f64>,
+    /// There are more than [`MAX_POINTS`] points, the rest are dropped.
+    pub truncated: bool,
+}
+
+pub fn parse_sweep(input: &str) -> Result<Sweep, ParserError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParserError::EmptyInput);
+    }
+
+    if let Some((start, rest)) = input.split_once("..") {
+        let Some((stop, step)) = rest.split_once("step") else {
+            return Err(incorrect(Key::SweepExpectedStep));
+        };
+
+        return range(number(start)?, number(stop)?, number(step)?);
+    }
+
+    let mut points = input
+        .split(',')
+        .map(number)
+        .collect::<Result<Vec<_>, _>>()?;
+    let truncated = points.len() > MAX_POINTS;
+    points.truncate(MAX_POINTS);
+
+    Ok(Sweep { points, truncated })
+}
+
+fn range(start: f64, stop: f64, step: f64) -> Result<Sweep, ParserError> {
+    if step <= 0.0 || !step.is_finite() {
+        return Err(incorrect(Key::SweepStepNotPositive));
+    }
+
+    // число шагов считается до выделения памяти: диапазон может быть огромным
+    let steps = ((stop - start).abs() / step + STEP_EPSILON).floor();
+    let truncated = steps >= MAX_POINTS as f64;
+    let count = if truncated {
+        MAX_POINTS
+    } else {
+        steps as usize + 1
+    };
+    let step = if stop < start { -step } else { step };
+
+    Ok(Sweep {
+        points: (0..count).map(|k| start + k as f64 * step).collect(),
+        truncated,
+    })
+}
+
+/// A single number with an optional suffix, e.g. "4.7k".
+fn number(input: &str) -> Result<f64, ParserError> {
+    match parser::parse_blocks(input.trim()).as_deref() {
+        Ok([Block::Number(n)]) => Ok(*n),
+        Ok([Block::NumberSuffix((n, s))]) => Ok(n * s.coefficient()),
+        Ok(_) => Err(incorrect(Key::SweepExpectedNumber)),
+        Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
+    }
+}
+
+fn incorrect(key: Key) -> ParserError {
+    ParserError::IncorrectInput(t(key).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(input: &str) -> Vec<f64> {
+        parse_sweep(input).unwrap().points
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(points("1..5 step 1"), [1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(points("1k..2k step 500"), [1000.0, 1500.0, 2000.0]);
+        // вниз от начала к концу
+        assert_eq!(points("10..4 step 3"), [10.0, 7.0, 4.0]);
+        assert_eq!(points("5..5 step 1"), [5.0]);
+
+        let points = points("0.1..0.3 step 0.1");
+        assert_eq!(points.len(), 3);
+        assert!((points[2] - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_range_stop_off_step() {
+        // the stop is not on a step boundary: the last point stays below it
+        assert_eq!(points("1..10 step 4"), [1.0, 5.0, 9.0]);
+        assert_eq!(points("1..2 step 5"), [1.0]);
+        assert_eq!(points("10..1 step 4"), [10.0, 6.0, 2.0]);
+    }
+
+    #[test]
+    fn test_list() {
+        assert_eq!(points("1, 2.2, 4.7k"), [1.0, 2.2, 4700.0]);
+        assert_eq!(points("12"), [12.0]);
+    }
+
+    #[test]
+    fn test_row_cap() {
+        let sweep = parse_sweep("1..500 step 1").unwrap();
+        assert_eq!(sweep.points.len(), MAX_POINTS);
+        assert!(!sweep.truncated);
+
+        let sweep = parse_sweep("1..501 step 1").unwrap();
+        assert_eq!(sweep.points.len(), MAX_POINTS);
+        assert_eq!(sweep.points.last(), Some(&500.0));
+        assert!(sweep.truncated);
+
+        // огромный диапазон не выделяет память под все точки
+        let sweep = parse_sweep("0..1T step 1p").unwrap();
+        assert_eq!(sweep.points.len(), MAX_POINTS);
+        assert!(sweep.truncated);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_sweep(" "), Err(ParserError::EmptyInput));
+        for input in [
+            "1..10",
+            "1..10 step 0",
+            "1..10 step -1",
+            "1..x step 1",
+            "1, , 2",
+            "1 5%",
+        ] {
+            assert!(
+                matches!(parse_sweep(input), Err(ParserError::IncorrectInput(_))),
+                "{}",
+                input
+            );
+        }
+    }
+}
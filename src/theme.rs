@@ -0,0 +1,76 @@
+//! # Interface Theme
+//!
+//! The theme is either chosen by hand or follows the dark/light preference of the OS. The
+//! preference is read on startup and again whenever the window gets focus, so a change made
+//! in the system settings is picked up when the user returns to the app.
+
+use std::fmt;
+
+use iced::Theme;
+
+use crate::i18n::{t, Key};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeChoice {
+    /// Follow the OS preference.
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+impl ThemeChoice {
+    pub const ALL: [ThemeChoice; 3] = [ThemeChoice::Auto, ThemeChoice::Light, ThemeChoice::Dark];
+
+    /// Theme to draw with; `system` is the theme of the OS preference.
+    pub fn resolve(&self, system: &Theme) -> Theme {
+        match self {
+            ThemeChoice::Auto => system.clone(),
+            ThemeChoice::Light => Theme::Light,
+            ThemeChoice::Dark => Theme::Dark,
+        }
+    }
+}
+
+impl fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeChoice::Auto => write!(f, "{}", t(Key::ThemeAuto)),
+            ThemeChoice::Light => write!(f, "{}", t(Key::ThemeLight)),
+            ThemeChoice::Dark => write!(f, "{}", t(Key::ThemeDark)),
+        }
+    }
+}
+
+/// Maps the OS preference to a theme. An unspecified preference is light.
+pub fn theme_for(mode: dark_light::Mode) -> Theme {
+    match mode {
+        dark_light::Mode::Dark => Theme::Dark,
+        dark_light::Mode::Light | dark_light::Mode::Default => Theme::Light,
+    }
+}
+
+/// Theme of the current OS preference.
+pub fn system_theme() -> Theme {
+    theme_for(dark_light::detect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_for() {
+        assert_eq!(theme_for(dark_light::Mode::Dark), Theme::Dark);
+        assert_eq!(theme_for(dark_light::Mode::Light), Theme::Light);
+        assert_eq!(theme_for(dark_light::Mode::Default), Theme::Light);
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(ThemeChoice::Auto.resolve(&Theme::Dark), Theme::Dark);
+        assert_eq!(ThemeChoice::Auto.resolve(&Theme::Light), Theme::Light);
+        assert_eq!(ThemeChoice::Light.resolve(&Theme::Dark), Theme::Light);
+        assert_eq!(ThemeChoice::Dark.resolve(&Theme::Light), Theme::Dark);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::types::{
+    compare,
+    interval::Interval,
+    resistance::Resistance,
+    tolerance_mode::{tolerance_mode, ToleranceMode},
+    Measurement, Tolerance,
+};
+use serde::Serialize;
+use std::cmp::Ordering;
+
+/// Conductance `G = 1 / R` in siemens. A derived value only, it is not entered.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Conductance {
+    pub value: f64,
+    pub tolerance: Option<Tolerance>,
+}
+
+impl Default for Conductance {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            tolerance: None,
+        }
+    }
+}
+
+impl Measurement for Conductance {
+    const NON_NEGATIVE: bool = true;
+
+    fn get_nominal_value(&self) -> f64 {
+        self.value
+    }
+
+    fn get_tolerance(&self) -> Option<Tolerance> {
+        self.tolerance
+    }
+
+    fn get_unit(&self) -> &'static str {
+        "S"
+    }
+}
+
+impl PartialOrd for Conductance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare(self, other)
+    }
+}
+
+impl Resistance {
+    /// Conductance of the resistance, `None` for zero or an infinite resistance. The maximum
+    /// conductance is at the minimum resistance, so the sides of the tolerance swap.
+    pub fn conductance(&self) -> Option<Conductance> {
+        if self.value == 0.0 || !self.value.is_finite() {
+            return None;
+        }
+
+        let value = 1.0 / self.value;
+        let tolerance = match (self.tolerance, tolerance_mode()) {
+            (None, _) => None,
+            // σ относительного отклонения в первом приближении не меняется
+            (Some(tol), ToleranceMode::Statistical { .. }) => Some(Tolerance {
+                plus: tol.minus,
+                minus: tol.plus,
+            }),
+            // при допуске от 100% интервал неограничен
+            (Some(_), ToleranceMode::Limits | ToleranceMode::Interval) => {
+                (Interval::new(1.0, 1.0) / Interval::of(self)).tolerance(value)
+            }
+        };
+
+        Some(Conductance { value, tolerance })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conductance() {
+        let conductance = "1k 5%"
+            .parse::<Resistance>()
+            .unwrap()
+            .conductance()
+            .unwrap();
+        assert_eq!(conductance.get_value_nom(), "1.00mS");
+        assert!((conductance.max_value() - 1.0 / 950.0).abs() < 1e-15);
+        assert!((conductance.min_value() - 1.0 / 1050.0).abs() < 1e-15);
+
+        let conductance = "100".parse::<Resistance>().unwrap().conductance().unwrap();
+        assert_eq!(conductance.get_tolerance(), None);
+        assert_eq!(conductance.get_value_nom(), "10.00mS");
+
+        assert_eq!("0".parse::<Resistance>().unwrap().conductance(), None);
+        let wide = "1k 100%"
+            .parse::<Resistance>()
+            .unwrap()
+            .conductance()
+            .unwrap();
+        assert_eq!(wide.get_tolerance(), None);
+    }
+}
@@ -1,11 +1,16 @@
 use crate::types::{
-    calculate_multiplication_with_tolerance, resistance::Resistance, voltage::Voltage, Measurement,
-    ParserError, Tolerance,
+    calculate_addition_with_tolerance, calculate_multiplication_with_tolerance, compare,
+    parse_value, resistance::Resistance, voltage::Voltage, Measurement, ParserError, Tolerance,
+};
+use serde::Serialize;
+use std::{
+    cmp::Ordering,
+    iter::Sum,
+    ops::{Add, Mul, Neg},
+    str::FromStr,
 };
-use crate::{parser, parser::Block};
-use std::{ops::Mul, str::FromStr};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Current {
     pub value: f64,
     pub tolerance: Option<Tolerance>,
@@ -21,6 +26,8 @@ impl Default for Current {
 }
 
 impl Measurement for Current {
+    const NON_NEGATIVE: bool = false;
+
     fn get_nominal_value(&self) -> f64 {
         self.value
     }
@@ -34,72 +41,40 @@ impl Measurement for Current {
     }
 }
 
+impl PartialOrd for Current {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare(self, other)
+    }
+}
+
 impl FromStr for Current {
     type Err = ParserError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let input = input.trim();
-        if input.trim().is_empty() {
-            return Err(ParserError::EmptyInput);
-        }
+        parse_value(input, |value, tolerance| Current { value, tolerance })
+    }
+}
+
+impl Add for Current {
+    type Output = Current;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let result = calculate_addition_with_tolerance(&self, &rhs);
 
-        match parser::parse_blocks(input) {
-            Ok((input, result)) => {
-                // If there is any remaining unparsed input, it's an error
-                if !input.is_empty() {
-                    return Err(ParserError::IncorrectInput(input.to_string()));
-                }
-
-                let mut value = f64::NAN;
-                let mut tol: Option<Tolerance> = None;
-
-                // Process each parsed block
-                for block in result {
-                    match block {
-                        Block::Number(n) => value = n,
-                        Block::NumberSuffix((n, s)) => value = n * s.coefficient(),
-                        Block::TolMinus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: tt.plus,
-                                    minus: t,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: 0.0,
-                                    minus: t,
-                                })
-                            };
-                        }
-                        Block::TolPlus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: tt.minus,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: 0.0,
-                                })
-                            };
-                        }
-                        Block::TolPlusMinus(t) => {
-                            tol = Some(Tolerance { plus: t, minus: t });
-                        }
-                    }
-                }
-
-                Ok(Current {
-                    value,
-                    tolerance: tol,
-                })
-            }
-            Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
+        Current {
+            value: result.0,
+            tolerance: result.1,
         }
     }
 }
 
+impl Sum for Current {
+    /// Currents of parallel branches added up, zero without tolerance for none.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(Add::add).unwrap_or_default()
+    }
+}
+
 impl Mul<Resistance> for Current {
     type Output = Voltage;
 
@@ -107,8 +82,59 @@ impl Mul<Resistance> for Current {
         let (value, tol) = calculate_multiplication_with_tolerance(&self, &rhs);
 
         Voltage {
-            value: value,
+            value,
             tolerance: tol,
         }
     }
 }
+
+impl Neg for Current {
+    type Output = Current;
+
+    fn neg(self) -> Self::Output {
+        Current {
+            value: -self.value,
+            tolerance: self.tolerance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_neg() {
+        let current = Current {
+            value: 2e-3,
+            tolerance: Some(Tolerance {
+                plus: 5.0,
+                minus: 1.0,
+            }),
+        };
+        let reverse = -current;
+
+        assert_eq!(reverse.value, -2e-3);
+        assert_eq!(reverse.tolerance, current.tolerance);
+        assert_eq!(reverse.get_value_nom(), "-2.00mA");
+        assert_eq!(reverse.get_tol_value_plus(), "100.00uA");
+        assert_eq!(reverse.get_tol_value_minus(), "-20.00uA");
+        assert_eq!((-reverse).value, 2e-3);
+    }
+
+    #[test]
+    fn test_current_sum() {
+        assert_eq!(
+            Vec::<Current>::new().into_iter().sum::<Current>(),
+            Current::default()
+        );
+
+        let branches = ["10m 5%", "30m 1%"].map(|c| c.parse::<Current>().unwrap());
+        let total = branches.iter().copied().sum::<Current>();
+        assert!((total.value - 40e-3).abs() < 1e-15);
+        // 0.5mA + 0.3mA из 40mA
+        let tol = total.tolerance.unwrap();
+        assert!((tol.plus - 2.0).abs() < 1e-9);
+        assert!((tol.minus - 2.0).abs() < 1e-9);
+    }
+}
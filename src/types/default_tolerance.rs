@@ -0,0 +1,164 @@
+//! # Default Tolerances
+//!
+//! A value entered without a tolerance, e.g. `10k`, can get a default one per quantity, e.g.
+//! ±1% for every resistance. An explicit tolerance, `0%` included, is always kept. The
+//! defaults are applied while parsing, so every scene behaves the same. All defaults are off
+//! unless set in the settings.
+//!
+//! Like the interface language, the defaults are kept per thread.
+
+use std::cell::Cell;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::{Measurement, Tolerance};
+use crate::i18n::{t, Key};
+
+thread_local! {
+    static DEFAULTS: Cell<DefaultTolerances> = const { Cell::new(DefaultTolerances::OFF) };
+}
+
+/// Symmetrical default tolerance of every quantity in percent, `None` if off.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DefaultTolerances {
+    pub voltage: Option<f64>,
+    pub current: Option<f64>,
+    pub resistance: Option<f64>,
+    pub power: Option<f64>,
+}
+
+impl DefaultTolerances {
+    pub const OFF: DefaultTolerances = DefaultTolerances {
+        voltage: None,
+        current: None,
+        resistance: None,
+        power: None,
+    };
+
+    /// Default of the quantity with the unit of [`Measurement::get_unit`].
+    pub fn for_unit(&self, unit: &str) -> Option<f64> {
+        match unit {
+            "V" => self.voltage,
+            "A" => self.current,
+            "R" => self.resistance,
+            "W" => self.power,
+            _ => None,
+        }
+    }
+}
+
+/// Sets the default tolerances for the current thread.
+pub fn set_default_tolerances(defaults: DefaultTolerances) {
+    DEFAULTS.with(|d| d.set(defaults));
+}
+
+/// Returns the default tolerances of the current thread.
+pub fn default_tolerances() -> DefaultTolerances {
+    DEFAULTS.with(|d| d.get())
+}
+
+/// Tolerance of a parsed value: its own one, or the default of its quantity if it has none.
+pub fn apply(value: &impl Measurement) -> Option<Tolerance> {
+    value.get_tolerance().or_else(|| {
+        let percent = default_tolerances().for_unit(value.get_unit())?;
+        Some(Tolerance {
+            plus: percent,
+            minus: percent,
+        })
+    })
+}
+
+/// The default tolerance in percent that parsing `input` as `M` assumes, `None` if the input
+/// has its own tolerance, does not parse or there is no default.
+pub fn assumed<M>(input: &str) -> Option<f64>
+where
+    M: Measurement + FromStr,
+{
+    let defaults = default_tolerances();
+
+    set_default_tolerances(DefaultTolerances::OFF);
+    let parsed = input.parse::<M>();
+    set_default_tolerances(defaults);
+
+    match parsed {
+        Ok(value) if value.get_tolerance().is_none() => defaults.for_unit(value.get_unit()),
+        _ => None,
+    }
+}
+
+/// Suffix of the input hint that shows the assumed default tolerance, e.g. " (assumed ±1%)".
+/// Empty if no default is assumed.
+pub fn assumed_hint<M>(input: &str) -> String
+where
+    M: Measurement + FromStr,
+{
+    match assumed::<M>(input) {
+        Some(percent) => format!(" ({} ±{}%)", t(Key::AssumedTolerance), percent),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{resistance::Resistance, voltage::Voltage};
+
+    const DEFAULTS: DefaultTolerances = DefaultTolerances {
+        resistance: Some(1.0),
+        ..DefaultTolerances::OFF
+    };
+
+    #[test]
+    fn test_apply_default() {
+        let r = |s: &str| s.parse::<Resistance>().unwrap().get_tolerance();
+
+        assert_eq!(r("10k"), None);
+
+        set_default_tolerances(DEFAULTS);
+        assert_eq!(
+            r("10k"),
+            Some(Tolerance {
+                plus: 1.0,
+                minus: 1.0
+            })
+        );
+        // явный допуск, в том числе 0%, не заменяется
+        assert_eq!(
+            r("10k 0%"),
+            Some(Tolerance {
+                plus: 0.0,
+                minus: 0.0
+            })
+        );
+        assert_eq!(
+            r("10k +5%"),
+            Some(Tolerance {
+                plus: 5.0,
+                minus: 0.0
+            })
+        );
+        // every resistor of a network gets the default
+        let network = "10k || 10k".parse::<Resistance>().unwrap();
+        assert_eq!(network.get_value_max(), "5.05kR");
+        // other quantities have no default
+        assert_eq!("12".parse::<Voltage>().unwrap().get_tolerance(), None);
+
+        set_default_tolerances(DefaultTolerances::OFF);
+    }
+
+    #[test]
+    fn test_assumed() {
+        set_default_tolerances(DEFAULTS);
+
+        assert_eq!(assumed::<Resistance>("10k"), Some(1.0));
+        assert_eq!(assumed_hint::<Resistance>("10k"), " (assumed ±1%)");
+        assert_eq!(assumed::<Resistance>("10k 0%"), None);
+        assert_eq!(assumed::<Resistance>("10x"), None);
+        assert_eq!(assumed_hint::<Voltage>("12"), "");
+        // the defaults are restored after the check
+        assert_eq!(default_tolerances(), DEFAULTS);
+
+        set_default_tolerances(DefaultTolerances::OFF);
+    }
+}
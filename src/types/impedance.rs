@@ -0,0 +1,164 @@
+use crate::types::{resistance::Resistance, Measurement, Tolerance};
+use serde::Serialize;
+use std::ops::Add;
+
+/// Complex impedance `R + jX` in ohms. Unlike a [`Resistance`], a reactance adds to it at a
+/// right angle, so a series or parallel combination needs the complex arithmetic. As a
+/// [`Measurement`] its value is the magnitude; the tolerance is not tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct Impedance {
+    pub resistance: f64,
+    pub reactance: f64,
+}
+
+impl Impedance {
+    pub fn new(resistance: f64, reactance: f64) -> Self {
+        Self {
+            resistance,
+            reactance,
+        }
+    }
+
+    /// A pure reactance `jX`: positive for an inductor, negative for a capacitor.
+    pub fn reactance(reactance: f64) -> Self {
+        Self::new(0.0, reactance)
+    }
+
+    /// Impedance of the given magnitude and phase in degrees.
+    pub fn from_polar(magnitude: f64, phase: f64) -> Self {
+        let phase = phase.to_radians();
+
+        Self::new(magnitude * phase.cos(), magnitude * phase.sin())
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.resistance.hypot(self.reactance)
+    }
+
+    /// Phase in degrees, from -180 to 180.
+    pub fn phase(&self) -> f64 {
+        self.reactance.atan2(self.resistance).to_degrees()
+    }
+
+    /// Parallel combination `Z1 * Z2 / (Z1 + Z2)`, `None` if the sum is zero, e.g. for an
+    /// inductor and a capacitor at resonance.
+    pub fn parallel(&self, other: &Impedance) -> Option<Impedance> {
+        let sum = *self + *other;
+        let denominator = sum.resistance.powi(2) + sum.reactance.powi(2);
+        if denominator == 0.0 {
+            return None;
+        }
+
+        // произведение, умноженное на сопряжённую сумму
+        let re = self.resistance * other.resistance - self.reactance * other.reactance;
+        let im = self.resistance * other.reactance + self.reactance * other.resistance;
+
+        Some(Impedance::new(
+            (re * sum.resistance + im * sum.reactance) / denominator,
+            (im * sum.resistance - re * sum.reactance) / denominator,
+        ))
+    }
+}
+
+impl From<Resistance> for Impedance {
+    fn from(resistance: Resistance) -> Self {
+        Impedance::new(resistance.value, 0.0)
+    }
+}
+
+impl Measurement for Impedance {
+    const NON_NEGATIVE: bool = true;
+
+    fn get_nominal_value(&self) -> f64 {
+        self.magnitude()
+    }
+
+    fn get_tolerance(&self) -> Option<Tolerance> {
+        None
+    }
+
+    fn get_unit(&self) -> &'static str {
+        "R"
+    }
+
+    /// Magnitude and phase, e.g. "1.41kR∠45.0°".
+    fn get_value_nom(&self) -> String {
+        format!("{}∠{:.1}°", self.normalize(self.magnitude()), self.phase())
+    }
+}
+
+/// Series connection.
+impl Add for Impedance {
+    type Output = Impedance;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Impedance::new(
+            self.resistance + rhs.resistance,
+            self.reactance + rhs.reactance,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Impedance, expected: Impedance) {
+        assert!(
+            (actual.resistance - expected.resistance).abs() < 1e-9
+                && (actual.reactance - expected.reactance).abs() < 1e-9,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_series() {
+        let r: Impedance = "1k".parse::<Resistance>().unwrap().into();
+        let z = r + Impedance::reactance(1000.0);
+
+        assert_close(z, Impedance::new(1000.0, 1000.0));
+        assert!((z.magnitude() - 1000.0 * 2f64.sqrt()).abs() < 1e-9);
+        assert!((z.phase() - 45.0).abs() < 1e-9);
+        assert_eq!(z.get_value_nom(), "1.41kR∠45.0°");
+
+        // L и C в последовательном резонансе компенсируют друг друга
+        let lc = Impedance::reactance(50.0) + Impedance::reactance(-50.0);
+        assert_close(lc, Impedance::default());
+    }
+
+    #[test]
+    fn test_parallel() {
+        // 100 || j100 = (100·j100) / (100 + j100) = 50 + j50
+        let z = Impedance::new(100.0, 0.0)
+            .parallel(&Impedance::reactance(100.0))
+            .unwrap();
+        assert_close(z, Impedance::new(50.0, 50.0));
+
+        // (3 + j4) || (3 - j4) = 25 / 6
+        let z = Impedance::new(3.0, 4.0)
+            .parallel(&Impedance::new(3.0, -4.0))
+            .unwrap();
+        assert_close(z, Impedance::new(25.0 / 6.0, 0.0));
+
+        // резистивный случай совпадает с Resistance::parallel
+        let z = Impedance::new(1000.0, 0.0)
+            .parallel(&Impedance::new(1000.0, 0.0))
+            .unwrap();
+        assert_close(z, Impedance::new(500.0, 0.0));
+
+        // параллельный резонанс: сумма равна нулю
+        assert_eq!(
+            Impedance::reactance(50.0).parallel(&Impedance::reactance(-50.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_polar() {
+        let z = Impedance::from_polar(10.0, -90.0);
+        assert_close(z, Impedance::new(0.0, -10.0));
+        assert_eq!(z.get_value_nom(), "10.00R∠-90.0°");
+    }
+}
@@ -0,0 +1,171 @@
+//! # Interval Arithmetic
+//!
+//! A tolerance band as a closed interval `[min, max]`. Unlike the sum of percentages, the
+//! operations give the true extremes of the result, e.g. `10 1%` × `2 1%` is
+//! `[19.602, 20.402]`, that is +2.01% / −1.99%. Used by the calculations in the interval
+//! tolerance mode.
+
+use std::ops::{Add, Div, Mul, Sub};
+
+use super::{sorted, Measurement, Tolerance};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Interval {
+    /// Interval between two bounds in any order.
+    pub fn new(a: f64, b: f64) -> Self {
+        let (min, max) = sorted(a, b);
+
+        Self { min, max }
+    }
+
+    /// Tolerance band of a measurement. The band is taken as entered, without the coverage
+    /// of the statistical mode.
+    pub fn of(value: &impl Measurement) -> Self {
+        let nominal = value.get_nominal_value();
+
+        match value.get_tolerance() {
+            Some(tol) => Self::new(
+                nominal * (1.0 - tol.minus / 100.0),
+                nominal * (1.0 + tol.plus / 100.0),
+            ),
+            None => Self::new(nominal, nominal),
+        }
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    /// The interval as a tolerance around `nominal`. `None` if it cannot be written so: the
+    /// nominal value is zero or the interval is unbounded.
+    pub fn tolerance(&self, nominal: f64) -> Option<Tolerance> {
+        if nominal == 0.0 || !self.min.is_finite() || !self.max.is_finite() {
+            return None;
+        }
+
+        // у отрицательного значения плюс увеличивает модуль
+        let (far, near) = if nominal > 0.0 {
+            (self.max, self.min)
+        } else {
+            (self.min, self.max)
+        };
+
+        Some(Tolerance {
+            plus: (far / nominal - 1.0) * 100.0,
+            minus: (1.0 - near / nominal) * 100.0,
+        })
+    }
+
+    fn from_candidates(candidates: [f64; 4]) -> Self {
+        let min = candidates.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = candidates.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Self { min, max }
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Interval {
+            min: self.min + rhs.min,
+            max: self.max + rhs.max,
+        }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Interval {
+            min: self.min - rhs.max,
+            max: self.max - rhs.min,
+        }
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Interval::from_candidates([
+            self.min * rhs.min,
+            self.min * rhs.max,
+            self.max * rhs.min,
+            self.max * rhs.max,
+        ])
+    }
+}
+
+impl Div for Interval {
+    type Output = Interval;
+
+    /// A divisor that contains zero gives an unbounded interval.
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs.contains(0.0) {
+            return Interval {
+                min: f64::NEG_INFINITY,
+                max: f64::INFINITY,
+            };
+        }
+
+        Interval::from_candidates([
+            self.min / rhs.min,
+            self.min / rhs.max,
+            self.max / rhs.min,
+            self.max / rhs.max,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_interval(interval: Interval, min: f64, max: f64) {
+        assert!((interval.min - min).abs() < 1e-12, "{:?}", interval);
+        assert!((interval.max - max).abs() < 1e-12, "{:?}", interval);
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = Interval::new(9.9, 10.1);
+        let b = Interval::new(-2.0, 1.0);
+
+        assert_interval(a + b, 7.9, 11.1);
+        assert_interval(a - b, 8.9, 12.1);
+        assert_interval(a * b, -20.2, 10.1);
+        assert_interval(b / a, -2.0 / 9.9, 1.0 / 9.9);
+        assert_eq!(
+            a / b,
+            Interval {
+                min: f64::NEG_INFINITY,
+                max: f64::INFINITY
+            }
+        );
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let tol = Interval::new(19.602, 20.402).tolerance(20.0).unwrap();
+        assert!((tol.plus - 2.01).abs() < 1e-9);
+        assert!((tol.minus - 1.99).abs() < 1e-9);
+
+        let tol = Interval::new(-10.5, -9.8).tolerance(-10.0).unwrap();
+        assert!((tol.plus - 5.0).abs() < 1e-9);
+        assert!((tol.minus - 2.0).abs() < 1e-9);
+
+        assert_eq!(Interval::new(-1.0, 1.0).tolerance(0.0), None);
+        assert_eq!(
+            Interval::new(f64::NEG_INFINITY, f64::INFINITY).tolerance(1.0),
+            None
+        );
+    }
+}
@@ -1,21 +1,261 @@
+//! # Quantities With Tolerances
+//!
+//! Voltage, current, resistance and power carry a nominal value and an optional tolerance.
+//! The arithmetic between them is limited to the dimensionally valid operations, so a wrong
+//! formula does not compile:
+//!
+//! | Operation                   | Result     |
+//! |-----------------------------|------------|
+//! | `Voltage ± Voltage`         | Voltage    |
+//! | `Voltage / Current`         | Resistance |
+//! | `Voltage / Resistance`      | Current    |
+//! | `Voltage * Current`         | Power      |
+//! | `Voltage * Ratio`           | Voltage    |
+//! | `Current * Resistance`      | Voltage    |
+//! | `Resistance * Current`      | Voltage    |
+//! | `Resistance + Resistance`   | Resistance |
+//! | `Resistance * f64`          | Resistance |
+//! | `Power + Power`             | Power      |
+//! | `Power / Voltage`           | Current    |
+//! | `Power / Current`           | Voltage    |
+//! | `Impedance + Impedance`     | Impedance  |
+//! | `-Quantity`                 | Quantity   |
+//!
+//! Every operation is covered by `tests/ohm_algebra.rs`.
+
+pub mod conductance;
 pub mod current;
+pub mod default_tolerance;
+pub mod impedance;
+pub mod interval;
 pub mod power;
+pub mod ratio;
 pub mod resistance;
+pub mod tolerance_mode;
 pub mod voltage;
 
-#[derive(Debug, Clone, PartialEq)]
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::i18n::{t, Key};
+use crate::parser::{self, Block};
+use interval::Interval;
+use tolerance_mode::{tolerance_mode, ToleranceMode};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ParserError {
     EmptyInput,
     IncorrectInput(String),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Tolerance {
     pub plus: f64,
     pub minus: f64,
 }
 
-#[derive(Debug, PartialEq)]
+/// Parses a value with an optional tolerance as any quantity, the generic form of
+/// `str::parse`:
+///
+/// ```rust
+/// use ecw::types::{parse, resistance::Resistance, voltage::Voltage, Measurement};
+///
+/// let voltage: Voltage = parse("12k 5%").unwrap();
+/// assert_eq!(voltage.get_value_nom(), "12.00kV");
+///
+/// let resistance = parse::<Resistance>("10k || 10k").unwrap();
+/// assert_eq!(resistance.get_nominal_value(), 5000.0);
+/// ```
+pub fn parse<M>(input: &str) -> Result<M, ParserError>
+where
+    M: Measurement + FromStr<Err = ParserError>,
+{
+    input.parse::<M>()
+}
+
+/// Shared body of `FromStr` of the quantities: parses a plain value like "4.7k +5% -3%" and
+/// builds the quantity with `build`. The default tolerance is applied here.
+pub(crate) fn parse_value<M: Measurement>(
+    input: &str,
+    build: impl Fn(f64, Option<Tolerance>) -> M,
+) -> Result<M, ParserError> {
+    parser::check_length(input).map_err(|e| ParserError::IncorrectInput(e.to_string()))?;
+    let input = parser::normalize_signs(input);
+    let input =
+        parser::strip_grouping(&input).map_err(|e| ParserError::IncorrectInput(e.to_string()))?;
+    let (input, _) = parser::strip_units(&input);
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParserError::EmptyInput);
+    }
+
+    match parser::parse_blocks(input) {
+        Ok(blocks) => from_blocks(blocks, build),
+        Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
+    }
+}
+
+/// Builds a quantity from the parsed blocks of a plain value and applies the default
+/// tolerance if there is no explicit one. An absolute tolerance is made relative to the
+/// nominal value, so a zero nominal with one is an error. A standard uncertainty is an error
+/// outside the statistical mode, where it would be taken for a worst-case limit.
+pub(crate) fn from_blocks<M: Measurement>(
+    blocks: Vec<Block>,
+    build: impl Fn(f64, Option<Tolerance>) -> M,
+) -> Result<M, ParserError> {
+    // номинал - последнее число, допуски применяются по порядку
+    let value = blocks
+        .iter()
+        .rev()
+        .find_map(|block| match block {
+            Block::Number(n) => Some(*n),
+            Block::NumberSuffix((n, s)) => Some(n * s.coefficient()),
+            _ => None,
+        })
+        .unwrap_or(f64::NAN);
+    let mut tol: Option<Tolerance> = None;
+
+    for block in blocks {
+        match block {
+            Block::Number(_) | Block::NumberSuffix(_) => {}
+            Block::AbsTolPlusMinus(_) | Block::StdDev(_) if value == 0.0 => {
+                return Err(ParserError::IncorrectInput(
+                    "an absolute tolerance needs a nominal value other than zero".to_string(),
+                ));
+            }
+            Block::StdDev(_) if !matches!(tolerance_mode(), ToleranceMode::Statistical { .. }) => {
+                return Err(ParserError::IncorrectInput(
+                    "a standard uncertainty needs the statistical tolerance mode".to_string(),
+                ));
+            }
+            // в статистическом режиме допуск и есть σ
+            Block::AbsTolPlusMinus(a) | Block::StdDev(a) => {
+                let t = a / value.abs() * 100.0;
+                tol = Some(Tolerance { plus: t, minus: t });
+            }
+            Block::TolMinus(t) => {
+                tol = Some(Tolerance {
+                    plus: tol.map_or(0.0, |tol| tol.plus),
+                    minus: t,
+                });
+            }
+            Block::TolPlus(t) => {
+                tol = Some(Tolerance {
+                    plus: t,
+                    minus: tol.map_or(0.0, |tol| tol.minus),
+                });
+            }
+            Block::TolPlusMinus(t) => {
+                tol = Some(Tolerance { plus: t, minus: t });
+            }
+        }
+    }
+
+    let parsed = build(value, tol);
+    let tolerance = default_tolerance::apply(&parsed);
+
+    Ok(build(value, tolerance))
+}
+
+/// Side of a tolerance band.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    Plus,
+    Minus,
+}
+
+impl Bound {
+    pub fn opposite(self) -> Bound {
+        match self {
+            Bound::Plus => Bound::Minus,
+            Bound::Minus => Bound::Plus,
+        }
+    }
+}
+
+/// Order of two quantities for `PartialOrd`: by the nominal value, then by the tolerance
+/// (none first), so only equal quantities compare as equal. `None` if either has a NaN.
+pub(crate) fn compare(a: &impl Measurement, b: &impl Measurement) -> Option<Ordering> {
+    fn tolerance(m: &impl Measurement) -> Option<(f64, f64)> {
+        m.get_tolerance().map(|tol| (tol.plus, tol.minus))
+    }
+
+    match a.get_nominal_value().partial_cmp(&b.get_nominal_value())? {
+        Ordering::Equal => tolerance(a).partial_cmp(&tolerance(b)),
+        ordering => Some(ordering),
+    }
+}
+
+fn sorted(a: f64, b: f64) -> (f64, f64) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Records which input tolerance bounds produce each side of a calculated tolerance.
+///
+/// For example `I = U / R` reaches its maximum with `U` at its plus bound and `R` at its
+/// minus bound, so `plus` holds `[("Voltage", Plus), ("Resistance", Minus)]`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ToleranceProvenance {
+    pub plus: Vec<(&'static str, Bound)>,
+    pub minus: Vec<(&'static str, Bound)>,
+}
+
+impl ToleranceProvenance {
+    /// Provenance of an entered value: its own bounds.
+    pub fn input(name: &'static str) -> Self {
+        Self {
+            plus: vec![(name, Bound::Plus)],
+            minus: vec![(name, Bound::Minus)],
+        }
+    }
+
+    /// Provenance of `self * other`, mirrors `calculate_multiplication_with_tolerance`.
+    pub fn multiplication(&self, other: &Self) -> Self {
+        Self {
+            plus: Self::merge(&self.plus, &other.plus),
+            minus: Self::merge(&self.minus, &other.minus),
+        }
+    }
+
+    /// Provenance of `self / other`, mirrors `calculate_division_with_tolerance`.
+    pub fn division(&self, other: &Self) -> Self {
+        Self {
+            plus: Self::merge(&self.plus, &other.minus),
+            minus: Self::merge(&self.minus, &other.plus),
+        }
+    }
+
+    /// Contributors of the requested side of the band.
+    pub fn side(&self, bound: Bound) -> &[(&'static str, Bound)] {
+        match bound {
+            Bound::Plus => &self.plus,
+            Bound::Minus => &self.minus,
+        }
+    }
+
+    fn merge(
+        a: &[(&'static str, Bound)],
+        b: &[(&'static str, Bound)],
+    ) -> Vec<(&'static str, Bound)> {
+        let mut result = a.to_vec();
+        for item in b {
+            if !result.contains(item) {
+                result.push(*item);
+            }
+        }
+
+        result
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Dim {
     Pico,
     Nano,
@@ -59,9 +299,87 @@ impl Dim {
             Dim::Tera => 1e12,
         }
     }
+
+    /// The prefixes a value can be entered with, from the smallest.
+    pub const PREFIXES: [Dim; 8] = [
+        Dim::Pico,
+        Dim::Nano,
+        Dim::Micro,
+        Dim::Milli,
+        Dim::Kilo,
+        Dim::Mega,
+        Dim::Giga,
+        Dim::Tera,
+    ];
+
+    /// The letter of the prefix in an input, e.g. `u` for micro. Empty for [`Dim::None`].
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Dim::Pico => "p",
+            Dim::Nano => "n",
+            Dim::Micro => "u",
+            Dim::Milli => "m",
+            Dim::None => "",
+            Dim::Kilo => "k",
+            Dim::Mega => "M",
+            Dim::Giga => "G",
+            Dim::Tera => "T",
+        }
+    }
+
+    /// Sets the prefix of the nominal value of an input: `"10 5%"` with kilo becomes
+    /// `"10k 5%"`, a prefix already there is replaced, so `"10m"` with mega becomes `"10M"`.
+    /// An input without a number is returned as is.
+    pub fn append_to(&self, input: &str) -> String {
+        // номинал - первое слово, заканчивающееся цифрой или приставкой после цифры
+        let number = input.split_whitespace().find_map(|word| {
+            let digits = word.trim_end_matches(|c| Dim::from(c) != Dim::None);
+            let prefixed = word.len() - digits.len() <= 1;
+            let ends_with_digit = digits.ends_with(|c: char| c.is_ascii_digit());
+
+            (prefixed && ends_with_digit).then_some((word, digits))
+        });
+        let Some((word, digits)) = number else {
+            return input.to_string();
+        };
+
+        // слово - срез input, поэтому его смещение известно
+        let start = word.as_ptr() as usize - input.as_ptr() as usize;
+        format!(
+            "{}{}{}{}",
+            &input[..start],
+            digits,
+            self.symbol(),
+            &input[start + word.len()..]
+        )
+    }
+}
+
+impl fmt::Display for Dim {
+    /// The letter, the name and the power of ten, e.g. "M — mega, 10⁶": the letter case
+    /// alone is easy to confuse.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (key, power) = match self {
+            Dim::Pico => (Key::PrefixPico, "10⁻¹²"),
+            Dim::Nano => (Key::PrefixNano, "10⁻⁹"),
+            Dim::Micro => (Key::PrefixMicro, "10⁻⁶"),
+            Dim::Milli => (Key::PrefixMilli, "10⁻³"),
+            Dim::None => return Ok(()),
+            Dim::Kilo => (Key::PrefixKilo, "10³"),
+            Dim::Mega => (Key::PrefixMega, "10⁶"),
+            Dim::Giga => (Key::PrefixGiga, "10⁹"),
+            Dim::Tera => (Key::PrefixTera, "10¹²"),
+        };
+
+        write!(f, "{} — {}, {}", self.symbol(), t(key), power)
+    }
 }
 
 pub trait Measurement {
+    /// The quantity cannot be negative, e.g. a resistance: its minimum is shown clamped at
+    /// zero when the minus tolerance exceeds 100%.
+    const NON_NEGATIVE: bool = false;
+
     fn get_nominal_value(&self) -> f64;
     fn get_tolerance(&self) -> Option<Tolerance>;
     fn get_unit(&self) -> &'static str;
@@ -90,25 +408,86 @@ pub trait Measurement {
         format!("{}", value)
     }
 
+    /// Numerically lower and upper ends of the tolerance band, both the nominal value if
+    /// there is no tolerance. In the statistical mode the band is scaled to the chosen
+    /// coverage. The plus side of a negative value is its lower end: `-10 +5%` reaches -10.5.
+    fn band(&self) -> (f64, f64) {
+        let nominal = self.get_nominal_value();
+
+        match self.get_tolerance() {
+            Some(tol) => {
+                let k = tolerance_mode().coverage();
+                sorted(
+                    nominal * (100.0 - k * tol.minus) / 100.0,
+                    nominal * (100.0 + k * tol.plus) / 100.0,
+                )
+            }
+            None => (nominal, nominal),
+        }
+    }
+
+    /// Lower bound of the tolerance band, never above [`Measurement::max_value`].
+    fn min_value(&self) -> f64 {
+        self.band().0
+    }
+
+    /// Upper bound of the tolerance band.
+    fn max_value(&self) -> f64 {
+        self.band().1
+    }
+
+    /// Returns `true` if the nominal value of `self` lies within the tolerance band of `other`,
+    /// e.g. whether a measured value meets a specification.
+    fn within(&self, other: &impl Measurement) -> bool {
+        let value = self.get_nominal_value();
+
+        other.min_value() <= value && value <= other.max_value()
+    }
+
+    /// Returns `true` if the nominal values differ by at most `abs_eps`, or by at most
+    /// `rel_eps` of the larger magnitude. `abs_eps` covers values around zero, where a
+    /// relative comparison never succeeds.
+    fn approx_eq(&self, other: &impl Measurement, rel_eps: f64, abs_eps: f64) -> bool {
+        let a = self.get_nominal_value();
+        let b = other.get_nominal_value();
+        let diff = (a - b).abs();
+
+        diff <= abs_eps || diff <= rel_eps * a.abs().max(b.abs())
+    }
+
+    /// Returns `true` if the tolerance bands of `self` and `other` intersect, touching bounds
+    /// included. A value without tolerance is a band of zero width.
+    fn overlaps(&self, other: &impl Measurement) -> bool {
+        let (a_low, a_high) = self.band();
+        let (b_low, b_high) = other.band();
+
+        a_low <= b_high && b_low <= a_high
+    }
+
     fn get_value_nom(&self) -> String {
         let value = self.get_nominal_value();
 
         self.normalize(value)
     }
 
+    /// `true` if the lower bound of a non-negative quantity would be below zero.
+    fn is_min_clamped(&self) -> bool {
+        Self::NON_NEGATIVE && self.get_nominal_value() >= 0.0 && self.min_value() < 0.0
+    }
+
     fn get_value_min(&self) -> String {
-        if let Some(tol) = self.get_tolerance() {
-            let min = self.get_nominal_value() * (100.0 - tol.minus) / 100.0;
-            self.normalize(min)
+        if self.is_min_clamped() {
+            format!("0.00{} ({})", self.get_unit(), t(Key::ClampedAtZero))
+        } else if self.get_tolerance().is_some() {
+            self.normalize(self.min_value())
         } else {
             "N/A".to_string()
         }
     }
 
     fn get_value_max(&self) -> String {
-        if let Some(tol) = self.get_tolerance() {
-            let max = self.get_nominal_value() * (100.0 + tol.plus) / 100.0;
-            self.normalize(max)
+        if self.get_tolerance().is_some() {
+            self.normalize(self.max_value())
         } else {
             "N/A".to_string()
         }
@@ -116,7 +495,8 @@ pub trait Measurement {
 
     fn get_tol_value_plus(&self) -> String {
         if let Some(tol) = self.get_tolerance() {
-            let delta = self.get_nominal_value() * tol.plus / 100.0;
+            let delta =
+                self.get_nominal_value().abs() * tolerance_mode().coverage() * tol.plus / 100.0;
             self.normalize(delta)
         } else {
             "N/A".to_string()
@@ -125,7 +505,8 @@ pub trait Measurement {
 
     fn get_tol_value_minus(&self) -> String {
         if let Some(tol) = self.get_tolerance() {
-            let delta = self.get_nominal_value() * tol.minus / 100.0;
+            let delta =
+                self.get_nominal_value().abs() * tolerance_mode().coverage() * tol.minus / 100.0;
             let result = self.normalize(delta);
             format!("-{}", result)
         } else {
@@ -135,7 +516,7 @@ pub trait Measurement {
 
     fn get_tol_percent_plus(&self) -> String {
         if let Some(tol) = self.get_tolerance() {
-            format!("{:.2}%", tol.plus)
+            format!("{:.2}%", tolerance_mode().coverage() * tol.plus)
         } else {
             "N/A".to_string()
         }
@@ -143,11 +524,73 @@ pub trait Measurement {
 
     fn get_tol_percent_minus(&self) -> String {
         if let Some(tol) = self.get_tolerance() {
-            format!("-{:.2}%", tol.minus)
+            format!("-{:.2}%", tolerance_mode().coverage() * tol.minus)
         } else {
             "N/A".to_string()
         }
     }
+
+    /// Nominal value in base units with full precision and without SI prefixes, e.g.
+    /// "= 0.0047 Ω" for "4.7m", so a wrong suffix is obvious.
+    fn get_value_hint(&self) -> String {
+        let unit = match self.get_unit() {
+            "R" => "Ω",
+            unit => unit,
+        };
+
+        format!("= {} {}", self.get_nominal_value(), unit)
+    }
+
+    /// Formats the measurement back into the input syntax accepted by `FromStr`
+    /// (e.g. "4.7k 5%" or "12 +5% -3%"), so computed values can be written into input fields.
+    fn get_value_input(&self) -> String {
+        let value = self.get_nominal_value();
+        let prefixes = [
+            (1e-12, "p"),
+            (1e-9, "n"),
+            (1e-6, "u"),
+            (1e-3, "m"),
+            (1.0, ""),
+            (1e3, "k"),
+            (1e6, "M"),
+            (1e9, "G"),
+            (1e12, "T"),
+        ];
+
+        let (threshold, prefix) = prefixes
+            .iter()
+            .rev()
+            .find(|(threshold, _)| value.abs() >= *threshold)
+            .copied()
+            .unwrap_or((1.0, ""));
+
+        let number = format!("{:.3}", value / threshold);
+        let number = number.trim_end_matches('0').trim_end_matches('.');
+        let mut result = format!("{}{}", number, prefix);
+
+        match self.get_tolerance() {
+            Some(tol) if tol.plus == tol.minus => {
+                result.push_str(&format!(" {}%", tol.plus));
+            }
+            Some(tol) => {
+                result.push_str(&format!(" +{}% -{}%", tol.plus, tol.minus));
+            }
+            None => (),
+        }
+
+        result
+    }
+}
+
+/// Tolerance of `result` from the true extremes of `interval` in the interval mode. `None` in
+/// the other modes, or if the interval cannot be written as a tolerance, e.g. around zero;
+/// the percentages are combined as usual then.
+fn interval_tolerance(result: f64, interval: impl FnOnce() -> Interval) -> Option<Tolerance> {
+    if tolerance_mode() != ToleranceMode::Interval {
+        return None;
+    }
+
+    interval().tolerance(result)
 }
 
 pub fn calculate_multiplication_with_tolerance<M: Measurement, N: Measurement>(
@@ -166,6 +609,11 @@ pub fn calculate_multiplication_with_tolerance<M: Measurement, N: Measurement>(
         return (result, None);
     }
 
+    if let Some(tol) = interval_tolerance(result, || Interval::of(factor1) * Interval::of(factor2))
+    {
+        return (result, Some(tol));
+    }
+
     let (operand1_min, operand1_max) = match operand1_tol {
         Some(tol) => (tol.minus, tol.plus),
         None => (0.0, 0.0),
@@ -175,9 +623,10 @@ pub fn calculate_multiplication_with_tolerance<M: Measurement, N: Measurement>(
         Some(tol) => (tol.minus, tol.plus),
         None => (0.0, 0.0),
     };
+    let mode = tolerance_mode();
     let tol = Tolerance {
-        plus: operand1_max + operand2_max,
-        minus: operand1_min + operand2_min,
+        plus: mode.combine(operand1_max, operand2_max),
+        minus: mode.combine(operand1_min, operand2_min),
     };
 
     (result, Some(tol))
@@ -203,6 +652,11 @@ pub fn calculate_division_with_tolerance<M: Measurement, N: Measurement>(
         return (result, None);
     }
 
+    if let Some(tol) = interval_tolerance(result, || Interval::of(factor1) / Interval::of(factor2))
+    {
+        return (result, Some(tol));
+    }
+
     let (operand1_min, operand1_max) = match operand1_tol {
         Some(tol) => (tol.minus, tol.plus),
         None => (0.0, 0.0),
@@ -213,9 +667,37 @@ pub fn calculate_division_with_tolerance<M: Measurement, N: Measurement>(
         None => (0.0, 0.0),
     };
 
+    let mode = tolerance_mode();
     let tol = Tolerance {
-        plus: operand1_max + operand2_min,
-        minus: operand1_min + operand2_max,
+        plus: mode.combine(operand1_max, operand2_min),
+        minus: mode.combine(operand1_min, operand2_max),
+    };
+
+    (result, Some(tol))
+}
+
+/// Square root of `value` with the tolerance of the root. Exact in the limit and interval
+/// modes, as the root is monotonic: `+p%` becomes `(√(1 + p) − 1)`. In the statistical mode
+/// the first-order (GUM) sensitivity halves the relative deviation.
+pub fn calculate_square_root_with_tolerance(
+    value: f64,
+    tolerance: Option<Tolerance>,
+) -> (f64, Option<Tolerance>) {
+    let result = value.sqrt();
+    let Some(tol) = tolerance else {
+        return (result, None);
+    };
+
+    let tol = match tolerance_mode() {
+        ToleranceMode::Limits | ToleranceMode::Interval => Tolerance {
+            plus: ((1.0 + tol.plus / 100.0).sqrt() - 1.0) * 100.0,
+            // ниже нуля корень не уходит
+            minus: (1.0 - (1.0 - tol.minus.min(100.0) / 100.0).sqrt()) * 100.0,
+        },
+        ToleranceMode::Statistical { .. } => Tolerance {
+            plus: tol.plus / 2.0,
+            minus: tol.minus / 2.0,
+        },
     };
 
     (result, Some(tol))
@@ -237,6 +719,11 @@ pub fn calculate_addition_with_tolerance<M: Measurement, N: Measurement>(
         return (result, None);
     }
 
+    if let Some(tol) = interval_tolerance(result, || Interval::of(factor1) + Interval::of(factor2))
+    {
+        return (result, Some(tol));
+    }
+
     let (operand1_min, operand1_max) = match operand1_tol {
         Some(tol) => (
             operand1_nom - operand1_nom * (1.0 - tol.minus / 100.0),
@@ -253,11 +740,17 @@ pub fn calculate_addition_with_tolerance<M: Measurement, N: Measurement>(
         None => (0.0, 0.0),
     };
 
-    let max_result = operand1_max + operand2_max;
-    let min_result = operand1_min + operand2_min;
+    let mode = tolerance_mode();
+    let max_result = mode.combine(operand1_max, operand2_max);
+    let min_result = mode.combine(operand1_min, operand2_min);
+    // RSS теряет знак отклонений
+    let scale = match mode {
+        ToleranceMode::Limits | ToleranceMode::Interval => result,
+        ToleranceMode::Statistical { .. } => result.abs(),
+    };
 
-    let tol_plus = (max_result / result) * 100.0;
-    let tol_minus = (min_result / result) * 100.0;
+    let tol_plus = (max_result / scale) * 100.0;
+    let tol_minus = (min_result / scale) * 100.0;
 
     let tol = Tolerance {
         plus: tol_plus,
@@ -283,6 +776,11 @@ pub fn calculate_subtraction_with_tolerance<M: Measurement, N: Measurement>(
         return (result, None);
     }
 
+    if let Some(tol) = interval_tolerance(result, || Interval::of(factor1) - Interval::of(factor2))
+    {
+        return (result, Some(tol));
+    }
+
     let (operand1_min, operand1_max) = match operand1_tol {
         Some(tol) => (
             operand1_nom - operand1_nom * (1.0 - tol.minus / 100.0),
@@ -299,11 +797,18 @@ pub fn calculate_subtraction_with_tolerance<M: Measurement, N: Measurement>(
         None => (0.0, 0.0),
     };
 
-    let max_result = operand1_max + operand2_max;
-    let min_result = operand1_min + operand2_min;
+    // верхняя граница разности - при наименьшем вычитаемом, нижняя - при наибольшем
+    let mode = tolerance_mode();
+    let max_result = mode.combine(operand1_max, operand2_min);
+    let min_result = mode.combine(operand1_min, operand2_max);
+    // RSS теряет знак отклонений
+    let scale = match mode {
+        ToleranceMode::Limits | ToleranceMode::Interval => result,
+        ToleranceMode::Statistical { .. } => result.abs(),
+    };
 
-    let tol_plus = (max_result / result) * 100.0;
-    let tol_minus = (min_result / result) * 100.0;
+    let tol_plus = (max_result / scale) * 100.0;
+    let tol_minus = (min_result / scale) * 100.0;
 
     let tol = Tolerance {
         plus: tol_plus,
@@ -316,6 +821,240 @@ pub fn calculate_subtraction_with_tolerance<M: Measurement, N: Measurement>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
+
+    #[test]
+    fn test_min_clamped() {
+        let resistance = "10 +5% -150%".parse::<Resistance>().unwrap();
+        assert!(resistance.is_min_clamped());
+        assert_eq!(resistance.get_value_min(), "0.00R (clamped)");
+        assert_eq!(resistance.get_value_max(), "10.50R");
+
+        let resistance = "10 100%".parse::<Resistance>().unwrap();
+        assert!(!resistance.is_min_clamped());
+
+        // напряжение может быть отрицательным
+        let voltage = "10 +5% -150%".parse::<Voltage>().unwrap();
+        assert!(!voltage.is_min_clamped());
+        assert_eq!(voltage.get_value_min(), "-5.00V");
+
+        assert!(matches!(
+            "10 +-5%".parse::<Power>(),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_min_max_order() {
+        let v = |s: &str| s.parse::<Voltage>().unwrap();
+
+        for (input, min, max) in [
+            ("10 +5% -2%", 9.8, 10.5),
+            ("10 +0% -5%", 9.5, 10.0),
+            ("10 +5% -0%", 10.0, 10.5),
+            // плюс допуска увеличивает модуль отрицательного значения
+            ("-10 +5% -2%", -10.5, -9.8),
+            ("-10 +0% -5%", -10.0, -9.5),
+            ("-10 +5% -0%", -10.5, -10.0),
+            ("-10 5%", -10.5, -9.5),
+            ("-10", -10.0, -10.0),
+            ("0 5%", 0.0, 0.0),
+        ] {
+            let value = v(input);
+            assert!((value.min_value() - min).abs() < 1e-12, "{}", input);
+            assert!((value.max_value() - max).abs() < 1e-12, "{}", input);
+            assert!(value.min_value() <= value.max_value(), "{}", input);
+        }
+
+        assert_eq!(v("-10 +5% -2%").get_value_min(), "-10.50V");
+        assert_eq!(v("-10 +5% -2%").get_value_max(), "-9.80V");
+        // the bounds stay ordered in the statistical mode too
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Statistical { coverage: 3 });
+        let value = v("-10 +1% -2%");
+        assert!((value.min_value() + 10.3).abs() < 1e-12);
+        assert!((value.max_value() + 9.4).abs() < 1e-12);
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Limits);
+    }
+
+    #[test]
+    fn test_parse() {
+        let voltage: Voltage = parse("12k 5%").unwrap();
+        assert_eq!(voltage.get_value_nom(), "12.00kV");
+        assert_eq!(
+            voltage.get_tolerance(),
+            Some(Tolerance {
+                plus: 5.0,
+                minus: 5.0
+            })
+        );
+
+        let resistance = parse::<Resistance>("10k + 4.7k").unwrap();
+        assert_eq!(resistance.get_nominal_value(), 14700.0);
+
+        assert!(matches!(
+            parse::<Voltage>(" "),
+            Err(ParserError::EmptyInput)
+        ));
+        assert!(matches!(
+            parse::<Resistance>("10x"),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_absolute_tolerance() {
+        let voltage: Voltage = parse("12 +/-0.5").unwrap();
+        let tol = voltage.get_tolerance().unwrap();
+        assert_eq!(tol.plus, tol.minus);
+        assert!((tol.plus - 0.5 / 12.0 * 100.0).abs() < 1e-12);
+        assert_eq!(voltage.get_tol_percent_plus(), "4.17%");
+        assert!((voltage.max_value() - 12.5).abs() < 1e-12);
+
+        // приставка у допуска и отрицательный номинал
+        let current: Current = parse("-2 +/-100m").unwrap();
+        assert!((current.get_tolerance().unwrap().minus - 5.0).abs() < 1e-12);
+        let resistance = parse::<Resistance>("1k +/-10 || 1k").unwrap();
+        assert_eq!(resistance.get_nominal_value(), 500.0);
+
+        assert!(matches!(
+            parse::<Voltage>("0 +/-0.5"),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_std_dev_propagation() {
+        // σ не может быть границей допуска
+        assert!(matches!(
+            parse::<Voltage>("12.00 s=0.05"),
+            Err(ParserError::IncorrectInput(_))
+        ));
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Interval);
+        assert!(parse::<Voltage>("12.00 s=0.05").is_err());
+
+        // GUM: R = U / I, u(U) = 0.05 V, u(I) = 10 mA
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Statistical { coverage: 2 });
+        let voltage: Voltage = parse("12.00 s=0.05").unwrap();
+        assert!((voltage.get_tolerance().unwrap().plus - 0.05 / 12.0 * 100.0).abs() < 1e-12);
+        assert!(matches!(
+            parse::<Voltage>("0 s=0.05"),
+            Err(ParserError::IncorrectInput(_))
+        ));
+
+        let resistance = voltage / parse::<Current>("2 s=10m").unwrap();
+        // u(R)/R = √((0.05/12)² + (0.01/2)²) = 0.6508%
+        let relative = (0.05f64 / 12.0).hypot(0.01 / 2.0);
+        let tol = resistance.get_tolerance().unwrap();
+        assert!((tol.plus - relative * 100.0).abs() < 1e-12);
+        // расширенная неопределённость при k = 2: 6 ± 0.0781
+        assert!((resistance.max_value() - 6.0 * (1.0 + 2.0 * relative)).abs() < 1e-12);
+        assert!((resistance.max_value() - 6.0781).abs() < 1e-4);
+
+        // U = U1 − U2: абсолютные неопределённости складываются квадратично
+        let drop = parse::<Voltage>("12 s=0.03").unwrap() - parse::<Voltage>("5 s=0.04").unwrap();
+        assert!((drop.max_value() - (7.0 + 2.0 * 0.05)).abs() < 1e-12);
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Limits);
+    }
+
+    #[test]
+    fn test_square_root_tolerance() {
+        let tol = Some(Tolerance {
+            plus: 21.0,
+            minus: 19.0,
+        });
+        // √1.21 = 1.1, √0.81 = 0.9
+        let (value, root) = calculate_square_root_with_tolerance(100.0, tol);
+        assert_eq!(value, 10.0);
+        let root = root.unwrap();
+        assert!((root.plus - 10.0).abs() < 1e-9);
+        assert!((root.minus - 10.0).abs() < 1e-9);
+
+        // первый порядок: чувствительность корня 1/2
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Statistical { coverage: 2 });
+        let (_, root) = calculate_square_root_with_tolerance(100.0, tol);
+        assert_eq!(
+            root,
+            Some(Tolerance {
+                plus: 10.5,
+                minus: 9.5
+            })
+        );
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Limits);
+
+        assert_eq!(calculate_square_root_with_tolerance(4.0, None), (2.0, None));
+    }
+
+    #[test]
+    fn test_append_prefix() {
+        assert_eq!(Dim::Kilo.append_to("10"), "10k");
+        assert_eq!(Dim::Mega.append_to("10m 5%"), "10M 5%");
+        assert_eq!(Dim::Milli.append_to(" -4.7 +5% -2%"), " -4.7m +5% -2%");
+        assert_eq!(Dim::Micro.append_to("typ 12 min 11"), "typ 12u min 11");
+        assert_eq!(Dim::Kilo.append_to("10k || 10k"), "10k || 10k");
+        assert_eq!(Dim::Pico.append_to(""), "");
+        assert_eq!(Dim::Pico.append_to("5%"), "5%");
+
+        assert_eq!(Dim::Mega.to_string(), "M — mega, 10⁶");
+        assert_eq!(Dim::Milli.to_string(), "m — milli, 10⁻³");
+    }
+
+    #[test]
+    fn test_parse_grouping() {
+        // точка остаётся десятичным разделителем на любом языке интерфейса
+        for lang in [crate::i18n::Lang::English, crate::i18n::Lang::Russian] {
+            crate::i18n::set_lang(lang);
+
+            for input in [
+                "1_000_000",
+                "1 000 000",
+                "1\u{202f}000\u{202f}000",
+                "1,000,000",
+            ] {
+                let voltage: Voltage = parse(input).unwrap();
+                assert_eq!(voltage.get_nominal_value(), 1e6, "{}", input);
+            }
+            let resistance = parse::<Resistance>("1,500.5 || 1_500.5").unwrap();
+            assert!((resistance.get_nominal_value() - 750.25).abs() < 1e-9);
+
+            let current: Current = parse("1,000m +1_000% -5%").unwrap();
+            assert_eq!(current.get_nominal_value(), 1.0);
+            assert_eq!(current.get_tolerance().unwrap().plus, 1000.0);
+
+            assert!(matches!(
+                parse::<Voltage>("1,000 000"),
+                Err(ParserError::IncorrectInput(_))
+            ));
+            assert!(matches!(
+                parse::<Resistance>("1_000,000"),
+                Err(ParserError::IncorrectInput(_))
+            ));
+            assert!(matches!(
+                parse::<Voltage>("12 100m"),
+                Err(ParserError::IncorrectInput(_))
+            ));
+            assert_eq!(
+                parse::<Voltage>("10 100").unwrap().get_nominal_value(),
+                10100.0
+            );
+        }
+        crate::i18n::set_lang(crate::i18n::Lang::English);
+    }
+
+    #[test]
+    fn test_parse_too_long() {
+        // вставленный документ отклоняется до разбора
+        let pasted = format!("12 {}", " ".repeat(parser::MAX_INPUT_LEN));
+        for result in [
+            parse::<Voltage>(&pasted).map(|_| ()),
+            parse::<Resistance>(&pasted).map(|_| ()),
+        ] {
+            match result {
+                Err(ParserError::IncorrectInput(e)) => assert!(e.contains("longer than")),
+                other => panic!("{:?}", other),
+            }
+        }
+        assert!(parse::<Voltage>(pasted.trim_end()).is_ok());
+    }
 
     #[test]
     fn test_trait_measurement() {
@@ -358,6 +1097,138 @@ mod tests {
         assert_eq!(test.get_tol_percent_minus(), "-3.30%");
     }
 
+    #[test]
+    fn test_trait_measurement_negative() {
+        struct Test;
+
+        impl Measurement for Test {
+            fn get_nominal_value(&self) -> f64 {
+                -0.5
+            }
+
+            fn get_tolerance(&self) -> Option<Tolerance> {
+                Some(Tolerance {
+                    plus: 10.0,
+                    minus: 2.0,
+                })
+            }
+
+            fn get_unit(&self) -> &'static str {
+                "A"
+            }
+        }
+
+        let test = Test;
+
+        assert_eq!(test.get_value_nom(), "-500.00mA");
+        assert_eq!(test.get_tol_value_plus(), "50.00mA");
+        assert_eq!(test.get_tol_value_minus(), "-10.00mA");
+        assert_eq!(test.get_tol_percent_plus(), "10.00%");
+        assert_eq!(test.get_tol_percent_minus(), "-2.00%");
+    }
+
+    #[test]
+    fn test_trait_measurement_approx_eq() {
+        let v = |s: &str| s.parse::<voltage::Voltage>().unwrap();
+
+        assert!(v("1").approx_eq(&v("1.0000000001"), 1e-9, 0.0));
+        assert!(!v("1").approx_eq(&v("1.001"), 1e-9, 0.0));
+        // около нуля относительное сравнение не работает
+        assert!(!v("0").approx_eq(&v("1e-15"), 1e-9, 0.0));
+        assert!(v("0").approx_eq(&v("1e-15"), 1e-9, 1e-12));
+        assert!(v("-1e-13").approx_eq(&v("1e-13"), 1e-9, 1e-12));
+        assert!(!v("-1").approx_eq(&v("1"), 1e-9, 1e-12));
+        // допуск не учитывается, только номинал
+        assert!(!v("1 50%").approx_eq(&v("1.2"), 1e-9, 0.0));
+    }
+
+    #[test]
+    fn test_trait_measurement_overlaps() {
+        let v = |s: &str| s.parse::<voltage::Voltage>().unwrap();
+
+        // 10 +10% -0% -> [10, 11], 12 -10% -> [10.8, 12]
+        assert!(v("10 +10% -0%").overlaps(&v("12 +0% -10%")));
+        assert!(v("12 +0% -10%").overlaps(&v("10 +10% -0%")));
+        // the same values with the tolerance on the other side do not meet
+        assert!(!v("10 +0% -10%").overlaps(&v("12 +10% -0%")));
+        // touching bounds
+        assert!(v("10 +10% -0%").overlaps(&v("11")));
+        // bands of zero width
+        assert!(v("0").overlaps(&v("0")));
+        assert!(!v("0").overlaps(&v("1e-15")));
+        assert!(v("0").overlaps(&v("1e-15 +0% -100%")));
+        // negative values, the band is mirrored
+        assert!(v("-10 10%").overlaps(&v("-11.5 5%")));
+    }
+
+    #[test]
+    fn test_trait_measurement_value_input() {
+        struct Test(f64, Option<Tolerance>);
+
+        impl Measurement for Test {
+            fn get_nominal_value(&self) -> f64 {
+                self.0
+            }
+
+            fn get_tolerance(&self) -> Option<Tolerance> {
+                self.1
+            }
+
+            fn get_unit(&self) -> &'static str {
+                "TEST"
+            }
+        }
+
+        assert_eq!(Test(15000.0, None).get_value_input(), "15k");
+        assert_eq!(Test(4700.0, None).get_value_input(), "4.7k");
+        assert_eq!(Test(0.0125, None).get_value_input(), "12.5m");
+        assert_eq!(Test(12.0, None).get_value_input(), "12");
+        assert_eq!(Test(0.0, None).get_value_input(), "0");
+        assert_eq!(
+            Test(
+                1e6,
+                Some(Tolerance {
+                    plus: 5.0,
+                    minus: 5.0
+                })
+            )
+            .get_value_input(),
+            "1M 5%"
+        );
+        assert_eq!(
+            Test(
+                220.0,
+                Some(Tolerance {
+                    plus: 5.0,
+                    minus: 3.3
+                })
+            )
+            .get_value_input(),
+            "220 +5% -3.3%"
+        );
+    }
+
+    #[test]
+    fn test_trait_measurement_value_hint() {
+        assert_eq!(
+            "4.7m".parse::<Resistance>().unwrap().get_value_hint(),
+            "= 0.0047 Ω"
+        );
+        assert_eq!(
+            "12k 5%".parse::<Voltage>().unwrap().get_value_hint(),
+            "= 12000 V"
+        );
+        assert_eq!(
+            "2.2M".parse::<Resistance>().unwrap().get_value_hint(),
+            "= 2200000 Ω"
+        );
+        assert_eq!(
+            "150u".parse::<Current>().unwrap().get_value_hint(),
+            "= 0.00015 A"
+        );
+        assert_eq!("-5".parse::<Power>().unwrap().get_value_hint(), "= -5 W");
+    }
+
     #[test]
     fn test_trait_calculation() {
         struct Value1;
@@ -430,14 +1301,14 @@ mod tests {
                 minus: 3.033333333333341
             })
         );
-        // -
+        // -: 315 - 146.25 = 168.75, 290.1 - 151.5 = 138.6
         let d = calculate_subtraction_with_tolerance(&value1, &value2);
         assert_eq!(d.0, 150.0);
         assert_eq!(
             d.1,
             Some(Tolerance {
-                plus: 11.0,
-                minus: 9.100000000000023
+                plus: 12.5,
+                minus: 7.600000000000024
             })
         );
 
@@ -502,4 +1373,87 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_statistical_tolerance_mode() {
+        use std::str::FromStr;
+
+        let voltage = voltage::Voltage::from_str("10 1%").unwrap();
+        let current = current::Current::from_str("2 +2% -1%").unwrap();
+        let product = |mode| {
+            tolerance_mode::set_tolerance_mode(mode);
+            let (value, tolerance) = calculate_multiplication_with_tolerance(&voltage, &current);
+            power::Power { value, tolerance }
+        };
+
+        let limits = product(ToleranceMode::Limits);
+        assert_eq!(
+            limits.get_tolerance(),
+            Some(Tolerance {
+                plus: 3.0,
+                minus: 2.0
+            })
+        );
+        assert!((limits.max_value() - 20.6).abs() < 1e-12);
+        assert!((limits.min_value() - 19.6).abs() < 1e-12);
+
+        // 1σ складываются как сумма квадратов, полоса показана как 3σ
+        let statistical = product(ToleranceMode::Statistical { coverage: 3 });
+        let tol = statistical.get_tolerance().unwrap();
+        assert!((tol.plus - 5f64.sqrt()).abs() < 1e-12);
+        assert!((tol.minus - 2f64.sqrt()).abs() < 1e-12);
+        assert!((statistical.max_value() - 20.0 * (1.0 + 0.03 * 5f64.sqrt())).abs() < 1e-12);
+        assert!((statistical.min_value() - 20.0 * (1.0 - 0.03 * 2f64.sqrt())).abs() < 1e-12);
+        assert_eq!(statistical.get_tol_percent_plus(), "6.71%");
+        assert_eq!(statistical.get_tol_percent_minus(), "-4.24%");
+
+        // at 1σ the band is tighter than the worst case, at 3σ it is wider
+        assert!(tol.plus < 3.0);
+        assert!(statistical.max_value() > 20.6);
+
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Limits);
+    }
+
+    #[test]
+    fn test_interval_tolerance_mode() {
+        let assert_band = |value: &dyn Fn() -> (f64, f64), min: f64, max: f64| {
+            let (value_min, value_max) = value();
+            assert!((value_min - min).abs() < 1e-12, "{} != {}", value_min, min);
+            assert!((value_max - max).abs() < 1e-12, "{} != {}", value_max, max);
+        };
+        let voltage = "10 1%".parse::<Voltage>().unwrap();
+        let resistance = "1k 5%".parse::<Resistance>().unwrap();
+
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Interval);
+
+        // I = U / R, P = U * I: крайние значения считаются вручную
+        let current = voltage / resistance;
+        assert_band(
+            &|| (current.min_value(), current.max_value()),
+            9.9 / 1050.0,
+            10.1 / 950.0,
+        );
+        let power = voltage * current;
+        assert_band(
+            &|| (power.min_value(), power.max_value()),
+            9.9 * 9.9 / 1050.0,
+            10.1 * 10.1 / 950.0,
+        );
+        let tol = power.get_tolerance().unwrap();
+        assert!(tol.plus > 7.3 && tol.minus < 6.7);
+
+        let difference = voltage - "4 2%".parse::<Voltage>().unwrap();
+        assert_band(
+            &|| (difference.min_value(), difference.max_value()),
+            9.9 - 4.08,
+            10.1 - 3.92,
+        );
+        let series = resistance + "2k 1%".parse::<Resistance>().unwrap();
+        assert_band(&|| (series.min_value(), series.max_value()), 2930.0, 3070.0);
+
+        // the percentages only add up approximately
+        tolerance_mode::set_tolerance_mode(ToleranceMode::Limits);
+        let power = voltage * (voltage / resistance);
+        assert!((power.get_tolerance().unwrap().plus - 7.0).abs() < 1e-12);
+    }
 }
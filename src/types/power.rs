@@ -1,14 +1,16 @@
 use crate::types::{
-    calculate_division_with_tolerance, calculate_multiplication_with_tolerance, current::Current,
-    resistance::Resistance, voltage::Voltage, Measurement, ParserError, Tolerance,
+    calculate_addition_with_tolerance, calculate_division_with_tolerance, compare,
+    current::Current, parse_value, voltage::Voltage, Measurement, ParserError, Tolerance,
 };
-use crate::{parser, parser::Block};
+use serde::Serialize;
 use std::{
-    ops::{Div, Mul},
+    cmp::Ordering,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Neg},
     str::FromStr,
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Power {
     pub value: f64,
     pub tolerance: Option<Tolerance>,
@@ -24,6 +26,8 @@ impl Default for Power {
 }
 
 impl Measurement for Power {
+    const NON_NEGATIVE: bool = true;
+
     fn get_nominal_value(&self) -> f64 {
         self.value
     }
@@ -37,72 +41,49 @@ impl Measurement for Power {
     }
 }
 
+impl PartialOrd for Power {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare(self, other)
+    }
+}
+
 impl FromStr for Power {
     type Err = ParserError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let input = input.trim();
-        if input.trim().is_empty() {
-            return Err(ParserError::EmptyInput);
-        }
+        parse_value(input, |value, tolerance| Power { value, tolerance })
+    }
+}
+
+impl AddAssign for Power {
+    fn add_assign(&mut self, rhs: Self) {
+        let result = calculate_addition_with_tolerance(self, &rhs);
 
-        match parser::parse_blocks(input) {
-            Ok((input, result)) => {
-                // If there is any remaining unparsed input, it's an error
-                if !input.is_empty() {
-                    return Err(ParserError::IncorrectInput(input.to_string()));
-                }
-
-                let mut value = f64::NAN;
-                let mut tol: Option<Tolerance> = None;
-
-                // Process each parsed block
-                for block in result {
-                    match block {
-                        Block::Number(n) => value = n,
-                        Block::NumberSuffix((n, s)) => value = n * s.coefficient(),
-                        Block::TolMinus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: tt.plus,
-                                    minus: t,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: 0.0,
-                                    minus: t,
-                                })
-                            };
-                        }
-                        Block::TolPlus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: tt.minus,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: 0.0,
-                                })
-                            };
-                        }
-                        Block::TolPlusMinus(t) => {
-                            tol = Some(Tolerance { plus: t, minus: t });
-                        }
-                    }
-                }
-
-                Ok(Power {
-                    value,
-                    tolerance: tol,
-                })
-            }
-            Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
+        self.value = result.0;
+        self.tolerance = result.1;
+    }
+}
+
+impl Add for Power {
+    type Output = Power;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let result = calculate_addition_with_tolerance(&self, &rhs);
+
+        Power {
+            value: result.0,
+            tolerance: result.1,
         }
     }
 }
 
+impl Sum for Power {
+    /// Total power of the items, zero without tolerance for none.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(Add::add).unwrap_or_default()
+    }
+}
+
 impl Div<Voltage> for Power {
     type Output = Current;
 
@@ -110,39 +91,74 @@ impl Div<Voltage> for Power {
         let (value, tol) = calculate_division_with_tolerance(&self, &rhs);
 
         Current {
-            value: value,
+            value,
             tolerance: tol,
         }
     }
 }
 
 impl Div<Current> for Power {
-    type Output = Resistance;
+    type Output = Voltage;
 
     fn div(self, rhs: Current) -> Self::Output {
-        let current2 = calculate_multiplication_with_tolerance(&rhs, &rhs);
-        let current2 = Current {
-            value: current2.0,
-            tolerance: current2.1,
-        };
-        let (value, tol) = calculate_division_with_tolerance(&self, &current2);
-
-        Resistance {
-            value: value,
+        let (value, tol) = calculate_division_with_tolerance(&self, &rhs);
+
+        Voltage {
+            value,
             tolerance: tol,
         }
     }
 }
 
-impl Mul<Current> for Power {
-    type Output = Voltage;
+impl Neg for Power {
+    type Output = Power;
 
-    fn mul(self, rhs: Current) -> Self::Output {
-        let (value, tol) = calculate_division_with_tolerance(&self, &rhs);
-
-        Voltage {
-            value: value,
-            tolerance: tol,
+    fn neg(self) -> Self::Output {
+        Power {
+            value: -self.value,
+            tolerance: self.tolerance,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_add() {
+        let p1 = "1 10%".parse::<Power>().unwrap();
+        let p2 = "3".parse::<Power>().unwrap();
+
+        // 1 ± 0.1 + 3 -> 4 ± 0.1
+        let sum = p1 + p2;
+        assert_eq!(sum.value, 4.0);
+        let tol = sum.tolerance.unwrap();
+        assert!((tol.plus - 2.5).abs() < 1e-9);
+        assert!((tol.minus - 2.5).abs() < 1e-9);
+
+        let mut total = p1;
+        total += p2;
+        assert_eq!(total, sum);
+
+        assert_eq!(p2 + p2, "6".parse::<Power>().unwrap());
+    }
+
+    #[test]
+    fn test_power_sum() {
+        assert_eq!(std::iter::empty::<Power>().sum::<Power>(), Power::default());
+
+        let powers = ["1 10%", "3"].map(|p| p.parse::<Power>().unwrap());
+        let total = powers.iter().copied().sum::<Power>();
+        assert_eq!(total, powers[0] + powers[1]);
+        assert_eq!(total.value, 4.0);
+        assert!((total.tolerance.unwrap().plus - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_power_eq() {
+        assert_eq!("500m".parse::<Power>(), "0.5".parse::<Power>());
+        assert_ne!("0.5 1%".parse::<Power>(), "0.5".parse::<Power>());
+        assert_ne!("0.5".parse::<Power>(), "0.6".parse::<Power>());
+    }
+}
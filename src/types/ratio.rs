@@ -0,0 +1,132 @@
+use crate::types::{
+    calculate_multiplication_with_tolerance, compare, parse_value, voltage::Voltage, Measurement,
+    ParserError, Tolerance,
+};
+use serde::Serialize;
+use std::{cmp::Ordering, ops::Mul, str::FromStr};
+
+/// Dimensionless ratio or gain, e.g. the output of a divider per volt of its input.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Ratio {
+    pub value: f64,
+    pub tolerance: Option<Tolerance>,
+}
+
+impl Default for Ratio {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            tolerance: None,
+        }
+    }
+}
+
+impl Measurement for Ratio {
+    fn get_nominal_value(&self) -> f64 {
+        self.value
+    }
+
+    fn get_tolerance(&self) -> Option<Tolerance> {
+        self.tolerance
+    }
+
+    fn get_unit(&self) -> &'static str {
+        ""
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare(self, other)
+    }
+}
+
+impl FromStr for Ratio {
+    type Err = ParserError;
+
+    /// A plain number, `"0.5"`, or a percentage as the nominal value, `"50%"`; both may be
+    /// followed by a tolerance, so `"50% 1%"` is 0.5 ±1%.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim_start();
+        let (first, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+
+        // проценты в номинале: без знака, иначе это допуск
+        let percent = first
+            .strip_suffix('%')
+            .filter(|number| number.starts_with(|c: char| c.is_ascii_digit() || c == '.'));
+        match percent {
+            Some(number) => {
+                parse_value(&format!("{} {}", number, rest), |value, tolerance| Ratio {
+                    value: value / 100.0,
+                    tolerance,
+                })
+            }
+            None => parse_value(input, |value, tolerance| Ratio { value, tolerance }),
+        }
+    }
+}
+
+impl Mul<Ratio> for Voltage {
+    type Output = Voltage;
+
+    fn mul(self, rhs: Ratio) -> Self::Output {
+        let (value, tol) = calculate_multiplication_with_tolerance(&self, &rhs);
+
+        Voltage {
+            value,
+            tolerance: tol,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_parser() {
+        assert_eq!(
+            "50%".parse::<Ratio>(),
+            Ok(Ratio {
+                value: 0.5,
+                tolerance: None
+            })
+        );
+        assert_eq!(
+            "0.25".parse::<Ratio>(),
+            Ok(Ratio {
+                value: 0.25,
+                tolerance: None
+            })
+        );
+        assert_eq!(
+            "50% 1%".parse::<Ratio>(),
+            Ok(Ratio {
+                value: 0.5,
+                tolerance: Some(Tolerance {
+                    plus: 1.0,
+                    minus: 1.0
+                })
+            })
+        );
+        assert_eq!("".parse::<Ratio>(), Err(ParserError::EmptyInput));
+        assert!("%".parse::<Ratio>().is_err());
+    }
+
+    #[test]
+    fn test_voltage_scaling() {
+        let input = "12 2%".parse::<Voltage>().unwrap();
+        let output = input * "50% 1%".parse::<Ratio>().unwrap();
+
+        assert_eq!(output.get_value_nom(), "6.00V");
+        assert_eq!(
+            output.tolerance,
+            Some(Tolerance {
+                plus: 3.0,
+                minus: 3.0
+            })
+        );
+    }
+}
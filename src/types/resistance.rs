@@ -1,12 +1,22 @@
 use crate::types::{
-    calculate_addition_with_tolerance, calculate_division_with_tolerance,
-    calculate_multiplication_with_tolerance, current::Current, power::Power, Measurement,
-    ParserError, Tolerance,
+    calculate_addition_with_tolerance, calculate_multiplication_with_tolerance,
+    calculate_subtraction_with_tolerance, compare,
+    current::Current,
+    from_blocks,
+    tolerance_mode::{tolerance_mode, ToleranceMode},
+    voltage::Voltage,
+    Measurement, ParserError, Tolerance,
+};
+use crate::{
+    parser,
+    parser::{Block, Expr},
+};
+use serde::Serialize;
+use std::{
+    cmp::Ordering, iter::Sum, ops::Add, ops::AddAssign, ops::Mul, ops::Neg, ops::Sub, str::FromStr,
 };
-use crate::{parser, parser::Block};
-use std::{ops::Add, ops::AddAssign, ops::Mul, str::FromStr};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Resistance {
     pub value: f64,
     pub tolerance: Option<Tolerance>,
@@ -22,6 +32,8 @@ impl Default for Resistance {
 }
 
 impl Measurement for Resistance {
+    const NON_NEGATIVE: bool = true;
+
     fn get_nominal_value(&self) -> f64 {
         self.value
     }
@@ -35,69 +47,137 @@ impl Measurement for Resistance {
     }
 }
 
+impl PartialOrd for Resistance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare(self, other)
+    }
+}
+
 impl FromStr for Resistance {
     type Err = ParserError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let input = input.trim();
-        if input.trim().is_empty() {
-            return Err(ParserError::EmptyInput);
-        }
+        evaluate_resistance_expr(input)
+    }
+}
 
-        match parser::parse_blocks(input) {
-            Ok((input, result)) => {
-                // If there is any remaining unparsed input, it's an error
-                if !input.is_empty() {
-                    return Err(ParserError::IncorrectInput(input.to_string()));
-                }
+/// Difference below this share of the minuend is taken as cancellation: its relative
+/// tolerance would be meaningless.
+const CANCELLATION: f64 = 1e-9;
 
-                let mut value = f64::NAN;
-                let mut tol: Option<Tolerance> = None;
-
-                // Process each parsed block
-                for block in result {
-                    match block {
-                        Block::Number(n) => value = n,
-                        Block::NumberSuffix((n, s)) => value = n * s.coefficient(),
-                        Block::TolMinus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: tt.plus,
-                                    minus: t,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: 0.0,
-                                    minus: t,
-                                })
-                            };
-                        }
-                        Block::TolPlus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: tt.minus,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: 0.0,
-                                })
-                            };
-                        }
-                        Block::TolPlusMinus(t) => {
-                            tol = Some(Tolerance { plus: t, minus: t });
-                        }
-                    }
+/// Evaluates a resistor network expression: `+` for series, `||` for parallel connection
+/// and parentheses, e.g. `"(10k || 10k) + 1k"` is 6k. `-` subtracts, e.g. the leads from a
+/// measured value, and the difference must stay above zero. A plain value like
+/// `"4.7k 5%"` is an expression too.
+pub fn evaluate_resistance_expr(input: &str) -> Result<Resistance, ParserError> {
+    fn evaluate(expr: Expr) -> Result<Resistance, ParserError> {
+        match expr {
+            // допуск по умолчанию - у каждого резистора цепочки
+            Expr::Value(blocks) => {
+                let number = blocks
+                    .iter()
+                    .any(|block| matches!(block, Block::Number(_) | Block::NumberSuffix(_)));
+                if !number {
+                    return Err(ParserError::IncorrectInput(
+                        "every resistance of the expression needs a value".to_string(),
+                    ));
                 }
+                from_blocks(blocks, |value, tolerance| Resistance { value, tolerance })
+            }
+            Expr::Series(a, b) => Ok(evaluate(*a)? + evaluate(*b)?),
+            Expr::Difference(a, b) => {
+                let minuend = evaluate(*a)?;
+                let difference = minuend - evaluate(*b)?;
+                if !difference.value.is_finite()
+                    || difference.value <= minuend.value.abs() * CANCELLATION
+                {
+                    return Err(ParserError::IncorrectInput(
+                        "the subtracted resistance must be below the one it is subtracted from"
+                            .to_string(),
+                    ));
+                }
+                Ok(difference)
+            }
+            Expr::Parallel(a, b) => evaluate(*a)?.parallel(&evaluate(*b)?).ok_or_else(|| {
+                ParserError::IncorrectInput("parallel resistances must be above zero".to_string())
+            }),
+        }
+    }
+
+    parser::check_length(input).map_err(|e| ParserError::IncorrectInput(e.to_string()))?;
+    let input = parser::normalize_signs(input);
+    let input =
+        parser::strip_grouping(&input).map_err(|e| ParserError::IncorrectInput(e.to_string()))?;
+    let (input, _) = parser::strip_units(&input);
+    let input = input.trim();
+    if input.trim().is_empty() {
+        return Err(ParserError::EmptyInput);
+    }
+
+    match parser::parse_expr(input) {
+        Ok(expr) => evaluate(expr),
+        Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
+    }
+}
+
+impl Resistance {
+    /// Parallel combination `R1 * R2 / (R1 + R2)`. The bounds are combined bound with
+    /// bound, since the result grows with both resistances. `None` unless both are above zero.
+    pub fn parallel(&self, other: &Resistance) -> Option<Resistance> {
+        const ZERO: Tolerance = Tolerance {
+            plus: 0.0,
+            minus: 0.0,
+        };
+
+        fn combine(r1: f64, r2: f64) -> f64 {
+            r1 * r2 / (r1 + r2)
+        }
+
+        if self.min_value() <= 0.0 || other.min_value() <= 0.0 {
+            return None;
+        }
 
-                Ok(Resistance {
+        let value = combine(self.value, other.value);
+        let (tol1, tol2) = match (self.tolerance, other.tolerance) {
+            (None, None) => {
+                return Some(Resistance {
                     value,
-                    tolerance: tol,
+                    tolerance: None,
                 })
             }
-            Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
-        }
+            (tol1, tol2) => (tol1.unwrap_or(ZERO), tol2.unwrap_or(ZERO)),
+        };
+        let tolerance = match tolerance_mode() {
+            // параллельное соединение монотонно, крайние значения дают точный интервал
+            ToleranceMode::Limits | ToleranceMode::Interval => {
+                let max = combine(
+                    self.value * (1.0 + tol1.plus / 100.0),
+                    other.value * (1.0 + tol2.plus / 100.0),
+                );
+                let min = combine(
+                    self.value * (1.0 - tol1.minus / 100.0),
+                    other.value * (1.0 - tol2.minus / 100.0),
+                );
+                Tolerance {
+                    plus: (max / value - 1.0) * 100.0,
+                    minus: (1.0 - min / value) * 100.0,
+                }
+            }
+            mode @ ToleranceMode::Statistical { .. } => {
+                // чувствительность R1||R2 к относительному отклонению каждого резистора
+                let w1 = other.value / (self.value + other.value);
+                let w2 = self.value / (self.value + other.value);
+                Tolerance {
+                    plus: mode.combine(w1 * tol1.plus, w2 * tol2.plus),
+                    minus: mode.combine(w1 * tol1.minus, w2 * tol2.minus),
+                }
+            }
+        };
+
+        Some(Resistance {
+            value,
+            tolerance: Some(tolerance),
+        })
     }
 }
 
@@ -123,20 +203,239 @@ impl Add for Resistance {
     }
 }
 
+impl Sum for Resistance {
+    /// Series connection of the resistances, zero without tolerance for none.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(Add::add).unwrap_or_default()
+    }
+}
+
+impl Sub for Resistance {
+    type Output = Resistance;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let result = calculate_subtraction_with_tolerance(&self, &rhs);
+
+        Resistance {
+            value: result.0,
+            tolerance: result.1,
+        }
+    }
+}
+
 impl Mul<Current> for Resistance {
-    type Output = Power;
+    type Output = Voltage;
 
     fn mul(self, rhs: Current) -> Self::Output {
-        let current2 = calculate_multiplication_with_tolerance(&rhs, &rhs);
-        let current2 = Current {
-            value: current2.0,
-            tolerance: current2.1,
-        };
-        let (value, tol) = calculate_division_with_tolerance(&current2, &self);
+        let (value, tol) = calculate_multiplication_with_tolerance(&self, &rhs);
 
-        Power {
-            value: value,
+        Voltage {
+            value,
             tolerance: tol,
         }
     }
 }
+
+/// Scales the value, the relative tolerance is kept.
+impl Mul<f64> for Resistance {
+    type Output = Resistance;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Resistance {
+            value: self.value * rhs,
+            tolerance: self.tolerance,
+        }
+    }
+}
+
+impl Neg for Resistance {
+    type Output = Resistance;
+
+    fn neg(self) -> Self::Output {
+        Resistance {
+            value: -self.value,
+            tolerance: self.tolerance,
+        }
+    }
+}
+
+/// The candidate with the nominal value closest to the one of `target`, e.g. the standard
+/// value nearest to a calculated one. Candidates with a NaN value are skipped; `None` if
+/// none is left or the target is NaN. Of two equally close candidates the first is taken.
+pub fn closest_to<'a>(candidates: &'a [Resistance], target: &Resistance) -> Option<&'a Resistance> {
+    if target.value.is_nan() {
+        return None;
+    }
+
+    candidates
+        .iter()
+        .filter(|candidate| !candidate.value.is_nan())
+        .min_by(|a, b| {
+            let da = (a.value - target.value).abs();
+            let db = (b.value - target.value).abs();
+            da.total_cmp(&db)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eseries::ESeries;
+
+    fn ohms(value: f64) -> Resistance {
+        Resistance {
+            value,
+            tolerance: None,
+        }
+    }
+
+    #[test]
+    fn test_resistance_order() {
+        let mut values = [
+            ohms(4.7e3),
+            ohms(100.0),
+            "1k 5%".parse().unwrap(),
+            ohms(1e3),
+        ];
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let sorted: Vec<f64> = values.iter().map(|r| r.value).collect();
+        assert_eq!(sorted, [100.0, 1e3, 1e3, 4.7e3]);
+        // при равном номинале значение без допуска идёт первым
+        assert_eq!(values[1].tolerance, None);
+        assert!(values[1] < values[2]);
+
+        assert_eq!(ohms(f64::NAN).partial_cmp(&ohms(1.0)), None);
+    }
+
+    #[test]
+    fn test_closest_to() {
+        let e24: Vec<Resistance> = ESeries::E24
+            .values()
+            .iter()
+            .map(|value| ohms(value * 1e3))
+            .collect();
+
+        assert_eq!(closest_to(&e24, &ohms(3456.0)).unwrap().value, 3.6e3);
+        assert_eq!(closest_to(&e24, &ohms(1e3)).unwrap().value, 1e3);
+
+        let with_nan = [ohms(f64::NAN), ohms(2.2e3)];
+        assert_eq!(closest_to(&with_nan, &ohms(1e3)).unwrap().value, 2.2e3);
+        assert_eq!(closest_to(&e24, &ohms(f64::NAN)), None);
+        assert_eq!(closest_to(&[], &ohms(1e3)), None);
+    }
+
+    #[test]
+    fn test_resistance_parallel_parser() {
+        let r = "10k || 10k".parse::<Resistance>().unwrap();
+        assert_eq!(r.value, 5000.0);
+        assert_eq!(r.tolerance, None);
+
+        let r = "10k 1% || 10k 1%".parse::<Resistance>().unwrap();
+        assert!((r.value - 5000.0).abs() < 1e-9);
+        let tol = r.tolerance.unwrap();
+        assert!((tol.plus - 1.0).abs() < 1e-9);
+        assert!((tol.minus - 1.0).abs() < 1e-9);
+
+        // 10k || 15k = 6k, the tolerance of a single resistor is spread over the result
+        let r = "10k +10% || 15k".parse::<Resistance>().unwrap();
+        assert!((r.value - 6000.0).abs() < 1e-9);
+        let tol = r.tolerance.unwrap();
+        assert!((tol.plus - (11e3 * 15e3 / 26e3 / 6e3 - 1.0) * 100.0).abs() < 1e-9);
+        assert_eq!(tol.minus, 0.0);
+    }
+
+    #[test]
+    fn test_evaluate_resistance_expr() {
+        let nominal = |s: &str| evaluate_resistance_expr(s).unwrap().value;
+
+        assert_eq!(nominal("(10k || 10k) + 1k"), 6000.0);
+        assert_eq!(nominal("1k + 10k || 10k"), 6000.0);
+        assert_eq!(nominal("(1k + 9k) || 10k"), 5000.0);
+        assert_eq!(nominal("1k + 2k + 3k"), 6000.0);
+        assert_eq!(nominal("((4.7k))"), 4700.0);
+        // знак допуска не путается с последовательным соединением
+        assert_eq!(nominal("1k +5% + 1k"), 2000.0);
+
+        let r = evaluate_resistance_expr("1k 1% + 1k 1%").unwrap();
+        let tol = r.tolerance.unwrap();
+        assert!((tol.plus - 1.0).abs() < 1e-9);
+        assert!((tol.minus - 1.0).abs() < 1e-9);
+
+        assert!(matches!(
+            evaluate_resistance_expr("(1k + "),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_resistance_sum() {
+        assert_eq!(
+            Vec::<Resistance>::new().into_iter().sum::<Resistance>(),
+            ohms(0.0)
+        );
+
+        let resistances = ["1k 1%", "2k 1%", "1k"].map(|r| r.parse::<Resistance>().unwrap());
+        let r = resistances.iter().copied().sum::<Resistance>();
+        assert_eq!(r.value, 4000.0);
+        // 30R из 4k
+        let tol = r.tolerance.unwrap();
+        assert!((tol.plus - 0.75).abs() < 1e-9);
+        assert!((tol.minus - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resistance_sub() {
+        let r = ohms(0.5) - ohms(0.02);
+        assert!((r.value - 0.48).abs() < 1e-12);
+        assert_eq!(r.tolerance, None);
+
+        // допуск измерения относится к разности: 0.5 ±1% даёт ±5mR на 0.48
+        let r = evaluate_resistance_expr("0.5 1% - 20m").unwrap();
+        assert!((r.value - 0.48).abs() < 1e-12);
+        let tol = r.tolerance.unwrap();
+        assert!((tol.plus - 0.5 / 0.48).abs() < 1e-9);
+        assert!((tol.minus - 0.5 / 0.48).abs() < 1e-9);
+
+        assert_eq!(
+            evaluate_resistance_expr("(1k || 1k) - 100").unwrap().value,
+            400.0
+        );
+
+        // верхняя граница - при нижней границе проводов: 0.48 +0 -10mR
+        let r = evaluate_resistance_expr("0.5 - 20m +50% -0%").unwrap();
+        assert!((r.max_value() - 0.48).abs() < 1e-12);
+        assert!((r.min_value() - 0.47).abs() < 1e-12);
+
+        // провода не могут быть сопротивлением больше измеренного, разность около нуля
+        // и слагаемое из одного допуска тоже не принимаются
+        for input in [
+            "20m - 0.5",
+            "0.3 - 0.1 - 0.2",
+            "1 - 1",
+            "10k - 5%",
+            "10k + 5%",
+        ] {
+            assert!(
+                matches!(
+                    evaluate_resistance_expr(input),
+                    Err(ParserError::IncorrectInput(_))
+                ),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_resistance_parallel_zero() {
+        assert!(matches!(
+            "0 || 10k".parse::<Resistance>(),
+            Err(ParserError::IncorrectInput(_))
+        ));
+        assert!(matches!(
+            "10k || ".parse::<Resistance>(),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+}
@@ -0,0 +1,78 @@
+//! # Tolerance Interpretation
+//!
+//! By default a tolerance is a hard limit: the value never leaves its band, and the band of a
+//! result is the worst case of the input bands. In the statistical mode a tolerance is the
+//! standard deviation (1σ) of the value, results combine relative deviations as the root sum
+//! of squares (RSS), and the displayed band is the deviation scaled to the chosen coverage.
+//! The interval mode keeps the worst-case meaning but computes the true extremes of every
+//! operation with [`Interval`](super::interval::Interval) arithmetic.
+//!
+//! Like the interface language, the mode is kept per thread.
+
+use std::cell::Cell;
+use std::fmt;
+
+use crate::i18n::{t, Key};
+
+thread_local! {
+    static MODE: Cell<ToleranceMode> = const { Cell::new(ToleranceMode::Limits) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToleranceMode {
+    /// The tolerance is the worst-case limit.
+    #[default]
+    Limits,
+    /// The tolerance is 1σ, the band is shown as `coverage`·σ.
+    Statistical { coverage: u8 },
+    /// The tolerance is the worst-case limit, results are computed with interval arithmetic.
+    Interval,
+}
+
+impl ToleranceMode {
+    pub const ALL: [ToleranceMode; 5] = [
+        ToleranceMode::Limits,
+        ToleranceMode::Interval,
+        ToleranceMode::Statistical { coverage: 1 },
+        ToleranceMode::Statistical { coverage: 2 },
+        ToleranceMode::Statistical { coverage: 3 },
+    ];
+
+    /// Factor between a stored tolerance and the displayed band.
+    pub fn coverage(&self) -> f64 {
+        match self {
+            ToleranceMode::Limits | ToleranceMode::Interval => 1.0,
+            ToleranceMode::Statistical { coverage } => f64::from(*coverage),
+        }
+    }
+
+    /// Combines relative (or absolute) deviations of independent operands.
+    pub fn combine(&self, a: f64, b: f64) -> f64 {
+        match self {
+            ToleranceMode::Limits | ToleranceMode::Interval => a + b,
+            ToleranceMode::Statistical { .. } => a.hypot(b),
+        }
+    }
+}
+
+impl fmt::Display for ToleranceMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToleranceMode::Limits => write!(f, "{}", t(Key::ToleranceLimits)),
+            ToleranceMode::Interval => write!(f, "{}", t(Key::ToleranceInterval)),
+            ToleranceMode::Statistical { coverage } => {
+                write!(f, "{} {}σ", t(Key::ToleranceStatistical), coverage)
+            }
+        }
+    }
+}
+
+/// Sets the tolerance interpretation for the current thread.
+pub fn set_tolerance_mode(mode: ToleranceMode) {
+    MODE.with(|m| m.set(mode));
+}
+
+/// Returns the tolerance interpretation of the current thread.
+pub fn tolerance_mode() -> ToleranceMode {
+    MODE.with(|m| m.get())
+}
@@ -1,20 +1,18 @@
-use crate::{
-    parser,
-    parser::Block,
-    types::{
-        calculate_addition_with_tolerance, calculate_division_with_tolerance,
-        calculate_multiplication_with_tolerance, calculate_subtraction_with_tolerance,
-        current::Current, power::Power, resistance::Resistance, Measurement, ParserError,
-        Tolerance,
-    },
+use crate::types::{
+    calculate_addition_with_tolerance, calculate_division_with_tolerance,
+    calculate_multiplication_with_tolerance, calculate_subtraction_with_tolerance, compare,
+    current::Current, parse_value, power::Power, resistance::Resistance, Measurement, ParserError,
+    Tolerance,
 };
 
+use serde::Serialize;
 use std::{
-    ops::{Add, Div, Mul, Sub},
+    cmp::Ordering,
+    ops::{Add, Div, Mul, Neg, Sub},
     str::FromStr,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Voltage {
     pub value: f64,
     pub tolerance: Option<Tolerance>,
@@ -30,6 +28,8 @@ impl Default for Voltage {
 }
 
 impl Measurement for Voltage {
+    const NON_NEGATIVE: bool = false;
+
     fn get_nominal_value(&self) -> f64 {
         self.value
     }
@@ -43,69 +43,17 @@ impl Measurement for Voltage {
     }
 }
 
+impl PartialOrd for Voltage {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare(self, other)
+    }
+}
+
 impl FromStr for Voltage {
     type Err = ParserError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let input = input.trim();
-        if input.trim().is_empty() {
-            return Err(ParserError::EmptyInput);
-        }
-
-        match parser::parse_blocks(input) {
-            Ok((input, result)) => {
-                // If there is any remaining unparsed input, it's an error
-                if !input.is_empty() {
-                    return Err(ParserError::IncorrectInput(input.to_string()));
-                }
-
-                let mut value = f64::NAN;
-                let mut tol: Option<Tolerance> = None;
-
-                // Process each parsed block
-                for block in result {
-                    match block {
-                        Block::Number(n) => value = n,
-                        Block::NumberSuffix((n, s)) => value = n * s.coefficient(),
-                        Block::TolMinus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: tt.plus,
-                                    minus: t,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: 0.0,
-                                    minus: t,
-                                })
-                            };
-                        }
-                        Block::TolPlus(t) => {
-                            tol = if let Some(tt) = tol {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: tt.minus,
-                                })
-                            } else {
-                                Some(Tolerance {
-                                    plus: t,
-                                    minus: 0.0,
-                                })
-                            };
-                        }
-                        Block::TolPlusMinus(t) => {
-                            tol = Some(Tolerance { plus: t, minus: t });
-                        }
-                    }
-                }
-
-                Ok(Voltage {
-                    value,
-                    tolerance: tol,
-                })
-            }
-            Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
-        }
+        parse_value(input, |value, tolerance| Voltage { value, tolerance })
     }
 }
 
@@ -142,25 +90,7 @@ impl Div<Current> for Voltage {
         let (value, tol) = calculate_division_with_tolerance(&self, &rhs);
 
         Resistance {
-            value: value,
-            tolerance: tol,
-        }
-    }
-}
-
-impl Div<Power> for Voltage {
-    type Output = Resistance;
-
-    fn div(self, rhs: Power) -> Self::Output {
-        let voltage2 = calculate_multiplication_with_tolerance(&self, &self);
-        let voltage2 = Voltage {
-            value: voltage2.0,
-            tolerance: voltage2.1,
-        };
-        let (value, tol) = calculate_division_with_tolerance(&voltage2, &rhs);
-
-        Resistance {
-            value: value,
+            value,
             tolerance: tol,
         }
     }
@@ -173,7 +103,7 @@ impl Div<Resistance> for Voltage {
         let (value, tol) = calculate_division_with_tolerance(&self, &rhs);
 
         Current {
-            value: value,
+            value,
             tolerance: tol,
         }
     }
@@ -186,12 +116,23 @@ impl Mul<Current> for Voltage {
         let (value, tol) = calculate_multiplication_with_tolerance(&self, &rhs);
 
         Power {
-            value: value,
+            value,
             tolerance: tol,
         }
     }
 }
 
+impl Neg for Voltage {
+    type Output = Voltage;
+
+    fn neg(self) -> Self::Output {
+        Voltage {
+            value: -self.value,
+            tolerance: self.tolerance,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,4 +235,105 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_voltage_signed_parser() {
+        assert_eq!(
+            "-5".parse::<Voltage>(),
+            Ok(Voltage {
+                value: -5.0,
+                tolerance: None
+            })
+        );
+        assert_eq!(
+            "+12".parse::<Voltage>(),
+            Ok(Voltage {
+                value: 12.0,
+                tolerance: None
+            })
+        );
+        assert_eq!(
+            "-5 -10%".parse::<Voltage>(),
+            Ok(Voltage {
+                value: -5.0,
+                tolerance: Some(Tolerance {
+                    plus: 0.0,
+                    minus: 10.0
+                })
+            })
+        );
+        assert_eq!(
+            "-5 +3% -2%".parse::<Voltage>(),
+            Ok(Voltage {
+                value: -5.0,
+                tolerance: Some(Tolerance {
+                    plus: 3.0,
+                    minus: 2.0
+                })
+            })
+        );
+    }
+
+    #[test]
+    fn test_voltage_whitespace_parser() {
+        let expected = Ok(Voltage {
+            value: 10e3,
+            tolerance: Some(Tolerance {
+                plus: 5.0,
+                minus: 5.0,
+            }),
+        });
+
+        assert_eq!(" 10k  5% ".parse::<Voltage>(), expected);
+        assert_eq!("\t10k\t5%\t".parse::<Voltage>(), expected);
+        assert_eq!("10k\u{a0}5%".parse::<Voltage>(), expected);
+        assert_eq!("\u{a0}".parse::<Voltage>(), Err(ParserError::EmptyInput));
+        assert_eq!(" \t ".parse::<Voltage>(), Err(ParserError::EmptyInput));
+    }
+
+    #[test]
+    fn test_voltage_unicode_signs_parser() {
+        assert_eq!("12 ±5%".parse::<Voltage>(), "12 +/-5%".parse::<Voltage>());
+        assert_eq!("12 −5%".parse::<Voltage>(), "12 -5%".parse::<Voltage>());
+        assert_eq!(
+            "−5 +3% −2%".parse::<Voltage>(),
+            "-5 +3% -2%".parse::<Voltage>()
+        );
+        assert!("12 ±5%".parse::<Voltage>().is_ok());
+    }
+
+    #[test]
+    fn test_voltage_limits_parser() {
+        let voltage = "typ 12 min 11.4 max 12.6".parse::<Voltage>().unwrap();
+        assert_eq!(voltage.get_value_nom(), "12.00V");
+        assert_eq!(voltage.get_tol_percent_plus(), "5.00%");
+        assert_eq!(voltage.get_tol_percent_minus(), "-5.00%");
+
+        let tol = voltage.tolerance.unwrap();
+        assert!((tol.plus - 5.0).abs() < 1e-9);
+        assert!((tol.minus - 5.0).abs() < 1e-9);
+
+        assert_eq!("max 12.6 typ 12 min 11.4".parse::<Voltage>(), Ok(voltage));
+        assert_eq!("typ 3.3".parse::<Voltage>().unwrap().tolerance, None);
+        assert!("min 11.4 max 12.6".parse::<Voltage>().is_err());
+        assert!(matches!(
+            "typ 0 max 1".parse::<Voltage>(),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_voltage_within() {
+        let spec = "5 5%".parse::<Voltage>().unwrap();
+
+        assert!("4.9".parse::<Voltage>().unwrap().within(&spec));
+        assert!("5.25".parse::<Voltage>().unwrap().within(&spec));
+        assert!(!"4.7".parse::<Voltage>().unwrap().within(&spec));
+        assert!(!"5.3".parse::<Voltage>().unwrap().within(&spec));
+
+        // without tolerance only the nominal itself matches
+        let exact = "5".parse::<Voltage>().unwrap();
+        assert!("5".parse::<Voltage>().unwrap().within(&exact));
+        assert!(!"4.9".parse::<Voltage>().unwrap().within(&exact));
+    }
 }
@@ -0,0 +1,142 @@
+//! # Divider from Taps
+//!
+//! Creates the legs of a divider between two rails that produce the requested tap voltages
+//! within a total resistance. All legs carry the same current
+//! `I = (Utop - Ubottom) / Rtotal`, so the resistance of a leg is the voltage drop across it
+//! divided by that current.
+
+use std::fmt;
+
+use crate::i18n::{t, Key};
+
+/// A generated leg: the voltage at its top node and its exact resistance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneratedLeg {
+    pub voltage: f64,
+    pub resistance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorError {
+    RailsOrder,
+    NoTaps,
+    TapsNotMonotonic,
+    TapOutsideRails,
+    TotalNotPositive,
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            GeneratorError::RailsOrder => Key::RailsOrder,
+            GeneratorError::NoTaps => Key::NoTaps,
+            GeneratorError::TapsNotMonotonic => Key::TapsNotMonotonic,
+            GeneratorError::TapOutsideRails => Key::TapOutsideRails,
+            GeneratorError::TotalNotPositive => Key::TotalNotPositive,
+        };
+        write!(f, "{}", t(key))
+    }
+}
+
+/// Legs from the top rail down, one per tap plus the top one. The taps may be listed in
+/// either direction but must be strictly monotonic and lie strictly between the rails.
+pub fn generate(
+    top: f64,
+    bottom: f64,
+    taps: &[f64],
+    total: f64,
+) -> Result<Vec<GeneratedLeg>, GeneratorError> {
+    if top <= bottom {
+        return Err(GeneratorError::RailsOrder);
+    }
+    if total <= 0.0 {
+        return Err(GeneratorError::TotalNotPositive);
+    }
+    if taps.is_empty() {
+        return Err(GeneratorError::NoTaps);
+    }
+
+    let ascending = taps.windows(2).all(|w| w[0] < w[1]);
+    let descending = taps.windows(2).all(|w| w[0] > w[1]);
+    if !ascending && !descending {
+        return Err(GeneratorError::TapsNotMonotonic);
+    }
+    if taps.iter().any(|&tap| tap <= bottom || tap >= top) {
+        return Err(GeneratorError::TapOutsideRails);
+    }
+
+    let mut nodes = vec![top];
+    if ascending {
+        nodes.extend(taps.iter().rev());
+    } else {
+        nodes.extend(taps);
+    }
+    nodes.push(bottom);
+
+    let current = (top - bottom) / total;
+
+    Ok(nodes
+        .windows(2)
+        .map(|w| GeneratedLeg {
+            voltage: w[0],
+            resistance: (w[0] - w[1]) / current,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_legs(legs: &[GeneratedLeg], expected: &[(f64, f64)]) {
+        assert_eq!(legs.len(), expected.len());
+        for (leg, (voltage, resistance)) in legs.iter().zip(expected) {
+            assert_eq!(leg.voltage, *voltage);
+            assert!((leg.resistance - resistance).abs() < 1e-6, "{:?}", leg);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let legs = generate(5.0, 0.0, &[1.0, 2.5, 3.3], 100e3).unwrap();
+        assert_legs(&legs, &[(5.0, 34e3), (3.3, 16e3), (2.5, 30e3), (1.0, 20e3)]);
+
+        // порядок отводов не важен
+        assert_eq!(generate(5.0, 0.0, &[3.3, 2.5, 1.0], 100e3).unwrap(), legs);
+    }
+
+    #[test]
+    fn test_generate_bottom_rail() {
+        let legs = generate(12.0, -12.0, &[0.0], 24e3).unwrap();
+        assert_legs(&legs, &[(12.0, 12e3), (0.0, 12e3)]);
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        assert_eq!(
+            generate(0.0, 5.0, &[1.0], 10e3),
+            Err(GeneratorError::RailsOrder)
+        );
+        assert_eq!(
+            generate(5.0, 0.0, &[1.0], 0.0),
+            Err(GeneratorError::TotalNotPositive)
+        );
+        assert_eq!(generate(5.0, 0.0, &[], 10e3), Err(GeneratorError::NoTaps));
+        assert_eq!(
+            generate(5.0, 0.0, &[1.0, 3.3, 2.5], 10e3),
+            Err(GeneratorError::TapsNotMonotonic)
+        );
+        assert_eq!(
+            generate(5.0, 0.0, &[1.0, 1.0], 10e3),
+            Err(GeneratorError::TapsNotMonotonic)
+        );
+        assert_eq!(
+            generate(5.0, 0.0, &[1.0, 5.0], 10e3),
+            Err(GeneratorError::TapOutsideRails)
+        );
+        assert_eq!(
+            generate(5.0, 0.0, &[-1.0], 10e3),
+            Err(GeneratorError::TapOutsideRails)
+        );
+    }
+}
@@ -1,29 +1,230 @@
+mod generator;
+mod schematic;
+mod supply_sweep;
+mod templates;
+
+use crate::eseries::ESeries;
+use crate::i18n::{t, Key};
+use crate::plausibility;
+use crate::presets::{self, Kind, Preset};
+use crate::report::{self, Bounds};
+use crate::result_rows::{self, ResultRow};
+use crate::snapshot::Snapshot;
+use crate::status::SceneStatus;
+use crate::style::{self, TextStyle};
+use crate::sweep::{self, Sweep};
 use crate::types::{current::Current, power::Power, resistance::Resistance, voltage::Voltage};
-use crate::types::{Measurement, ParserError};
-use iced::widget::{Button, Column, Container, Row, Rule, Scrollable, Text, TextInput};
-use iced::{Color, Element, Fill};
+use crate::types::{default_tolerance, Dim, Measurement, ParserError, Tolerance};
+use crate::widgets::measurement_input::{Event, MeasurementInput};
+use crate::widgets::table::{Cell, CellCopy, Table, TableRow};
+use crate::widgets::{eseries_check, row_menu};
+use iced::padding;
+use iced::widget::{
+    button, checkbox, pick_list, Button, Column, Container, Row, Scrollable, Text, TextInput,
+};
+use iced::{Alignment, Element, Fill};
+use serde::Serialize;
+
+use generator::{GeneratedLeg, GeneratorError};
+pub use supply_sweep::{SweepResult, TapSweep};
+use templates::{Template, TEMPLATES};
+
+// ограничение числа плеч: каждое нажатие клавиши пересчитывает весь делитель
+const LEGS_MIN: usize = 1;
+const LEGS_MAX: usize = 64;
+const LEGS_DEFAULT: usize = 2;
+
+thread_local! {
+    // таблица результатов тоже называет ячейки Cell
+    static DEFAULT_LEGS: std::cell::Cell<usize> = const { std::cell::Cell::new(LEGS_DEFAULT) };
+}
+
+/// Sets the number of legs a new divider starts with, kept within the leg limits.
+pub fn set_default_legs(legs: usize) {
+    DEFAULT_LEGS.with(|l| l.set(legs.clamp(LEGS_MIN, LEGS_MAX)));
+}
+
+pub fn default_legs() -> usize {
+    DEFAULT_LEGS.with(|l| l.get())
+}
+
+/// Parses a leg count for [`set_default_legs`], [`LEGS_DEFAULT`] if the input is empty.
+/// `None` on anything but a whole number within the leg limits.
+pub fn parse_leg_count(input: &str) -> Option<usize> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Some(LEGS_DEFAULT);
+    }
+
+    input
+        .parse::<usize>()
+        .ok()
+        .filter(|legs| (LEGS_MIN..=LEGS_MAX).contains(legs))
+}
+
+// допуск на округление значений, переписанных в поля ввода при масштабировании
+const CURRENT_BUDGET_EPSILON: f64 = 1e-3;
+
+// относительная погрешность вычислений при сверке введённых плеч с током
+const CONSISTENCY_EPSILON: f64 = 1e-9;
+
+/// Rated power of a leg with the field left empty, W: a common 0805 or 1/8W resistor.
+const DEFAULT_RATED_POWER: f64 = 0.125;
 
 #[derive(Debug, Clone)]
 pub struct VoltageDivider {
     legs: Vec<Leg>,
+    current_max_raw: String,
+    current_max: Result<Current, ParserError>,
+    ratio_mode: bool,
+    ratio: RatioInput,
+    generator_mode: bool,
+    generator: GeneratorInput,
+    dirty: bool,
+    pinned: Option<Vec<Snapshot>>,
+    // пошаговое решение: формулы с подставленными значениями
+    show_work: bool,
+    steps: Vec<String>,
+    schematic: bool,
+    supply_sweep: SupplySweepInput,
+    // список ближайших стандартных значений раскрыт
+    standard_values: bool,
+    // открыт выбор видимых строк таблицы
+    rows_menu: bool,
+    // подписи плеч окрашены по нагрузке относительно номинальной мощности
+    heat_map: bool,
 }
 
+/// A worked example for new users: resistance and voltage of every leg, 10k over 10k on a
+/// 9V battery.
+const EXAMPLE: [(&str, &str); 2] = [("10k", "9"), ("10k", "")];
+
 impl Default for VoltageDivider {
     fn default() -> Self {
-        let legs = vec![Leg::default(), Leg::default()];
+        let legs = vec![Leg::default(); default_legs()];
+
+        Self {
+            legs,
+            current_max_raw: String::new(),
+            current_max: Err(ParserError::EmptyInput),
+            ratio_mode: false,
+            ratio: RatioInput::default(),
+            generator_mode: false,
+            generator: GeneratorInput::default(),
+            dirty: false,
+            pinned: None,
+            show_work: false,
+            steps: Vec::new(),
+            schematic: false,
+            supply_sweep: SupplySweepInput::default(),
+            standard_values: false,
+            rows_menu: false,
+            heat_map: false,
+        }
+    }
+}
+
+/// Inputs of the "divider from ratio" mode: `Uout = ratio * Uin` over a total resistance.
+#[derive(Debug, Clone)]
+struct RatioInput {
+    ratio_raw: String,
+    ratio: Result<f64, ParserError>,
+    total_raw: String,
+    total: Result<Resistance, ParserError>,
+    series: ESeries,
+}
+
+impl Default for RatioInput {
+    fn default() -> Self {
+        Self {
+            ratio_raw: String::new(),
+            ratio: Err(ParserError::EmptyInput),
+            total_raw: String::new(),
+            total: Err(ParserError::EmptyInput),
+            series: ESeries::default(),
+        }
+    }
+}
+
+/// Inputs of the "divider from taps" mode: the rails, the tap voltages and the total
+/// resistance the legs are created for.
+#[derive(Debug, Clone)]
+struct GeneratorInput {
+    top_raw: String,
+    top: Result<Voltage, ParserError>,
+    bottom_raw: String,
+    bottom: Result<Voltage, ParserError>,
+    taps_raw: String,
+    taps: Result<Vec<f64>, ParserError>,
+    total_raw: String,
+    total: Result<Resistance, ParserError>,
+    series: ESeries,
+}
+
+impl Default for GeneratorInput {
+    fn default() -> Self {
+        Self {
+            top_raw: String::new(),
+            top: Err(ParserError::EmptyInput),
+            bottom_raw: String::new(),
+            bottom: Err(ParserError::EmptyInput),
+            taps_raw: String::new(),
+            taps: Err(ParserError::EmptyInput),
+            total_raw: String::new(),
+            total: Err(ParserError::EmptyInput),
+            series: ESeries::default(),
+        }
+    }
+}
 
-        Self { legs: legs }
+impl GeneratorInput {
+    /// Voltage of the bottom rail, ground if the field is empty.
+    fn bottom(&self) -> Option<f64> {
+        match &self.bottom {
+            Ok(voltage) => Some(voltage.get_nominal_value()),
+            Err(ParserError::EmptyInput) => Some(0.0),
+            Err(ParserError::IncorrectInput(_)) => None,
+        }
+    }
+}
+
+/// Sweep of the supply voltage of the top leg and the tap voltages at every point.
+#[derive(Debug, Clone)]
+struct SupplySweepInput {
+    enabled: bool,
+    raw: String,
+    sweep: Result<Sweep, ParserError>,
+    result: Option<SweepResult>,
+}
+
+impl Default for SupplySweepInput {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            raw: String::new(),
+            sweep: Err(ParserError::EmptyInput),
+            result: None,
+        }
     }
 }
 
+/// A divider leg: the voltage at its top node, the resistance down to the next leg and the
+/// current and power of that resistance.
 #[derive(Debug, Clone)]
-struct Leg {
+pub struct Leg {
     resistance_raw: String,
     voltage_raw: String,
-    voltage: Result<Voltage, ParserError>,
-    current: Result<Current, ParserError>,
-    resistance: Result<Resistance, ParserError>,
-    power: Result<Power, ParserError>,
+    pub voltage: Result<Voltage, ParserError>,
+    pub current: Result<Current, ParserError>,
+    pub resistance: Result<Resistance, ParserError>,
+    pub power: Result<Power, ParserError>,
+    rated_power_raw: String,
+    rated_power: Result<Power, ParserError>,
+    // предупреждение о неправдоподобном вводе
+    warning: Option<String>,
+    // закреплённые поля: только для чтения, но участвуют в расчёте
+    resistance_locked: bool,
+    voltage_locked: bool,
 }
 
 impl Default for Leg {
@@ -35,6 +236,77 @@ impl Default for Leg {
             current: Err(ParserError::EmptyInput),
             resistance: Err(ParserError::EmptyInput),
             power: Err(ParserError::EmptyInput),
+            rated_power_raw: String::new(),
+            rated_power: Err(ParserError::EmptyInput),
+            warning: None,
+            resistance_locked: false,
+            voltage_locked: false,
+        }
+    }
+}
+
+impl Leg {
+    fn new(resistance_raw: String, voltage_raw: String) -> Self {
+        Self {
+            resistance: resistance_raw.parse::<Resistance>(),
+            voltage: voltage_raw.parse::<Voltage>(),
+            resistance_raw,
+            voltage_raw,
+            ..Leg::default()
+        }
+    }
+
+    /// Rated power of the resistor, [`DEFAULT_RATED_POWER`] if the field is empty and `None`
+    /// if it does not parse.
+    fn rated_power(&self) -> Option<f64> {
+        match &self.rated_power {
+            Ok(power) => Some(power.get_nominal_value()),
+            Err(ParserError::EmptyInput) => Some(DEFAULT_RATED_POWER),
+            Err(ParserError::IncorrectInput(_)) => None,
+        }
+    }
+
+    /// Worst-case power over the tolerance band against the rated power, `1.0` at the
+    /// rating. `None` while the power is not solved or the rating is wrong.
+    fn power_ratio(&self) -> Option<f64> {
+        let power = self.power.as_ref().ok()?;
+        let rated = self.rated_power().filter(|rated| *rated > 0.0)?;
+        let worst = power.min_value().abs().max(power.max_value().abs());
+
+        Some(worst / rated)
+    }
+
+    fn is_locked(&self, field: LegField) -> bool {
+        match field {
+            LegField::Resistance => self.resistance_locked,
+            LegField::Voltage => self.voltage_locked,
+        }
+    }
+}
+
+/// Input field of a leg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegField {
+    Resistance,
+    Voltage,
+}
+
+impl LegField {
+    /// Name of the input widget of the field in leg `leg`, see [`crate::focus`].
+    pub fn input_id(self, leg: usize) -> String {
+        let field = match self {
+            LegField::Resistance => "resistance",
+            LegField::Voltage => "voltage",
+        };
+
+        format!("voltage_divider.leg{}.{}", leg, field)
+    }
+
+    /// Kind of the presets offered next to the field.
+    fn kind(self) -> Kind {
+        match self {
+            LegField::Resistance => Kind::Resistance,
+            LegField::Voltage => Kind::Voltage,
         }
     }
 }
@@ -43,290 +315,713 @@ impl Default for Leg {
 pub enum Message {
     InputVoltageChanged(usize, String),
     InputResistanceChanged(usize, String),
+    InputCurrentMaxChanged(String),
+    ClearLegField(usize, LegField),
+    /// A prefix picked next to a leg field, see [`Dim::append_to`].
+    LegPrefixSelected(usize, LegField, Dim),
+    /// A preset picked next to a leg field, its value replaces the entered text.
+    LegPresetSelected(usize, LegField, Preset),
+    LegLockToggled(usize, LegField),
     LegAdd,
     LegDelete(usize),
+    ScaleResistors,
+    RatioModeToggled(bool),
+    InputRatioChanged(String),
+    InputTotalResistanceChanged(String),
+    SeriesSelected(ESeries),
+    GeneratorModeToggled(bool),
+    InputTopVoltageChanged(String),
+    InputBottomVoltageChanged(String),
+    InputTapsChanged(String),
+    InputGeneratorTotalChanged(String),
+    GeneratorSeriesSelected(ESeries),
+    CreateLegs,
+    ShowWorkToggled(bool),
+    SchematicToggled(bool),
+    HeatMapToggled(bool),
+    LegRatedPowerChanged(usize, String),
+    SupplySweepToggled(bool),
+    InputSupplySweepChanged(String),
+    StandardValuesToggled,
+    RowsMenuToggled,
+    RowVisibilityToggled(ResultRow),
+    PinResult,
+    Unpin,
+    /// Replaces the scene with [`EXAMPLE`] solved.
+    LoadExample,
+    /// Replaces the scene with the legs of the template solved.
+    TemplateSelected(Template),
+    /// Right click on a result cell. Handled by the application: copies the cell as shown,
+    /// or the exact number with Shift held.
+    CopyCell(CellCopy),
+    /// Enter in a leg field. Handled by the application: focuses
+    /// [`VoltageDivider::next_field`].
+    FieldSubmitted(usize, LegField),
+}
+
+impl Message {
+    /// Message of `event` of the input of `field` in leg `leg`.
+    fn from_leg_input(leg: usize, field: LegField, event: Event) -> Message {
+        match event {
+            Event::Input(s) => match field {
+                LegField::Resistance => Message::InputResistanceChanged(leg, s),
+                LegField::Voltage => Message::InputVoltageChanged(leg, s),
+            },
+            Event::Submit => Message::FieldSubmitted(leg, field),
+            Event::Clear => Message::ClearLegField(leg, field),
+            Event::Prefix(dim) => Message::LegPrefixSelected(leg, field, dim),
+            Event::Preset(preset) => Message::LegPresetSelected(leg, field, preset),
+            Event::LockToggled => Message::LegLockToggled(leg, field),
+            // у полей плеч нет кнопки отчёта об ошибке
+            Event::CopyErrorReport => unreachable!("leg inputs have no error report"),
+        }
+    }
+
+    /// Entered text carried by the message, e.g. to cut a paste over the input limit.
+    pub fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Message::InputVoltageChanged(_, s)
+            | Message::InputResistanceChanged(_, s)
+            | Message::LegRatedPowerChanged(_, s)
+            | Message::InputCurrentMaxChanged(s)
+            | Message::InputRatioChanged(s)
+            | Message::InputTotalResistanceChanged(s)
+            | Message::InputTopVoltageChanged(s)
+            | Message::InputBottomVoltageChanged(s)
+            | Message::InputTapsChanged(s)
+            | Message::InputGeneratorTotalChanged(s)
+            | Message::InputSupplySweepChanged(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// `true` for a keystroke in an input field, see [`VoltageDivider::store_text`].
+    pub fn is_text_input(&self) -> bool {
+        matches!(
+            self,
+            Message::InputVoltageChanged(..)
+                | Message::InputResistanceChanged(..)
+                | Message::LegRatedPowerChanged(..)
+                | Message::InputCurrentMaxChanged(_)
+                | Message::InputRatioChanged(_)
+                | Message::InputTotalResistanceChanged(_)
+                | Message::InputTopVoltageChanged(_)
+                | Message::InputBottomVoltageChanged(_)
+                | Message::InputTapsChanged(_)
+                | Message::InputGeneratorTotalChanged(_)
+        )
+    }
 }
 
 impl VoltageDivider {
     pub fn title(&self) -> String {
-        String::from("Voltage Divider")
+        String::from(t(Key::VoltageDivider))
     }
 
-    pub fn view(&self) -> Element<Message> {
-        Column::new()
-            .push(self.view_form())
+    /// The scene with the resistance and voltage of every leg entered as if typed, e.g.
+    /// [`EXAMPLE`].
+    fn with_legs(legs: &[(&str, &str)]) -> Self {
+        let mut divider = Self {
+            legs: vec![Leg::default(); legs.len()],
+            ..Self::default()
+        };
+        for (id, (resistance, voltage)) in legs.iter().enumerate() {
+            divider.update(Message::InputResistanceChanged(id, resistance.to_string()));
+            divider.update(Message::InputVoltageChanged(id, voltage.to_string()));
+        }
+
+        divider
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mode = checkbox(t(Key::RatioMode), self.ratio_mode)
+            .on_toggle(Message::RatioModeToggled)
+            .size(16)
+            .text_size(14);
+        let generator = checkbox(t(Key::GeneratorMode), self.generator_mode)
+            .on_toggle(Message::GeneratorModeToggled)
+            .size(16)
+            .text_size(14);
+        let example = button(Text::new(t(Key::LoadExample)).size(13))
+            .on_press(Message::LoadExample)
+            .style(button::secondary);
+        let template = pick_list(TEMPLATES, None::<Template>, Message::TemplateSelected)
+            .placeholder(t(Key::Template))
+            .text_size(13);
+        let column = Column::new()
+            .push(
+                Row::new()
+                    .push(Column::new().push(mode).push(generator).width(Fill))
+                    .push(template)
+                    .push(example)
+                    .spacing(5)
+                    .align_y(Alignment::Center),
+            )
+            .padding([5, 0]);
+
+        if self.ratio_mode {
+            return column.push(self.view_ratio()).into();
+        }
+        if self.generator_mode {
+            return column.push(self.view_generator()).into();
+        }
+
+        let mut column = column.push(self.view_form());
+
+        if self.is_reverse_current() {
+            let note = Text::new(t(Key::ReverseCurrent))
+                .style(style::warning_text)
+                .size(12);
+            column = column.push(note);
+        }
+        let inconsistent = self.inconsistent_legs();
+        if !inconsistent.is_empty() {
+            let legs: Vec<_> = inconsistent.iter().map(usize::to_string).collect();
+            let note = Text::new(format!("{} {}", t(Key::InconsistentLegs), legs.join(", ")))
+                .style(style::warning_text)
+                .size(12);
+            column = column.push(note);
+        }
+
+        column
             .push(self.view_result())
+            .push(eseries_check::view(
+                &self.calculated_resistances(),
+                self.standard_values,
+                Message::StandardValuesToggled,
+            ))
+            .push(self.view_schematic())
+            .push(self.view_work())
+            .push(self.view_supply_sweep())
+            .into()
+    }
+
+    /// Supply sweep checkbox and, when it is on, the sweep of U1 and the tap voltages at
+    /// every point with their extremes over the sweep.
+    fn view_supply_sweep(&self) -> Element<'_, Message> {
+        let input = &self.supply_sweep;
+        let toggle = checkbox(t(Key::SupplySweep), input.enabled)
+            .on_toggle(Message::SupplySweepToggled)
+            .size(16)
+            .text_size(14);
+        let mut column = Column::new().push(toggle).padding([5, 0]).spacing(5);
+        if !input.enabled {
+            return column.into();
+        }
+
+        let hint = match &input.sweep {
+            Err(ParserError::IncorrectInput(e)) => e.clone(),
+            _ => t(Key::ExampleSupplySweep).to_string(),
+        };
+        column = column
+            .push(
+                TextInput::new("", &input.raw)
+                    .on_input(Message::InputSupplySweepChanged)
+                    .size(15),
+            )
+            .push(Text::new(hint).size(12).style(style::hint_text));
+        if matches!(&input.sweep, Ok(sweep) if sweep.truncated) {
+            column = column.push(
+                Text::new(format!("{} {}", t(Key::SweepTruncated), sweep::MAX_POINTS))
+                    .size(12)
+                    .style(style::warning_text),
+            );
+        }
+
+        let Some(result) = &input.result else {
+            if input.sweep.is_ok() {
+                column = column.push(Text::new(t(Key::SupplySweepUnsolved)).size(13));
+            }
+            return column.into();
+        };
+
+        let volts = |value: f64| -> Cell {
+            Voltage {
+                value,
+                tolerance: None,
+            }
+            .get_value_nom()
             .into()
+        };
+        let header = std::iter::once("U1".to_string())
+            .chain(result.taps.iter().map(|tap| format!("U{}", tap.leg)))
+            .collect();
+        let mut rows: Vec<TableRow<Message>> = result
+            .supply
+            .iter()
+            .enumerate()
+            .map(|(point, supply)| {
+                let taps = result
+                    .taps
+                    .iter()
+                    .map(|tap| volts(tap.voltages[point].nominal));
+                TableRow::new(std::iter::once(volts(*supply)).chain(taps).collect())
+            })
+            .collect();
+        // крайние значения за всю развёртку с допусками
+        rows.push(TableRow::new(
+            std::iter::once(t(Key::ValueMin).into())
+                .chain(result.taps.iter().map(|tap| volts(tap.min)))
+                .collect(),
+        ));
+        rows.push(TableRow::new(
+            std::iter::once(t(Key::ValueMax).into())
+                .chain(result.taps.iter().map(|tap| volts(tap.max)))
+                .collect(),
+        ));
+
+        column.push(Table::new(header).rows(rows).view()).into()
     }
 
-    fn view_result(&self) -> Element<Message> {
-        fn format_measurement<T: Measurement, E>(data: Result<T, E>) -> (String, String, String) {
-            match data {
-                Ok(measurement) => (
-                    measurement.get_value_nom(),
-                    measurement.get_value_min(),
-                    measurement.get_value_max(),
-                ),
-                Err(_) => ("N/A".to_string(), "N/A".to_string(), "N/A".to_string()),
+    /// Resistances of the legs that are calculated rather than entered, for the standard
+    /// value check.
+    fn calculated_resistances(&self) -> Vec<(String, f64)> {
+        self.legs
+            .iter()
+            .enumerate()
+            .filter(|(_, leg)| leg.resistance_raw.trim().is_empty())
+            .filter_map(|(id, leg)| {
+                let r = leg.resistance.as_ref().ok()?;
+                Some((format!("R{}", id + 1), r.get_nominal_value()))
+            })
+            .collect()
+    }
+
+    /// Tap voltages over the supply sweep, `None` while it is off or does not solve.
+    pub fn supply_sweep(&self) -> Option<&SweepResult> {
+        self.supply_sweep.result.as_ref()
+    }
+
+    fn calculating_supply_sweep(&self) -> Option<SweepResult> {
+        match &self.supply_sweep.sweep {
+            Ok(sweep) if self.supply_sweep.enabled => {
+                supply_sweep::sweep_supply(&self.legs, &sweep.points)
             }
+            _ => None,
+        }
+    }
+
+    /// "Show work" checkbox and, when it is on, the formulas of the solution.
+    /// "Schematic" checkbox and, when it is on, the resistor ladder with the solved values.
+    fn view_schematic(&self) -> Element<'_, Message> {
+        let toggle = checkbox(t(Key::Schematic), self.schematic)
+            .on_toggle(Message::SchematicToggled)
+            .size(16)
+            .text_size(14);
+        let column = Column::new().push(toggle).padding([5, 0]);
+
+        if self.schematic {
+            column.push(schematic::view(&self.legs)).into()
+        } else {
+            column.into()
         }
-        fn format_tol<T: Measurement, E>(data: Result<T, E>) -> (String, String, String, String) {
-            match data {
-                Ok(measurement) => (
-                    measurement.get_tol_value_plus(),
-                    measurement.get_tol_value_minus(),
-                    measurement.get_tol_percent_plus(),
-                    measurement.get_tol_percent_minus(),
-                ),
-                Err(_) => (
-                    "N/A".to_string(),
-                    "N/A".to_string(),
-                    "N/A".to_string(),
-                    "N/A".to_string(),
-                ),
+    }
+
+    fn view_work(&self) -> Element<'_, Message> {
+        let toggle = checkbox(t(Key::ShowWork), self.show_work)
+            .on_toggle(Message::ShowWorkToggled)
+            .size(16)
+            .text_size(14);
+        let mut column = Column::new().push(toggle).padding([5, 0]).spacing(2);
+
+        if self.show_work {
+            for line in self.steps() {
+                column = column.push(Text::new(line).size(13));
             }
         }
 
-        let mut data: Vec<(String, Vec<Vec<String>>)> = Vec::new();
+        column.into()
+    }
+
+    /// Legs from the top, for black-box tests that drive the scene by messages.
+    #[cfg(feature = "test-internals")]
+    pub fn legs(&self) -> &[Leg] {
+        &self.legs
+    }
+
+    /// Steps of the last solution: the series current, then the calculated values of every
+    /// leg from the bottom up.
+    pub fn steps(&self) -> &[String] {
+        &self.steps
+    }
+
+    fn view_result(&self) -> Element<'_, Message> {
+        let live = self.snapshot();
+        let mut data: Vec<(String, Vec<Vec<Cell>>)> = Vec::new();
         for (id, leg) in self.legs.iter().enumerate() {
-            let (voltage_nom, voltage_min, voltage_max) = format_measurement(leg.voltage.clone());
-            let (voltage_tol_plus, voltage_tol_minus, voltage_tol_plus_p, voltage_tol_minus_p) =
-                format_tol(leg.voltage.clone());
-
-            let (current_nom, current_min, current_max) = format_measurement(leg.current.clone());
-            let (current_tol_plus, current_tol_minus, current_tol_plus_p, current_tol_minus_p) =
-                format_tol(leg.current.clone());
-
-            let (resistance_nom, resistance_min, resistance_max) =
-                format_measurement(leg.resistance.clone());
-            let (
-                resistance_tol_plus,
-                resistance_tol_minus,
-                resistance_tol_plus_p,
-                resistance_tol_minus_p,
-            ) = format_tol(leg.resistance.clone());
-
-            let (power_nom, power_min, power_max) = format_measurement(leg.power.clone());
-            let (power_tol_plus, power_tol_minus, power_tol_plus_p, power_tol_minus_p) =
-                format_tol(leg.power.clone());
-
-            let iter_data: Vec<Vec<String>> = vec![
-                vec![
-                    "Value nom".to_string(),
-                    voltage_nom,
-                    current_nom,
-                    resistance_nom,
-                    power_nom,
-                ],
-                vec![
-                    "Value max".to_string(),
-                    voltage_max,
-                    current_max,
-                    resistance_max,
-                    power_max,
-                ],
-                vec![
-                    "Value min".to_string(),
-                    voltage_min,
-                    current_min,
-                    resistance_min,
-                    power_min,
-                ],
-                vec![
-                    "Tol plus".to_string(),
-                    voltage_tol_plus,
-                    current_tol_plus,
-                    resistance_tol_plus,
-                    power_tol_plus,
-                ],
-                vec![
-                    "Tol minus".to_string(),
-                    voltage_tol_minus,
-                    current_tol_minus,
-                    resistance_tol_minus,
-                    power_tol_minus,
-                ],
-                vec![
-                    "Tol plus, %".to_string(),
-                    voltage_tol_plus_p,
-                    current_tol_plus_p,
-                    resistance_tol_plus_p,
-                    power_tol_plus_p,
-                ],
-                vec![
-                    "Tol minus, %".to_string(),
-                    voltage_tol_minus_p,
-                    current_tol_minus_p,
-                    resistance_tol_minus_p,
-                    power_tol_minus_p,
-                ],
-            ];
+            let mut iter_data = result_rows::table_rows(&[
+                result_rows::measurement_rows(&leg.voltage),
+                result_rows::measurement_rows(&leg.current),
+                result_rows::measurement_rows(&leg.resistance),
+                result_rows::measurement_rows(&leg.power),
+            ]);
+            // у добавленного после закрепления плеча нет закреплённых значений
+            if let Some(pinned) = self.pinned.as_ref().and_then(|p| p.get(id)) {
+                iter_data.extend(
+                    pinned
+                        .table_rows(&live[id])
+                        .map(|row| row.into_iter().map(Cell::from).collect()),
+                );
+            }
             let collect = (format!("R{}", id + 1), iter_data);
 
             data.push(collect);
         }
 
-        self.view_table(data).into()
-    }
-
-    fn view_table(&self, table_data: Vec<(String, Vec<Vec<String>>)>) -> Element<Message> {
-        const BORDER_WIDTH: u16 = 0;
-        const FIRST_COLUMN_WIDTH: u16 = 110;
-
-        fn create_text_cell(content: String) -> Element<'static, Message> {
-            let text = Text::new(content).width(Fill);
-
-            Container::new(text).padding(5).into()
-        }
-
-        fn create_table_row(
-            cell_1: String,
-            cell_2: String,
-            cell_3: String,
-            cell_4: String,
-            cell_5: String,
-        ) -> Element<'static, Message> {
-            Row::new()
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(Container::new(create_text_cell(cell_1)).width(FIRST_COLUMN_WIDTH))
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(Text::new("").width(1)) // Double border line
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(create_text_cell(cell_2))
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(create_text_cell(cell_3))
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(create_text_cell(cell_4))
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(create_text_cell(cell_5))
-                .push(Rule::vertical(BORDER_WIDTH))
-                .height(30)
-                .width(Fill)
-                .into()
+        let pin = Button::new(Text::new(t(Key::PinResult)).size(14)).on_press(Message::PinResult);
+        let mut buttons = Row::new().push(pin).spacing(5);
+        if self.pinned.is_some() {
+            let unpin = Button::new(Text::new(t(Key::Unpin)).size(14))
+                .on_press(Message::Unpin)
+                .style(button::secondary);
+            buttons = buttons.push(unpin);
         }
 
-        let mut table_sections = Vec::new();
-        // header
-        let header = Row::new()
-            .push(Rule::vertical(BORDER_WIDTH))
-            .push(Container::new(create_text_cell("".to_string())).width(50 + FIRST_COLUMN_WIDTH))
-            .push(Rule::vertical(BORDER_WIDTH))
-            .push(Text::new("").width(1)) // Double border line
-            .push(Rule::vertical(BORDER_WIDTH))
-            .push(create_text_cell("Voltage".to_string()))
-            .push(Rule::vertical(BORDER_WIDTH))
-            .push(create_text_cell("Current".to_string()))
-            .push(Rule::vertical(BORDER_WIDTH))
-            .push(create_text_cell("Resistance".to_string()))
-            .push(Rule::vertical(BORDER_WIDTH))
-            .push(create_text_cell("Power".to_string()))
-            .push(Rule::vertical(BORDER_WIDTH))
-            .push(Text::new("").width(15)) // padding for Scrollable
-            .height(30)
-            .width(Fill)
-            .into();
-        table_sections.push(Rule::horizontal(BORDER_WIDTH).into());
-        table_sections.push(header);
-
-        // data
-        for (section_label, rows) in table_data {
-            let mut row_elements = Vec::new();
-
-            for row_cells in rows {
-                let row = create_table_row(
-                    row_cells[0].clone(),
-                    row_cells[1].clone(),
-                    row_cells[2].clone(),
-                    row_cells[3].clone(),
-                    row_cells[4].clone(),
+        let rows_menu = row_menu::view(
+            result_rows::hidden_rows().voltage_divider,
+            self.rows_menu,
+            Message::RowsMenuToggled,
+            Message::RowVisibilityToggled,
+        );
+
+        Column::new()
+            .push(buttons)
+            .push(rows_menu)
+            .push(self.view_heat_map())
+            .push(self.view_table(data))
+            .into()
+    }
+
+    /// "Power color map" checkbox and, when it is on, the rated power of every leg.
+    fn view_heat_map(&self) -> Element<'_, Message> {
+        let toggle = checkbox(t(Key::PowerColorMap), self.heat_map)
+            .on_toggle(Message::HeatMapToggled)
+            .size(16)
+            .text_size(14);
+        let mut column = Column::new().push(toggle).padding([5, 0]).spacing(5);
+        if !self.heat_map {
+            return column.into();
+        }
+
+        let mut inputs = Row::new()
+            .push(Text::new(t(Key::RatedPower)).size(14))
+            .spacing(5)
+            .align_y(Alignment::Center);
+        for (id, leg) in self.legs.iter().enumerate() {
+            inputs = inputs
+                .push(Text::new(format!("R{}", id + 1)).size(14))
+                .push(
+                    TextInput::new("0.125W", &leg.rated_power_raw)
+                        .on_input(move |s| Message::LegRatedPowerChanged(id, s))
+                        .size(14)
+                        .width(70),
+                );
+        }
+        column = column.push(inputs);
+
+        for (id, leg) in self.legs.iter().enumerate() {
+            if let Err(ParserError::IncorrectInput(e)) = &leg.rated_power {
+                column = column.push(
+                    Text::new(format!("R{}: {}", id + 1, e))
+                        .size(12)
+                        .style(style::warning_text),
                 );
-                row_elements.push(Rule::horizontal(BORDER_WIDTH).into());
-                row_elements.push(row);
             }
-            row_elements.push(Rule::horizontal(BORDER_WIDTH).into());
+        }
 
-            let section_content = Column::from_vec(row_elements).width(Fill);
+        column.into()
+    }
 
-            let section_label_column = Column::new()
-                .push(Rule::horizontal(BORDER_WIDTH))
-                .push(
-                    Container::new(create_text_cell(section_label))
-                        .height(Fill)
-                        .align_y(iced::Alignment::Center),
-                )
-                .push(Rule::horizontal(BORDER_WIDTH))
-                .width(50);
+    fn view_table(&self, table_data: Vec<(String, Vec<Vec<Cell>>)>) -> Element<'_, Message> {
+        let current_warning = self.current_budget_factor().is_some();
 
-            let section_row = Row::new()
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(section_label_column)
-                .push(Rule::vertical(BORDER_WIDTH))
-                .push(section_content)
-                .push(Text::new("").width(15)) // padding for Scrollable
-                .height(210);
+        let header = vec![
+            "".to_string(),
+            t(Key::Voltage).to_string(),
+            t(Key::Current).to_string(),
+            t(Key::Resistance).to_string(),
+            t(Key::Power).to_string(),
+        ];
 
-            table_sections.push(section_row.into());
+        let hidden = result_rows::hidden_rows().voltage_divider;
+        let mut table = Table::new(header).on_copy(Message::CopyCell);
+        for ((section_label, rows), leg) in table_data.into_iter().zip(&self.legs) {
+            let rows = rows
+                .into_iter()
+                .enumerate()
+                .filter(|(id, _)| !hidden.contains_index(*id))
+                .map(|(_, row)| {
+                    let cells = row
+                        .into_iter()
+                        .enumerate()
+                        .map(|(id, cell)| match id {
+                            2 if current_warning => cell.style(style::warning_text),
+                            _ => cell,
+                        })
+                        .collect();
+                    TableRow::new(cells)
+                })
+                .collect();
+            table = table.section(section_label, rows);
+            // без рассчитанной мощности подпись остаётся нейтральной
+            if let Some(ratio) = leg.power_ratio().filter(|_| self.heat_map) {
+                table = table.heat(ratio);
+            }
         }
 
-        let table_layout = Column::from_vec(table_sections).padding([5, 0]).width(Fill);
+        // padding for Scrollable
+        let table_layout = Container::new(table.view()).padding(padding::right(15));
 
         Scrollable::new(table_layout).height(Fill).into()
     }
 
-    fn view_form(&self) -> Element<Message> {
+    fn view_ratio(&self) -> Element<'_, Message> {
+        let ratio_text = match &self.ratio.ratio {
+            Err(ParserError::IncorrectInput(e)) => format!("{}: {}", t(Key::RatioFieldError), e),
+            _ => String::from(t(Key::ExampleRatio)),
+        };
+        let total_text = match &self.ratio.total {
+            Err(ParserError::IncorrectInput(e)) => {
+                format!("{}: {}", t(Key::TotalResistanceFieldError), e)
+            }
+            _ => String::from(t(Key::ExampleTotalResistance)),
+        };
+
+        let series = Row::new()
+            .push(Text::new("").width(60))
+            .push(pick_list(
+                ESeries::ALL,
+                Some(self.ratio.series),
+                Message::SeriesSelected,
+            ))
+            .padding([5, 0]);
+
+        let na = || "N/A".to_string();
+        let (calculated, standard) = match self.ratio_legs() {
+            Some((r1, r2)) => {
+                let standard = self
+                    .ratio
+                    .series
+                    .nearest_resistance(&r1)
+                    .zip(self.ratio.series.nearest_resistance(&r2));
+                (Some((r1, r2)), standard)
+            }
+            None => (None, None),
+        };
+        let column = |legs: Option<(Resistance, Resistance)>| match legs {
+            Some((r1, r2)) => [
+                r1.get_value_nom(),
+                r2.get_value_nom(),
+                format!("{:.4}", ratio_of(&r1, &r2)),
+            ],
+            None => [na(), na(), na()],
+        };
+        let calculated = column(calculated);
+        let standard = column(standard);
+
+        let rows = ["R1", "R2", t(Key::Ratio)]
+            .into_iter()
+            .zip(calculated.into_iter().zip(standard))
+            .map(|(label, (calculated, standard))| {
+                TableRow::new(vec![label.into(), calculated.into(), standard.into()])
+            })
+            .collect();
+        let header = vec![
+            "".to_string(),
+            t(Key::Calculated).to_string(),
+            format!("{} ({})", t(Key::Standard), self.ratio.series),
+        ];
+
+        Column::new()
+            .push(field(
+                t(Key::Ratio),
+                &self.ratio.ratio_raw,
+                Message::InputRatioChanged,
+                ratio_text,
+            ))
+            .push(field(
+                t(Key::TotalResistance),
+                &self.ratio.total_raw,
+                Message::InputTotalResistanceChanged,
+                total_text,
+            ))
+            .push(series)
+            .push(Table::new(header).rows(rows).view())
+            .width(Fill)
+            .into()
+    }
+
+    fn view_generator(&self) -> Element<'_, Message> {
+        fn under_text<T>(value: &Result<T, ParserError>, example: Key) -> String {
+            match value {
+                Err(ParserError::IncorrectInput(e)) => e.clone(),
+                _ => String::from(t(example)),
+            }
+        }
+
+        let generator = &self.generator;
+        let series = Row::new()
+            .push(Text::new("").width(60))
+            .push(pick_list(
+                ESeries::ALL,
+                Some(generator.series),
+                Message::GeneratorSeriesSelected,
+            ))
+            .padding([5, 0]);
+
+        let mut column = Column::new()
+            .push(field(
+                t(Key::TopVoltage),
+                &generator.top_raw,
+                Message::InputTopVoltageChanged,
+                under_text(&generator.top, Key::ExampleTopVoltage),
+            ))
+            .push(field(
+                t(Key::BottomVoltage),
+                &generator.bottom_raw,
+                Message::InputBottomVoltageChanged,
+                under_text(&generator.bottom, Key::ExampleBottomVoltage),
+            ))
+            .push(field(
+                t(Key::Taps),
+                &generator.taps_raw,
+                Message::InputTapsChanged,
+                under_text(&generator.taps, Key::ExampleTaps),
+            ))
+            .push(field(
+                t(Key::TotalResistance),
+                &generator.total_raw,
+                Message::InputGeneratorTotalChanged,
+                under_text(&generator.total, Key::ExampleTotalResistance),
+            ))
+            .push(series)
+            .width(Fill);
+
+        match self.generated_legs() {
+            Some(Ok(legs)) => {
+                let rows = legs
+                    .iter()
+                    .enumerate()
+                    .map(|(id, leg)| {
+                        let resistance = Resistance {
+                            value: leg.resistance,
+                            tolerance: None,
+                        };
+                        let standard = generator
+                            .series
+                            .nearest_resistance(&resistance)
+                            .map(|r| r.get_value_nom())
+                            .unwrap_or_else(|| "N/A".to_string());
+                        let voltage = Voltage {
+                            value: leg.voltage,
+                            tolerance: None,
+                        };
+
+                        TableRow::new(vec![
+                            format!("R{}", id + 1).into(),
+                            voltage.get_value_nom().into(),
+                            resistance.get_value_nom().into(),
+                            standard.into(),
+                        ])
+                    })
+                    .collect();
+                let header = vec![
+                    "".to_string(),
+                    t(Key::Voltage).to_string(),
+                    t(Key::Calculated).to_string(),
+                    format!("{} ({})", t(Key::Standard), generator.series),
+                ];
+                let create = Button::new(Text::new(t(Key::CreateLegs)).size(14)).on_press_maybe(
+                    self.create_legs_count()
+                        .is_some()
+                        .then_some(Message::CreateLegs),
+                );
+
+                column = column
+                    .push(Table::new(header).rows(rows).view())
+                    .push(create);
+                if self.create_legs_count().is_none() {
+                    let note = Text::new(format!("{} ({})", t(Key::LegsLimit), LEGS_MAX))
+                        .style(style::hint_text)
+                        .size(12);
+                    column = column.push(note);
+                }
+            }
+            Some(Err(e)) => {
+                column = column.push(Text::new(e.to_string()).style(style::warning_text).size(12));
+            }
+            None => (),
+        }
+
+        column.into()
+    }
+
+    fn view_form(&self) -> Element<'_, Message> {
         let mut elements = Vec::new();
         for (id, leg) in self.legs.iter().enumerate() {
             let label1_text = format!("R{}", id + 1);
             let label2_text = format!("U{}", id + 1);
-            let delete = if id <= 1 { false } else { true };
+            // первое плечо удалить нельзя
+            let delete = id >= LEGS_MIN;
             let under_text = match (&self.legs[id].resistance, &self.legs[id].voltage) {
                 // Некорректный ввод сопротивления и напряжения
                 (Err(ParserError::IncorrectInput(e1)), Err(ParserError::IncorrectInput(e2))) => {
                     format!(
-                        "Resistance field error: {}; Voltage field error: {}",
-                        e1, e2
+                        "{}: {}; {}: {}",
+                        t(Key::ResistanceFieldError),
+                        e1,
+                        t(Key::VoltageFieldError),
+                        e2
                     )
                 }
                 // Некорректный ввод сопротивления, напряжение корректно
                 (Err(ParserError::IncorrectInput(e1)), Ok(_)) => {
-                    format!("Resistance field error: {}", e1)
+                    format!("{}: {}", t(Key::ResistanceFieldError), e1)
                 }
                 // Сопротивление корректно, некорректный ввод напряжения
                 (Ok(_), Err(ParserError::IncorrectInput(e2))) => {
-                    format!("Voltage field error: {}", e2)
+                    format!("{}: {}", t(Key::VoltageFieldError), e2)
                 }
                 // Пустой ввод сопротивления и напряжения
                 (Err(ParserError::EmptyInput), Err(ParserError::EmptyInput)) => {
-                    String::from("Both resistance and voltage fields are empty.")
+                    String::from(t(Key::BothFieldsEmpty))
                 }
                 // Пустой ввод сопротивления, напряжение корректно
-                (Err(ParserError::EmptyInput), Ok(_)) => String::from("Resistance field is empty."),
+                (Err(ParserError::EmptyInput), Ok(_)) => String::from(t(Key::ResistanceFieldEmpty)),
                 // Сопротивление корректно, пустой ввод напряжения
-                (Ok(_), Err(ParserError::EmptyInput)) => String::from("Voltage field is empty."),
+                (Ok(_), Err(ParserError::EmptyInput)) => String::from(t(Key::VoltageFieldEmpty)),
                 // Все корректно
-                (Ok(_), Ok(_)) => String::from("All fields are correct."),
+                (Ok(_), Ok(_)) => String::from(t(Key::AllFieldsCorrect)),
                 // Пример по умолчанию
-                _ => String::from("Example: 1k 5%"),
+                _ => String::from(t(Key::ExampleLeg)),
+            };
+            let input_error = matches!(leg.resistance, Err(ParserError::IncorrectInput(_)))
+                || matches!(leg.voltage, Err(ParserError::IncorrectInput(_)));
+            let under_text = match leg_hint(&label1_text, &label2_text, leg) {
+                Some(hint) if !input_error => format!("{} {}", under_text, hint),
+                _ => under_text,
+            };
+            let under_text: (_, TextStyle) = match &leg.warning {
+                Some(warning) if !input_error => (warning.clone(), style::warning_text),
+                _ => (under_text, style::hint_text),
             };
 
-            let field = self.create_input_field(
-                id,
-                label1_text,
-                &leg.resistance_raw,
-                label2_text,
-                &leg.voltage_raw,
-                under_text,
-                delete,
-            );
+            let field = self.create_input_field(id, label1_text, label2_text, under_text, delete);
             elements.push(field);
         }
 
-        let label = Container::new(Text::new("Add leg")).center_x(Fill);
+        let label = Container::new(Text::new(t(Key::AddLeg))).center_x(Fill);
         let button = Button::new(label)
-            .on_press(Message::LegAdd)
-            .width(Fill)
-            .into();
-        elements.push(button);
+            .on_press_maybe(self.can_add_leg().then_some(Message::LegAdd))
+            .width(Fill);
+        elements.push(button.into());
+        if !self.can_add_leg() {
+            let note = Text::new(format!("{} ({})", t(Key::LegsLimit), LEGS_MAX))
+                .style(style::hint_text)
+                .size(12);
+            elements.push(note.into());
+        }
+        elements.push(self.view_current_budget());
 
         Column::from_vec(elements)
             .padding([5, 0])
@@ -334,29 +1029,82 @@ impl VoltageDivider {
             .into()
     }
 
+    fn view_current_budget(&self) -> Element<'_, Message> {
+        let label = Text::new("I max")
+            .height(30)
+            .width(60)
+            .align_y(iced::Alignment::Center);
+        let input =
+            TextInput::new("", &self.current_max_raw).on_input(Message::InputCurrentMaxChanged);
+        let row1 = Row::new().push(label).push(input).padding([5, 0]);
+
+        let row2: Element<Message> = match (self.current_budget_factor(), self.series_current()) {
+            (Some(factor), Some(current)) => {
+                let warning = Text::new(format!(
+                    "{} {} {} {}",
+                    t(Key::DividerCurrent),
+                    current.get_value_nom(),
+                    t(Key::ExceedsBudget),
+                    self.current_max
+                        .as_ref()
+                        .map(|c| c.get_value_nom())
+                        .unwrap_or_default(),
+                ))
+                .style(style::warning_text)
+                .size(14)
+                .height(30)
+                .align_y(iced::Alignment::Center)
+                .width(Fill);
+                let button = Button::new(Text::new(format!(
+                    "{} ×{:.3}",
+                    t(Key::ScaleResistors),
+                    factor
+                )))
+                .on_press(Message::ScaleResistors);
+
+                Row::new()
+                    .push(Text::new("").width(60))
+                    .push(warning)
+                    .push(button)
+                    .into()
+            }
+            _ => {
+                let under_text = match &self.current_max {
+                    Err(ParserError::IncorrectInput(e)) => {
+                        format!("{}: {}", t(Key::CurrentMaxFieldError), e)
+                    }
+                    _ => String::from(t(Key::CurrentMaxHint)),
+                };
+
+                Row::new()
+                    .push(Text::new("").width(60))
+                    .push(Text::new(under_text).style(style::hint_text).size(12))
+                    .into()
+            }
+        };
+
+        Column::new().push(row1).push(row2).into()
+    }
+
     fn create_input_field<'a>(
-        &self,
+        &'a self,
         leg_id: usize,
         label1_text: String,
-        input1_value: &'a str,
         label2_text: String,
-        input2_value: &'a str,
-        under_text: String,
+        (under_text, under_text_style): (String, TextStyle),
         delete_button_view: bool,
     ) -> Element<'a, Message> {
-        let label1 = Text::new(label1_text)
-            .height(30)
-            .width(30)
-            .align_y(iced::Alignment::Center);
-        let input1 = TextInput::new("", input1_value)
-            .on_input(move |s| Message::InputResistanceChanged(leg_id, s));
-        let label2 = Text::new(label2_text)
-            .height(30)
-            .width(30)
-            .align_y(iced::Alignment::Center);
-        let input2 = TextInput::new("", input2_value)
-            .on_input(move |s| Message::InputVoltageChanged(leg_id, s));
-        let button1: Element<Message> = if delete_button_view == true {
+        let leg = &self.legs[leg_id];
+        let input = |label: String, value: &'a str, field: LegField| {
+            MeasurementInput::new(label, value, move |event| {
+                Message::from_leg_input(leg_id, field, event)
+            })
+            .id(field.input_id(leg_id))
+            .lock(leg.is_locked(field))
+            .presets(presets::presets_of(field.kind()))
+            .view()
+        };
+        let button1: Element<Message> = if delete_button_view {
             Button::new(Text::new("−").size(16))
                 .on_press(Message::LegDelete(leg_id))
                 .width(30)
@@ -368,22 +1116,40 @@ impl VoltageDivider {
         let button1 = Row::new().push(Text::new("").width(5)).push(button1);
 
         let row1 = Row::new()
-            .push(label1)
-            .push(input1)
-            .push(Text::new("").width(50))
-            .push(label2)
-            .push(input2)
+            .push(input(
+                label1_text,
+                &leg.resistance_raw,
+                LegField::Resistance,
+            ))
+            .push(Text::new("").width(20))
+            .push(input(label2_text, &leg.voltage_raw, LegField::Voltage))
             .push(button1);
 
-        let row2 = Row::new().push(Text::new("").width(30)).push(
-            Text::new(under_text)
-                .color(Color::from_rgb8(128, 128, 128))
-                .size(12),
-        );
+        let row2 = Row::new()
+            .push(Text::new("").width(30))
+            .push(Text::new(under_text).style(under_text_style).size(12));
 
         Column::new().push(row1).push(row2).into()
     }
 
+    /// The field Enter in `field` of leg `leg` moves the focus to: the next unlocked one, the
+    /// resistance then the voltage of every leg from the top down, from the bottom leg back to
+    /// the top. `None` if no other field is unlocked.
+    pub fn next_field(&self, leg: usize, field: LegField) -> Option<(usize, LegField)> {
+        let fields: Vec<(usize, LegField)> = (0..self.legs.len())
+            .flat_map(|leg| [(leg, LegField::Resistance), (leg, LegField::Voltage)])
+            .collect();
+        let position = fields.iter().position(|f| *f == (leg, field))?;
+
+        fields
+            .iter()
+            .cycle()
+            .skip(position + 1)
+            .take(fields.len() - 1)
+            .copied()
+            .find(|(leg, field)| !self.legs[*leg].is_locked(*field))
+    }
+
     pub fn update(&mut self, message: Message) {
         match message {
             Message::InputResistanceChanged(id, s) => {
@@ -394,115 +1160,841 @@ impl VoltageDivider {
                 self.legs[id].voltage_raw = s;
                 self.legs[id].voltage = self.legs[id].voltage_raw.parse::<Voltage>();
             }
-            Message::LegAdd => self.legs.push(Leg::default()),
-            Message::LegDelete(id) => {
-                let _leg = self.legs.remove(id);
+            Message::InputCurrentMaxChanged(s) => {
+                self.current_max_raw = s;
+                self.current_max = self.current_max_raw.parse::<Current>();
             }
-        }
-
-        // кажется нужно очищать значения если нет пользовательского ввода
-        for leg in &mut self.legs.iter_mut() {
-            if leg.voltage_raw.is_empty() {
-                leg.voltage = Err(ParserError::EmptyInput);
-                leg.power = Err(ParserError::EmptyInput);
-                leg.current = Err(ParserError::EmptyInput);
+            Message::ClearLegField(id, field) if self.legs[id].is_locked(field) => return,
+            Message::LegPrefixSelected(id, field, _) if self.legs[id].is_locked(field) => return,
+            Message::LegPrefixSelected(id, field, dim) => {
+                let leg = &self.legs[id];
+                return self.update(match field {
+                    LegField::Resistance => {
+                        Message::InputResistanceChanged(id, dim.append_to(&leg.resistance_raw))
+                    }
+                    LegField::Voltage => {
+                        Message::InputVoltageChanged(id, dim.append_to(&leg.voltage_raw))
+                    }
+                });
             }
-            if leg.resistance_raw.is_empty() {
-                leg.resistance = Err(ParserError::EmptyInput);
-                leg.power = Err(ParserError::EmptyInput);
-                leg.current = Err(ParserError::EmptyInput);
+            Message::LegPresetSelected(id, field, _) if self.legs[id].is_locked(field) => return,
+            Message::LegPresetSelected(id, field, preset) => {
+                let Some(raw) = preset.insert_into(field.kind()) else {
+                    return;
+                };
+                return self.update(match field {
+                    LegField::Resistance => Message::InputResistanceChanged(id, raw.to_string()),
+                    LegField::Voltage => Message::InputVoltageChanged(id, raw.to_string()),
+                });
             }
-        }
-
-        let mut v1: Option<Voltage> = None;
-        let mut v2: Option<Voltage> = None;
-        let mut r_sum: Option<Resistance> = None;
-        let mut empty_fields = false;
-
-        for leg in self.legs.iter().rev() {
-            match (leg.resistance.clone(), leg.voltage.clone()) {
-                (Err(_), Err(_)) => {
-                    v1 = None;
-                    v2 = None;
-                    r_sum = None;
-                    empty_fields = true;
-                }
-                (Ok(r), Ok(v)) => {
-                    v2 = Some(v);
-                    r_sum = if let Some(rr) = r_sum {
-                        Some(r + rr)
-                    } else {
-                        Some(r)
-                    };
+            Message::ClearLegField(id, field) => {
+                return self.update(match field {
+                    LegField::Resistance => Message::InputResistanceChanged(id, String::new()),
+                    LegField::Voltage => Message::InputVoltageChanged(id, String::new()),
+                });
+            }
+            Message::LegLockToggled(id, field) => {
+                let leg = &mut self.legs[id];
+                match field {
+                    LegField::Resistance => leg.resistance_locked = !leg.resistance_locked,
+                    LegField::Voltage => leg.voltage_locked = !leg.voltage_locked,
                 }
-                (Err(_), Ok(v)) => {
-                    v1 = Some(v);
+                return;
+            }
+            Message::LegAdd => {
+                if self.can_add_leg() {
+                    self.legs.push(Leg::default());
                 }
-                (Ok(r), Err(_)) => {
-                    if v2.is_none() {
-                        r_sum = if let Some(rr) = r_sum {
-                            Some(r + rr)
-                        } else {
-                            Some(r)
-                        };
-                    }
+            }
+            Message::LegDelete(id) => {
+                if self.legs.len() > LEGS_MIN && id < self.legs.len() {
+                    let _leg = self.legs.remove(id);
                 }
             }
+            Message::ScaleResistors => self.scale_resistors(),
+            Message::RatioModeToggled(enable) => {
+                self.ratio_mode = enable;
+                self.generator_mode &= !enable;
+            }
+            Message::InputRatioChanged(s) => {
+                self.ratio.ratio = parse_ratio(&s);
+                self.ratio.ratio_raw = s;
+            }
+            Message::InputTotalResistanceChanged(s) => {
+                self.ratio.total_raw = s;
+                self.ratio.total = self.ratio.total_raw.parse::<Resistance>();
+            }
+            Message::SeriesSelected(series) => self.ratio.series = series,
+            Message::GeneratorModeToggled(enable) => {
+                self.generator_mode = enable;
+                self.ratio_mode &= !enable;
+            }
+            Message::InputTopVoltageChanged(s) => {
+                self.generator.top_raw = s;
+                self.generator.top = self.generator.top_raw.parse::<Voltage>();
+            }
+            Message::InputBottomVoltageChanged(s) => {
+                self.generator.bottom_raw = s;
+                self.generator.bottom = self.generator.bottom_raw.parse::<Voltage>();
+            }
+            Message::InputTapsChanged(s) => {
+                self.generator.taps = parse_taps(&s);
+                self.generator.taps_raw = s;
+            }
+            Message::InputGeneratorTotalChanged(s) => {
+                self.generator.total_raw = s;
+                self.generator.total = self.generator.total_raw.parse::<Resistance>();
+            }
+            Message::GeneratorSeriesSelected(series) => self.generator.series = series,
+            Message::CreateLegs => self.create_legs(),
+            Message::ShowWorkToggled(enable) => self.show_work = enable,
+            Message::SchematicToggled(enable) => self.schematic = enable,
+            Message::HeatMapToggled(enable) => self.heat_map = enable,
+            Message::LegRatedPowerChanged(id, s) => {
+                self.legs[id].rated_power_raw = s;
+                self.legs[id].rated_power = self.legs[id].rated_power_raw.parse::<Power>();
+            }
+            Message::StandardValuesToggled => self.standard_values = !self.standard_values,
+            Message::RowsMenuToggled => self.rows_menu = !self.rows_menu,
+            Message::RowVisibilityToggled(row) => {
+                let mut hidden = result_rows::hidden_rows();
+                hidden.voltage_divider = hidden.voltage_divider.toggled(row);
+                result_rows::set_hidden_rows(hidden);
+            }
+            Message::SupplySweepToggled(enable) => self.supply_sweep.enabled = enable,
+            Message::InputSupplySweepChanged(s) => {
+                self.supply_sweep.sweep = sweep::parse_sweep(&s);
+                self.supply_sweep.raw = s;
+            }
+            Message::LoadExample => {
+                *self = Self::with_legs(&EXAMPLE);
+                return;
+            }
+            Message::TemplateSelected(template) => {
+                *self = Self::with_legs(template.legs);
+                return;
+            }
+            Message::PinResult => self.pinned = Some(self.snapshot()),
+            Message::Unpin => self.pinned = None,
+            Message::FieldSubmitted(..) | Message::CopyCell(_) => return,
         }
 
-        // если второе напряжение не определено, то принимаем его за 0
-        if v1.is_none() {
-            v1 = Some(Voltage::default());
-        }
+        // расчётные поля не учитываются: важен только ввод пользователя
+        self.dirty = self.has_input();
 
-        let current = if let (Some(v1), Some(v2), Some(r)) = (v1, v2, r_sum) {
-            if empty_fields == true {
-                None
-            } else {
-                Some((v2 - v1) / r)
-            }
+        self.solve();
+        self.check_plausibility();
+    }
+
+    /// Stores the text of a keystroke without parsing it, for the input debounce: the scene
+    /// is parsed and solved later by [`Self::recalculate`]. Other messages are updated as
+    /// usual.
+    pub fn store_text(&mut self, message: Message) {
+        let (raw, text) = match message {
+            Message::InputResistanceChanged(id, s) => (&mut self.legs[id].resistance_raw, s),
+            Message::InputVoltageChanged(id, s) => (&mut self.legs[id].voltage_raw, s),
+            Message::LegRatedPowerChanged(id, s) => (&mut self.legs[id].rated_power_raw, s),
+            Message::InputCurrentMaxChanged(s) => (&mut self.current_max_raw, s),
+            Message::InputRatioChanged(s) => (&mut self.ratio.ratio_raw, s),
+            Message::InputTotalResistanceChanged(s) => (&mut self.ratio.total_raw, s),
+            Message::InputTopVoltageChanged(s) => (&mut self.generator.top_raw, s),
+            Message::InputBottomVoltageChanged(s) => (&mut self.generator.bottom_raw, s),
+            Message::InputTapsChanged(s) => (&mut self.generator.taps_raw, s),
+            Message::InputGeneratorTotalChanged(s) => (&mut self.generator.total_raw, s),
+            other => return self.update(other),
+        };
+
+        *raw = text;
+        self.dirty = self.has_input();
+    }
+
+    /// Checks the entered leg values against the plausibility limits. Calculated values are
+    /// not checked: the warning points at the field with the typo.
+    fn check_plausibility(&mut self) {
+        fn check<M: Measurement>(raw: &str, value: &Result<M, ParserError>) -> Option<String> {
+            if raw.trim().is_empty() {
+                return None;
+            }
+            let value = value.as_ref().ok()?;
+            plausibility::check_unit(raw, value)
+                .or_else(|| plausibility::check(value, &plausibility::limits()))
+                .or_else(|| plausibility::check_tolerance(value))
+        }
+
+        for leg in self.legs.iter_mut() {
+            let warnings: Vec<_> = [
+                check(&leg.resistance_raw, &leg.resistance),
+                check(&leg.voltage_raw, &leg.voltage),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+
+            leg.warning = (!warnings.is_empty()).then(|| warnings.join("; "));
+        }
+    }
+
+    /// Results of every leg, top to bottom.
+    fn snapshot(&self) -> Vec<Snapshot> {
+        self.legs
+            .iter()
+            .map(|leg| Snapshot::capture(&leg.voltage, &leg.current, &leg.resistance, &leg.power))
+            .collect()
+    }
+
+    /// Diagnostic JSON report of the scene: raw inputs of every leg, their parse results and
+    /// the solved outputs with their tolerance bounds.
+    pub fn diagnostic_report(&self) -> String {
+        #[derive(Serialize)]
+        struct LegState<'a> {
+            resistance_raw: &'a str,
+            voltage_raw: &'a str,
+            resistance_parsed: Result<Resistance, ParserError>,
+            voltage_parsed: Result<Voltage, ParserError>,
+            voltage: Result<Bounds, ParserError>,
+            current: Result<Bounds, ParserError>,
+            resistance: Result<Bounds, ParserError>,
+            power: Result<Bounds, ParserError>,
+        }
+
+        #[derive(Serialize)]
+        struct RatioState<'a> {
+            ratio_raw: &'a str,
+            total_raw: &'a str,
+            ratio: &'a Result<f64, ParserError>,
+            total: &'a Result<Resistance, ParserError>,
+            series: ESeries,
+            legs: Option<(Bounds, Bounds)>,
+        }
+
+        #[derive(Serialize)]
+        struct GeneratorState<'a> {
+            top_raw: &'a str,
+            bottom_raw: &'a str,
+            taps_raw: &'a str,
+            total_raw: &'a str,
+            taps: &'a Result<Vec<f64>, ParserError>,
+            series: ESeries,
+            // напряжение и сопротивление каждого плеча или текст ошибки
+            legs: Option<Result<Vec<(f64, f64)>, String>>,
+        }
+
+        #[derive(Serialize)]
+        struct State<'a> {
+            legs: Vec<LegState<'a>>,
+            current_max_raw: &'a str,
+            current_max: &'a Result<Current, ParserError>,
+            series_current: Option<Bounds>,
+            ratio_mode: bool,
+            ratio: RatioState<'a>,
+            generator_mode: bool,
+            generator: GeneratorState<'a>,
+        }
+
+        let legs = self
+            .legs
+            .iter()
+            .map(|leg| LegState {
+                resistance_raw: &leg.resistance_raw,
+                voltage_raw: &leg.voltage_raw,
+                resistance_parsed: leg.resistance_raw.parse(),
+                voltage_parsed: leg.voltage_raw.parse(),
+                voltage: report::output(&leg.voltage),
+                current: report::output(&leg.current),
+                resistance: report::output(&leg.resistance),
+                power: report::output(&leg.power),
+            })
+            .collect();
+        let state = State {
+            legs,
+            current_max_raw: &self.current_max_raw,
+            current_max: &self.current_max,
+            series_current: self.series_current().as_ref().map(Bounds::of),
+            ratio_mode: self.ratio_mode,
+            ratio: RatioState {
+                ratio_raw: &self.ratio.ratio_raw,
+                total_raw: &self.ratio.total_raw,
+                ratio: &self.ratio.ratio,
+                total: &self.ratio.total,
+                series: self.ratio.series,
+                legs: self
+                    .ratio_legs()
+                    .map(|(r1, r2)| (Bounds::of(&r1), Bounds::of(&r2))),
+            },
+            generator_mode: self.generator_mode,
+            generator: GeneratorState {
+                top_raw: &self.generator.top_raw,
+                bottom_raw: &self.generator.bottom_raw,
+                taps_raw: &self.generator.taps_raw,
+                total_raw: &self.generator.total_raw,
+                taps: &self.generator.taps,
+                series: self.generator.series,
+                legs: self.generated_legs().map(|legs| match legs {
+                    Ok(legs) => Ok(legs
+                        .iter()
+                        .map(|leg| (leg.voltage, leg.resistance))
+                        .collect()),
+                    Err(e) => Err(e.to_string()),
+                }),
+            },
+        };
+
+        report::to_json("voltage_divider", state)
+    }
+
+    /// Parses all inputs again and solves the divider, e.g. after a global setting changed.
+    /// The entered text is kept as is.
+    pub fn recalculate(&mut self) {
+        for leg in self.legs.iter_mut() {
+            leg.resistance = leg.resistance_raw.parse::<Resistance>();
+            leg.voltage = leg.voltage_raw.parse::<Voltage>();
+            leg.rated_power = leg.rated_power_raw.parse::<Power>();
+        }
+        self.current_max = self.current_max_raw.parse::<Current>();
+        self.ratio.ratio = parse_ratio(&self.ratio.ratio_raw);
+        self.ratio.total = self.ratio.total_raw.parse::<Resistance>();
+        self.generator.top = self.generator.top_raw.parse::<Voltage>();
+        self.generator.bottom = self.generator.bottom_raw.parse::<Voltage>();
+        self.generator.taps = parse_taps(&self.generator.taps_raw);
+        self.generator.total = self.generator.total_raw.parse::<Resistance>();
+
+        self.solve();
+        self.check_plausibility();
+    }
+
+    /// Top (R1) and bottom (R2) resistors of the divider from ratio:
+    /// `R2 = ratio * Rtotal`, `R1 = Rtotal - R2`.
+    fn ratio_legs(&self) -> Option<(Resistance, Resistance)> {
+        let ratio = *self.ratio.ratio.as_ref().ok()?;
+        let total = *self.ratio.total.as_ref().ok()?;
+
+        Some((total * (1.0 - ratio), total * ratio))
+    }
+
+    /// Legs of the divider from taps, or `None` while an input is missing or wrong.
+    fn generated_legs(&self) -> Option<Result<Vec<GeneratedLeg>, GeneratorError>> {
+        let generator = &self.generator;
+        let top = generator.top.as_ref().ok()?.get_nominal_value();
+        let bottom = generator.bottom()?;
+        let taps = generator.taps.as_ref().ok()?;
+        let total = generator.total.as_ref().ok()?.get_nominal_value();
+
+        Some(generator::generate(top, bottom, taps, total))
+    }
+
+    /// Number of legs the divider from taps creates, or `None` if there is nothing to create
+    /// or the legs do not fit in the limit. A bottom rail other than ground takes an extra
+    /// leg that holds only its voltage.
+    fn create_legs_count(&self) -> Option<usize> {
+        let legs = self.generated_legs()?.ok()?.len();
+        let count = if self.generator.bottom()? == 0.0 {
+            legs
+        } else {
+            legs + 1
+        };
+
+        (count <= LEGS_MAX).then_some(count)
+    }
+
+    /// Replaces the legs with the ones generated from the taps and returns to the leg list,
+    /// where they can be edited as usual.
+    fn create_legs(&mut self) {
+        let (Some(Ok(generated)), Some(_)) = (self.generated_legs(), self.create_legs_count())
+        else {
+            return;
+        };
+
+        let mut legs: Vec<Leg> = generated
+            .iter()
+            .map(|leg| {
+                let resistance = Resistance {
+                    value: leg.resistance,
+                    tolerance: None,
+                };
+                let voltage = Voltage {
+                    value: leg.voltage,
+                    tolerance: None,
+                };
+                Leg::new(resistance.get_value_input(), voltage.get_value_input())
+            })
+            .collect();
+        if let Some(bottom) = self.generator.bottom().filter(|bottom| *bottom != 0.0) {
+            let voltage = Voltage {
+                value: bottom,
+                tolerance: None,
+            };
+            legs.push(Leg::new(String::new(), voltage.get_value_input()));
+        }
+
+        self.legs = legs;
+        self.generator_mode = false;
+    }
+
+    /// `true` if the scene holds user input that would be lost on exit.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn has_input(&self) -> bool {
+        let legs = self
+            .legs
+            .iter()
+            .flat_map(|leg| [&leg.resistance_raw, &leg.voltage_raw, &leg.rated_power_raw]);
+        let other = [
+            &self.current_max_raw,
+            &self.ratio.ratio_raw,
+            &self.ratio.total_raw,
+            &self.generator.top_raw,
+            &self.generator.bottom_raw,
+            &self.generator.taps_raw,
+            &self.generator.total_raw,
+            &self.supply_sweep.raw,
+        ];
+
+        legs.chain(other).any(|s| !s.trim().is_empty())
+    }
+
+    /// Status of the scene for the sidebar badge.
+    pub fn status(&self) -> SceneStatus {
+        fn is_error<T>(value: &Result<T, ParserError>) -> bool {
+            matches!(value, Err(ParserError::IncorrectInput(_)))
+        }
+
+        let error = if self.ratio_mode {
+            is_error(&self.ratio.ratio) || is_error(&self.ratio.total)
+        } else if self.generator_mode {
+            is_error(&self.generator.top)
+                || is_error(&self.generator.bottom)
+                || is_error(&self.generator.taps)
+                || is_error(&self.generator.total)
+                || matches!(self.generated_legs(), Some(Err(_)))
+        } else {
+            is_error(&self.current_max)
+                || self
+                    .legs
+                    .iter()
+                    .any(|leg| is_error(&leg.resistance) || is_error(&leg.voltage))
+        };
+        let solved = if self.ratio_mode {
+            self.ratio_legs().is_some()
+        } else if self.generator_mode {
+            matches!(self.generated_legs(), Some(Ok(_)))
+        } else {
+            self.legs
+                .iter()
+                .all(|leg| leg.voltage.is_ok() && leg.resistance.is_ok() && leg.current.is_ok())
+        };
+
+        if !self.has_input() {
+            SceneStatus::Empty
+        } else if error {
+            SceneStatus::Error
+        } else if solved {
+            SceneStatus::Solved
+        } else {
+            SceneStatus::Incomplete
+        }
+    }
+
+    fn can_add_leg(&self) -> bool {
+        self.legs.len() < LEGS_MAX
+    }
+
+    /// Series current of the divider, common to every leg once solved.
+    fn series_current(&self) -> Option<Current> {
+        self.legs
+            .iter()
+            .find_map(|leg| leg.current.as_ref().ok().copied())
+    }
+
+    /// `true` if the solved series current flows from the bottom leg to the top one.
+    fn is_reverse_current(&self) -> bool {
+        self.series_current()
+            .is_some_and(|current| current.get_nominal_value() < 0.0)
+    }
+
+    /// Legs, numbered from 1, whose entered resistance and voltage disagree with the series
+    /// current: the current through the leg from its drop and resistance is outside the
+    /// tolerance band of the series current. The entered values are kept, the scene only
+    /// warns about them.
+    fn inconsistent_legs(&self) -> Vec<usize> {
+        let Some(current) = self.series_current() else {
+            return Vec::new();
+        };
+
+        let mut inconsistent = Vec::new();
+        let mut below = Voltage::default();
+        for (id, leg) in self.legs.iter().enumerate().rev() {
+            let Ok(voltage) = leg.voltage else {
+                return Vec::new();
+            };
+            let entered =
+                !leg.resistance_raw.trim().is_empty() && !leg.voltage_raw.trim().is_empty();
+            if let (true, Ok(resistance)) = (entered, &leg.resistance) {
+                let leg_current = (voltage - below) / *resistance;
+                // допуск на погрешность округления при точных значениях
+                let epsilon = CONSISTENCY_EPSILON
+                    * leg_current
+                        .get_nominal_value()
+                        .abs()
+                        .max(current.get_nominal_value().abs());
+                if leg_current.min_value() > current.max_value() + epsilon
+                    || leg_current.max_value() < current.min_value() - epsilon
+                {
+                    inconsistent.push(id + 1);
+                }
+            }
+            below = voltage;
+        }
+
+        inconsistent.reverse();
+        inconsistent
+    }
+
+    /// Factor by which all resistances must be multiplied to bring the series current
+    /// down to the user-set budget, or `None` if there is no budget or it is met.
+    fn current_budget_factor(&self) -> Option<f64> {
+        let budget = self.current_max.as_ref().ok()?.get_nominal_value().abs();
+        let current = self.series_current()?.get_nominal_value().abs();
+
+        if budget > 0.0 && current > budget * (1.0 + CURRENT_BUDGET_EPSILON) {
+            Some(current / budget)
         } else {
             None
+        }
+    }
+
+    /// Multiplies every user-entered resistance by the budget factor. The ratios and thus
+    /// the tap voltages are kept, the raw strings are rewritten so the new values are visible.
+    fn scale_resistors(&mut self) {
+        let Some(factor) = self.current_budget_factor() else {
+            return;
         };
 
-        if current.is_some() {
-            let mut pre_voltage = Voltage::default();
+        for leg in self.legs.iter_mut() {
+            if leg.resistance_raw.trim().is_empty() {
+                continue;
+            }
+            if let Ok(r) = leg.resistance {
+                let scaled = Resistance {
+                    value: r.value * factor,
+                    tolerance: r.tolerance,
+                };
+                leg.resistance_raw = scaled.get_value_input();
+                leg.resistance = leg.resistance_raw.parse::<Resistance>();
+            }
+        }
+    }
 
-            for leg in &mut self.legs.iter_mut().rev() {
-                match (&leg.voltage, current, &leg.resistance) {
-                    (Ok(v), Some(c), Err(_)) => {
-                        leg.resistance = Ok((*v - pre_voltage) / c);
-                        leg.current = Ok(c);
-                        pre_voltage = *v;
-                    }
-                    (Ok(v), Some(c), Ok(_)) => {
+    fn solve(&mut self) {
+        self.steps = solve_legs(&mut self.legs);
+        self.supply_sweep.result = self.calculating_supply_sweep();
+    }
+}
+
+/// Solves the divider from the entered values of the legs, the calculated values are
+/// replaced. An entered value is never overwritten, even one that doesn't parse: only the
+/// empty fields are calculated. A resistance that would come out negative, as the voltage
+/// drop over the leg goes against the current, is an error of the leg instead. Returns the
+/// steps of the solution.
+fn solve_legs(legs: &mut [Leg]) -> Vec<String> {
+    let mut steps = Vec::new();
+
+    // кажется нужно очищать значения если нет пользовательского ввода
+    for leg in &mut legs.iter_mut() {
+        // ток и мощность всегда расчётные
+        leg.power = Err(ParserError::EmptyInput);
+        leg.current = Err(ParserError::EmptyInput);
+        if leg.voltage_raw.is_empty() {
+            leg.voltage = Err(ParserError::EmptyInput);
+        }
+        if leg.resistance_raw.is_empty() {
+            leg.resistance = Err(ParserError::EmptyInput);
+        }
+    }
+
+    let mut v1: Option<Voltage> = None;
+    let mut v2: Option<Voltage> = None;
+    let mut r_sum: Option<Resistance> = None;
+    let mut empty_fields = false;
+
+    for leg in legs.iter().rev() {
+        match (leg.resistance.clone(), leg.voltage.clone()) {
+            (Err(_), Err(_)) => {
+                v1 = None;
+                v2 = None;
+                r_sum = None;
+                empty_fields = true;
+            }
+            (Ok(r), Ok(v)) => {
+                v2 = Some(v);
+                r_sum = if let Some(rr) = r_sum {
+                    Some(r + rr)
+                } else {
+                    Some(r)
+                };
+            }
+            // нижний узел - ближайшее плечо с одним напряжением под полностью заданными,
+            // сопротивления под ним в ток не входят
+            (Err(_), Ok(v)) => {
+                if v2.is_none() {
+                    v1 = Some(v);
+                    r_sum = None;
+                }
+            }
+            (Ok(r), Err(_)) => {
+                if v2.is_none() {
+                    r_sum = if let Some(rr) = r_sum {
+                        Some(r + rr)
+                    } else {
+                        Some(r)
+                    };
+                }
+            }
+        }
+    }
+
+    // если второе напряжение не определено, то принимаем его за 0
+    if v1.is_none() {
+        v1 = Some(Voltage::default());
+    }
+
+    let current = if let (Some(v1), Some(v2), Some(r)) = (v1, v2, r_sum) {
+        if empty_fields {
+            None
+        } else {
+            Some((v2 - v1) / r)
+        }
+    } else {
+        None
+    };
+
+    if let (Some(c), Some(v1), Some(v2), Some(r)) = (current, v1, v2, r_sum) {
+        steps.push(format!(
+            "I = (U − U0) / ΣR = ({} − {}) / {} = {}",
+            v2.get_value_nom(),
+            v1.get_value_nom(),
+            r.get_value_nom(),
+            c.get_value_nom()
+        ));
+    }
+
+    if current.is_some() {
+        let mut pre_voltage = Voltage::default();
+        // напряжение нижнего узла плеча: следующее плечо или земля
+        let mut pre_label = String::from("0");
+
+        for (id, leg) in legs.iter_mut().enumerate().rev() {
+            let n = id + 1;
+            let drop = |v: &Voltage, c: &Current, sign: &str| {
+                format!(
+                    "({} − {}) {} {}",
+                    v.get_value_nom(),
+                    pre_voltage.get_value_nom(),
+                    sign,
+                    c.get_value_nom()
+                )
+            };
+
+            match (&leg.voltage, current, &leg.resistance) {
+                (Ok(v), Some(c), Err(ParserError::EmptyInput)) => {
+                    let r = (*v - pre_voltage) / c;
+                    let p = (*v - pre_voltage) * c;
+                    // падение на плече против направления тока: напряжения узлов не монотонны
+                    if r.get_nominal_value() < 0.0 {
+                        leg.resistance = Err(ParserError::IncorrectInput(format!(
+                            "{} R{n}",
+                            t(Key::NegativeResistance)
+                        )));
                         leg.current = Ok(c);
                         pre_voltage = *v;
+                        pre_label = format!("U{n}");
+                        continue;
                     }
-                    (Err(_), Some(c), Ok(r)) => {
-                        let v = (c * *r) + pre_voltage;
-                        leg.voltage = Ok(v);
-                        leg.current = Ok(c);
-                        pre_voltage = v;
-                    }
-                    (_, None, _) => leg.current = Err(ParserError::EmptyInput),
-                    _ => (),
+                    steps.push(format!(
+                        "R{n} = (U{n} − {pre_label}) / I = {} = {}",
+                        drop(v, &c, "/"),
+                        r.get_value_nom()
+                    ));
+                    steps.push(format!(
+                        "P{n} = (U{n} − {pre_label}) × I = {} = {}",
+                        drop(v, &c, "×"),
+                        p.get_value_nom()
+                    ));
+                    leg.resistance = Ok(r);
+                    leg.current = Ok(c);
+                    leg.power = Ok(p);
+                    pre_voltage = *v;
+                    pre_label = format!("U{n}");
+                }
+                // R с ошибкой ввода не заменяется расчётным
+                (Ok(v), Some(c), Ok(_) | Err(_)) => {
+                    let p = (*v - pre_voltage) * c;
+                    steps.push(format!(
+                        "P{n} = (U{n} − {pre_label}) × I = {} = {}",
+                        drop(v, &c, "×"),
+                        p.get_value_nom()
+                    ));
+                    leg.current = Ok(c);
+                    leg.power = Ok(p);
+                    pre_voltage = *v;
+                    pre_label = format!("U{n}");
+                }
+                (Err(ParserError::EmptyInput), Some(c), Ok(r)) => {
+                    let v = (c * *r) + pre_voltage;
+                    let p = (v - pre_voltage) * c;
+                    steps.push(format!(
+                        "U{n} = I × R{n} + {pre_label} = {} × {} + {} = {}",
+                        c.get_value_nom(),
+                        r.get_value_nom(),
+                        pre_voltage.get_value_nom(),
+                        v.get_value_nom()
+                    ));
+                    steps.push(format!(
+                        "P{n} = (U{n} − {pre_label}) × I = {} = {}",
+                        drop(&v, &c, "×"),
+                        p.get_value_nom()
+                    ));
+                    leg.voltage = Ok(v);
+                    leg.current = Ok(c);
+                    leg.power = Ok(p);
+                    pre_voltage = v;
+                    pre_label = format!("U{n}");
+                }
+                // U с ошибкой ввода остаётся, но следующее плечо считается от расчётного узла
+                (Err(_), Some(c), Ok(r)) => {
+                    pre_voltage = (c * *r) + pre_voltage;
+                    pre_label = format!("U{n}");
                 }
+                (_, None, _) => leg.current = Err(ParserError::EmptyInput),
+                _ => (),
             }
         }
     }
+
+    steps
+}
+
+/// Labeled input with a hint line under it, used by the ratio and taps modes.
+fn field<'a>(
+    label: &str,
+    value: &'a str,
+    on_input: fn(String) -> Message,
+    under_text: String,
+) -> Element<'a, Message> {
+    let label = Text::new(label.to_string())
+        .height(30)
+        .width(60)
+        .align_y(iced::Alignment::Center);
+    let input = TextInput::new("", value).on_input(on_input);
+    let row1 = Row::new().push(label).push(input).padding([5, 0]);
+    let row2 = Row::new()
+        .push(Text::new("").width(60))
+        .push(Text::new(under_text).style(style::hint_text).size(12));
+
+    Column::new().push(row1).push(row2).into()
+}
+
+/// Entered values of a leg in base units, e.g. "R1 = 1000 Ω, U1 = 10 V". Calculated values
+/// are skipped.
+fn leg_hint(resistance_label: &str, voltage_label: &str, leg: &Leg) -> Option<String> {
+    let resistance = match &leg.resistance {
+        Ok(r) if !leg.resistance_raw.trim().is_empty() => Some(format!(
+            "{} {}{}",
+            resistance_label,
+            r.get_value_hint(),
+            default_tolerance::assumed_hint::<Resistance>(&leg.resistance_raw)
+        )),
+        _ => None,
+    };
+    let voltage = match &leg.voltage {
+        Ok(v) if !leg.voltage_raw.trim().is_empty() => Some(format!(
+            "{} {}{}",
+            voltage_label,
+            v.get_value_hint(),
+            default_tolerance::assumed_hint::<Voltage>(&leg.voltage_raw)
+        )),
+        _ => None,
+    };
+    let hints: Vec<_> = [resistance, voltage].into_iter().flatten().collect();
+
+    (!hints.is_empty()).then(|| hints.join(", "))
+}
+
+fn parse_ratio(input: &str) -> Result<f64, ParserError> {
+    if input.trim().is_empty() {
+        return Err(ParserError::EmptyInput);
+    }
+
+    match input.trim().parse::<f64>() {
+        Ok(ratio) if ratio > 0.0 && ratio < 1.0 => Ok(ratio),
+        Ok(_) => Err(ParserError::IncorrectInput(
+            t(Key::RatioOutOfRange).to_string(),
+        )),
+        Err(e) => Err(ParserError::IncorrectInput(e.to_string())),
+    }
+}
+
+/// Comma-separated tap voltages, e.g. "1, 2.5, 3.3". Tolerances are ignored: the taps are
+/// targets for the nominal values.
+fn parse_taps(input: &str) -> Result<Vec<f64>, ParserError> {
+    if input.trim().is_empty() {
+        return Err(ParserError::EmptyInput);
+    }
+
+    input
+        .split(',')
+        .map(|tap| match tap.parse::<Voltage>() {
+            Ok(voltage) => Ok(voltage.get_nominal_value()),
+            Err(ParserError::EmptyInput) => {
+                Err(ParserError::IncorrectInput(t(Key::EmptyTap).to_string()))
+            }
+            Err(e) => Err(e),
+        })
+        .collect()
+}
+
+/// Output to input voltage ratio of a two resistor divider.
+fn ratio_of(r1: &Resistance, r2: &Resistance) -> f64 {
+    r2.get_nominal_value() / (r1.get_nominal_value() + r2.get_nominal_value())
+}
+
+impl ESeries {
+    /// Nearest standard resistor with the usual tolerance of the series.
+    fn nearest_resistance(&self, resistance: &Resistance) -> Option<Resistance> {
+        let tolerance = self.tolerance();
+
+        Some(Resistance {
+            value: self.nearest(resistance.get_nominal_value())?,
+            tolerance: Some(Tolerance {
+                plus: tolerance,
+                minus: tolerance,
+            }),
+        })
+    }
 }
 
 pub fn help() -> (String, String) {
     let title = String::from("Voltage Divider");
-    let text = String::from("
+    let text = format!(
+        "
 The program calculates parameters in a resistive voltage divider circuit. It allows you to define the characteristics of each leg of the divider and provides tools for customization.
 
+**Load example** replaces the inputs with a worked example: a 10k over 10k divider on a 9V battery.
+
+**Template** next to it replaces the inputs with a common divider, every value stays editable:
+{templates}
+A new divider starts with two legs; the number can be changed on the settings page.
+
 #### Features and Interface
 1. **Leg Configuration**:  
    - By default, the circuit starts with two legs.  
    - You can add additional legs using the **Add Leg** button.  
-   - Each additional leg will have a `-` button on the right for easy deletion.
+   - Every leg but the first one has a `-` button on the right for easy deletion.  
+   - The `✕` button next to a filled field clears just that field.  
+   - The padlock button locks a field against accidental edits; a locked value is still used in the calculation.  
+   - A divider has at least 1 and at most 64 legs; the **Add Leg** button is disabled at the limit.  
+   - A single leg is one resistor to ground: with its resistance and voltage entered, the current and power follow from Ohm's law.
 
 2. **Automatic Numbering**:  
    - Legs are numbered automatically, starting from 1, and renumbered dynamically after any additions or deletions.
@@ -514,7 +2006,25 @@ The program calculates parameters in a resistive voltage divider circuit. It all
 
 4. **Calculation Requirements**:  
    - All known fields must be filled in.  
-   - At least one leg must be fully defined, meaning both **resistance** and **voltage** must be provided for that leg.
+   - At least one leg must be fully defined, meaning both **resistance** and **voltage** must be provided for that leg.  
+   - Entered values are never replaced by calculated ones; only empty fields are calculated. If a leg with both values entered disagrees with the series current beyond the tolerances, a warning names the leg.
+   - A calculated resistance can't be negative: if the tap voltages and the series current imply one, e.g. a tap above the one higher up the chain, the resistance field of that leg shows an error instead.
+
+5. **Current Budget**:  
+   - The optional **I max** field sets the maximum allowed divider current.  
+   - When the solved current exceeds it, the current cells are highlighted and a **Scale resistors** button appears.  
+   - Scaling multiplies all entered resistances by the same factor, so the tap voltages are kept and the current meets the budget.
+
+6. **Divider from Ratio**:  
+   - Check **Divider from ratio** to enter the output to input voltage ratio and the total resistance instead of the legs.  
+   - The top and bottom resistors are `R1 = Rtotal - R2` and `R2 = ratio × Rtotal`.  
+   - The nearest standard values of the selected E-series (E12, E24, E48, E96) are shown next to them, with the ratio they actually give.
+
+7. **Divider from Taps**:  
+   - Check **Divider from taps** to enter the top and bottom rail voltages, a comma-separated list of tap voltages (e.g. `1, 2.5, 3.3`) and the total resistance.  
+   - The taps may be listed in either order but must be strictly increasing or decreasing and lie between the rails. An empty bottom rail is ground.  
+   - The table shows the exact resistance of every leg and the nearest value of the selected E-series.  
+   - **Create legs** replaces the legs with the exact values, where they can be edited as usual. A bottom rail other than ground is added as a last leg with only its voltage.
 
 #### Data Input Format
 ##### Value Units
@@ -530,18 +2040,742 @@ Supported unit prefixes:
 - **M** (mega, 10⁶),  
 - **G** (giga, 10⁹).
 
+The **10ⁿ** list next to a field sets the prefix of the entered value, replacing the one already there. Mind the case: `M` is mega, `m` is milli.
+
+The **★** list next to a leg field holds the resistance or voltage presets saved on the **Presets** page.
+
+Digits can be grouped: `1_000_000`, `1 000 000` or `1,000,000`. The period is always the decimal separator, and one number uses one kind of separator.
+
 ##### Uncertainty (Error Margins)
 Input values can include error margins using the following formats:  
 - Symmetrical error: 5% (±5% from the value),  
 - Asymmetrical positive error: +5%,  
 - Asymmetrical negative error: -5%,  
-- Symmetrical error: +/-5%.
+- Symmetrical error: +/-5%, or ±5% as pasted from documents (the `−` minus sign is accepted too).
+
+A sign in front of a number without `%` belongs to the value itself: `-5` is a negative value, `+12` is the same as `12`, while `-5%` is a tolerance. For example, `-5 +3% -2%` is −5 with +3%/−2% error.
+
+A value can also be entered by its datasheet limits: `typ 12 min 11.4 max 12.6` is 12 with +5%/−5% error. The typical value is required, `min` and `max` are optional and go in any order.
+
+**Settings** can define a default tolerance for every quantity, e.g. 1% for resistances. It is applied to a value entered without a tolerance and shown under the field (\"assumed ±1%\"); an explicit `0%` is kept.
+
+##### Resistor Networks
+A resistance field accepts a small resistor network: `||` is a parallel connection, `+` a series one, and parentheses group them. `||` binds tighter than `+`, so `(10k || 10k) + 1k` and `1k + 10k || 10k` are both 6k. The series `+` needs a space after it, otherwise it is read as a tolerance (`+5%`). Tolerances are combined: `10k 1% || 15k 1%` is 6k 1%. All parallel values must be above zero. A `-` with a space after it subtracts, e.g. the lead resistance from a two-terminal measurement of a low resistance: `0.5 1% - 20m` is 480mR, the tolerance of the measurement applies to the difference. The difference must stay above zero.
+
+##### Tolerance Mode
+With **Limits** selected in the sidebar the error margins are worst-case limits. With **Statistical** they are one standard deviation (1σ): the margins of the results are combined as the root of the sum of squares and shown at the chosen coverage (1σ, 2σ or 3σ). See the Ohm Law help for details.
 
 #### Results
 Once all required parameters are defined, the results will be displayed in a table below the input fields. Calculations account for any defined error margins and unit conversions. The results include:  
 - Voltage distribution across all legs,  
 - Current through each resistor,  
-- Power dissipated by each resistor.");
+- Power dissipated by each resistor.
+
+| Value                 | Formula                              |
+|-----------------------|--------------------------------------|
+| Divider current       | I = U₁ / (R₁ + R₂ + … + Rₙ)          |
+| Voltage across leg k  | ΔUₖ = Uₖ − Uₖ₊₁ = I × Rₖ, Uₙ₊₁ = 0 |
+| Power of leg k        | Pₖ = ΔUₖ × I                         |
+
+**Power color map** above the result table tints the label of every leg from green to red by its power against the rated power of the resistor, worst case over the tolerances. The rated power of each leg is entered next to the checkbox, **0.125W** if left empty. A leg without a solved power keeps its label as is.
+
+**Pin result** keeps the results of every leg for comparison: while you change the inputs, each leg shows its pinned nominal values and their change in percent (**Δ, %**).
+
+A right click on a value of the result table copies it as shown, e.g. `1.53mA`. With **Shift** held it copies the exact number in base units instead, e.g. `0.0015319148936170214`, ready for a spreadsheet.
+
+**⚙ Rows** above the result table hides the rows you do not use; the choice is kept apart from the one of the Ohm law scene.
+
+**Schematic** draws the divider as a resistor ladder from the top rail down to ground, with the voltage of every node and the resistance and current of every leg. A value that is not known yet is shown as `?`.
+
+**Show work** lists the formulas of the solution with the nominal values substituted: the series current first, then the calculated values of every leg from the bottom up.
+
+**Standard values** under the results lists, for every calculated resistance, the nearest value of the E24, E48 and E96 series and the error it brings in: `(standard − ideal) / ideal × 100`.
+
+**Sweep the supply voltage** solves the divider for every value of U1 given as a range (`4.5..5.5 step 0.5`) or a list. The table shows the nominal voltage of every tap at each point and, in the last two rows, the lowest and highest tap voltages over the whole sweep with the resistor tolerances.",
+        templates = templates::help()
+    );
 
     (title, text)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn divider(legs: &[(&str, &str)]) -> VoltageDivider {
+        let mut divider = VoltageDivider {
+            legs: vec![Leg::default(); legs.len()],
+            ..Default::default()
+        };
+        for (id, (resistance, voltage)) in legs.iter().enumerate() {
+            divider.update(Message::InputResistanceChanged(id, resistance.to_string()));
+            divider.update(Message::InputVoltageChanged(id, voltage.to_string()));
+        }
+
+        divider
+    }
+
+    #[test]
+    fn test_inconsistent_legs() {
+        assert!(divider(&[("1k", "10"), ("1k", "5")])
+            .inconsistent_legs()
+            .is_empty());
+
+        // 10V / 2k = 5mA, но через R2 по введённым значениям 2mA, через R1 — 8mA
+        let over = divider(&[("1k", "10"), ("1k", "2")]);
+        assert_eq!(over.inconsistent_legs(), vec![1, 2]);
+        assert_eq!(over.legs[1].resistance_raw, "1k");
+        assert_eq!(over.legs[1].voltage_raw, "2");
+        assert_eq!(
+            over.legs[1].voltage.as_ref().unwrap().get_nominal_value(),
+            2.0
+        );
+        assert_eq!(
+            over.legs[1]
+                .resistance
+                .as_ref()
+                .unwrap()
+                .get_nominal_value(),
+            1e3
+        );
+
+        // расхождение в пределах допусков не предупреждается
+        assert!(divider(&[("1k 10%", "10"), ("1k 10%", "5.4")])
+            .inconsistent_legs()
+            .is_empty());
+
+        // только одно плечо задано полностью: сверять нечего
+        assert!(divider(&[("1k", ""), ("1k", "5")])
+            .inconsistent_legs()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_input_error_kept() {
+        // ошибка ввода не заменяется расчётным значением
+        let divider = divider(&[("1k", "10"), ("1kx", "5")]);
+        assert!(matches!(
+            divider.legs[1].resistance,
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_example() {
+        let mut divider = divider(&[("1k", "5"), ("", ""), ("2k", "")]);
+        divider.update(Message::LoadExample);
+
+        // 9V на 10k + 10k: 4.5V в средней точке, 450uA, по 2.025mW на резистор
+        assert_eq!(divider.legs.len(), 2);
+        assert_eq!(divider.status(), SceneStatus::Solved);
+        let tap = &divider.legs[1];
+        assert_eq!(tap.voltage.as_ref().unwrap().get_value_nom(), "4.50V");
+        assert_eq!(tap.current.as_ref().unwrap().get_value_nom(), "450.00uA");
+        assert_eq!(tap.power.as_ref().unwrap().get_value_nom(), "2.02mW");
+    }
+
+    #[test]
+    fn test_templates() {
+        for template in TEMPLATES {
+            let mut divider = divider(&[("1k", "5"), ("1k", "")]);
+            divider.update(Message::TemplateSelected(template));
+
+            assert_eq!(divider.legs.len(), template.legs.len(), "{}", template);
+            assert_eq!(divider.status(), SceneStatus::Solved, "{}", template);
+            for leg in &divider.legs {
+                assert!(
+                    leg.voltage.is_ok() && leg.resistance.is_ok(),
+                    "{}",
+                    template
+                );
+                assert!(leg.current.is_ok() && leg.power.is_ok(), "{}", template);
+            }
+        }
+
+        // 3.3V × 10k / 0.8V − 10k
+        let mut divider = VoltageDivider::default();
+        divider.update(Message::TemplateSelected(TEMPLATES[2]));
+        let r1 = divider.legs[0].resistance.as_ref().unwrap();
+        assert!((r1.get_nominal_value() - 31.25e3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_default_legs() {
+        assert_eq!(VoltageDivider::default().legs.len(), 2);
+
+        set_default_legs(5);
+        assert_eq!(VoltageDivider::default().legs.len(), 5);
+        // пример задаёт свои плечи
+        assert_eq!(VoltageDivider::with_legs(&EXAMPLE).legs.len(), 2);
+
+        set_default_legs(100);
+        assert_eq!(default_legs(), LEGS_MAX);
+        set_default_legs(LEGS_DEFAULT);
+
+        assert_eq!(parse_leg_count(" 3 "), Some(3));
+        assert_eq!(parse_leg_count(""), Some(LEGS_DEFAULT));
+        assert_eq!(parse_leg_count("0"), None);
+        assert_eq!(parse_leg_count("-1"), None);
+    }
+
+    #[test]
+    fn test_negative_resistance() {
+        // через R3 идёт 5mA вниз, а отвод R2 ниже отвода R3
+        let inconsistent = divider(&[("", "10"), ("", "2"), ("1k", "5")]);
+        match &inconsistent.legs[1].resistance {
+            Err(ParserError::IncorrectInput(e)) => assert!(e.ends_with("R2")),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(
+            inconsistent.legs[0]
+                .resistance
+                .as_ref()
+                .unwrap()
+                .get_nominal_value(),
+            1.6e3
+        );
+
+        // отрицательная шина: ток течёт вверх, сопротивления положительные
+        let negative = divider(&[("", "-5"), ("1k", "-2")]);
+        assert_eq!(
+            negative.legs[0]
+                .resistance
+                .as_ref()
+                .unwrap()
+                .get_nominal_value(),
+            1.5e3
+        );
+        let negative = divider(&[("1k", "-5"), ("1k", "")]);
+        assert_eq!(
+            negative.legs[1]
+                .voltage
+                .as_ref()
+                .unwrap()
+                .get_nominal_value(),
+            -2.5
+        );
+    }
+
+    #[test]
+    fn test_current_budget_factor() {
+        // 10V over 10k -> 1mA
+        let mut divider = divider(&[("5k", "10"), ("5k", "")]);
+        assert_eq!(divider.series_current().unwrap().get_nominal_value(), 1e-3);
+        assert_eq!(divider.current_budget_factor(), None);
+
+        divider.update(Message::InputCurrentMaxChanged("2m".to_string()));
+        assert_eq!(divider.current_budget_factor(), None);
+
+        divider.update(Message::InputCurrentMaxChanged("250u".to_string()));
+        let factor = divider.current_budget_factor().unwrap();
+        assert!((factor - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_scale_resistors() {
+        let mut divider = divider(&[("4.7k 1%", "5"), ("10k 1%", "")]);
+        divider.update(Message::InputCurrentMaxChanged("100u".to_string()));
+
+        divider.update(Message::ScaleResistors);
+
+        assert_eq!(divider.legs[0].resistance_raw, "15.986k 1%");
+        assert_eq!(divider.legs[1].resistance_raw, "34.014k 1%");
+        assert_eq!(divider.current_budget_factor(), None);
+
+        // tap voltage is kept
+        let tap = divider.legs[1]
+            .voltage
+            .as_ref()
+            .unwrap()
+            .get_nominal_value();
+        assert!((tap - 5.0 * 10.0 / 14.7).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_reverse_current() {
+        let divider = divider(&[("1k", "5"), ("1k", "")]);
+        assert!(!divider.is_reverse_current());
+
+        let mut divider = divider;
+        divider.update(Message::InputVoltageChanged(0, "-5".to_string()));
+
+        let current = divider.series_current().unwrap();
+        assert_eq!(current.get_nominal_value(), -2.5e-3);
+        assert_eq!(current.get_value_nom(), "-2.50mA");
+        assert!(divider.is_reverse_current());
+    }
+
+    #[test]
+    fn test_legs_limit() {
+        let mut divider = VoltageDivider::default();
+        for _ in 0..LEGS_MAX + 10 {
+            divider.update(Message::LegAdd);
+        }
+        assert_eq!(divider.legs.len(), LEGS_MAX);
+        assert!(!divider.can_add_leg());
+
+        divider.update(Message::LegDelete(LEGS_MAX - 1));
+        assert_eq!(divider.legs.len(), LEGS_MAX - 1);
+        assert!(divider.can_add_leg());
+
+        let mut divider = VoltageDivider::default();
+        divider.update(Message::LegDelete(1));
+        divider.update(Message::LegDelete(5));
+        assert_eq!(divider.legs.len(), LEGS_MIN);
+    }
+
+    #[test]
+    fn test_single_leg() {
+        let mut divider = VoltageDivider::default();
+        divider.update(Message::LegDelete(1));
+        divider.update(Message::LegDelete(0));
+        assert_eq!(divider.legs.len(), 1);
+
+        divider.update(Message::InputResistanceChanged(0, "1k 1%".to_string()));
+        assert!(divider.legs[0].current.is_err());
+        assert_eq!(divider.status(), SceneStatus::Incomplete);
+
+        // один резистор на землю - просто закон Ома
+        divider.update(Message::InputVoltageChanged(0, "10".to_string()));
+        let leg = &divider.legs[0];
+        assert_eq!(leg.current.as_ref().unwrap().get_value_nom(), "10.00mA");
+        assert_eq!(leg.power.as_ref().unwrap().get_value_nom(), "100.00mW");
+        assert_eq!(leg.power.as_ref().unwrap().get_tol_percent_plus(), "1.00%");
+        assert_eq!(divider.status(), SceneStatus::Solved);
+
+        divider.update(Message::InputResistanceChanged(0, "".to_string()));
+        assert!(divider.legs[0].current.is_err());
+        assert!(divider.legs[0].power.is_err());
+    }
+
+    #[test]
+    fn test_lock_leg_field() {
+        let mut divider = divider(&[("1k", "10"), ("1k", "")]);
+        divider.update(Message::LegLockToggled(0, LegField::Voltage));
+
+        divider.update(Message::ClearLegField(0, LegField::Voltage));
+        assert_eq!(divider.legs[0].voltage_raw, "10");
+        assert_eq!(divider.status(), SceneStatus::Solved);
+
+        // the lock is per field
+        divider.update(Message::ClearLegField(0, LegField::Resistance));
+        assert!(divider.legs[0].resistance_raw.is_empty());
+
+        divider.update(Message::LegLockToggled(0, LegField::Voltage));
+        divider.update(Message::ClearLegField(0, LegField::Voltage));
+        assert!(divider.legs[0].voltage_raw.is_empty());
+    }
+
+    #[test]
+    fn test_leg_prefix_selected() {
+        let mut divider = divider(&[("10", "5"), ("10", "")]);
+        divider.update(Message::LegPrefixSelected(
+            1,
+            LegField::Resistance,
+            Dim::Kilo,
+        ));
+        assert_eq!(divider.legs[1].resistance_raw, "10k");
+
+        divider.update(Message::LegLockToggled(0, LegField::Voltage));
+        divider.update(Message::LegPrefixSelected(0, LegField::Voltage, Dim::Milli));
+        assert_eq!(divider.legs[0].voltage_raw, "5");
+    }
+
+    #[test]
+    fn test_leg_input_events() {
+        let mut divider = divider(&[("10", "5"), ("10", "")]);
+        let mut send =
+            |leg, field, event| divider.update(Message::from_leg_input(leg, field, event));
+        send(1, LegField::Resistance, Event::Prefix(Dim::Kilo));
+        send(1, LegField::Voltage, Event::Input("1".to_string()));
+        send(1, LegField::Voltage, Event::Clear);
+        send(0, LegField::Voltage, Event::LockToggled);
+        assert_eq!(divider.legs[1].resistance_raw, "10k");
+        assert!(divider.legs[1].voltage_raw.is_empty());
+        assert!(divider.legs[0].voltage_locked);
+
+        assert!(matches!(
+            Message::from_leg_input(1, LegField::Voltage, Event::Submit),
+            Message::FieldSubmitted(1, LegField::Voltage)
+        ));
+    }
+
+    #[test]
+    fn test_leg_preset_selected() {
+        let mut divider = divider(&[("10", "5"), ("10", "")]);
+        let load = Preset::new("Load", Kind::Resistance, "4.7k 1%").unwrap();
+        divider.update(Message::LegPresetSelected(
+            1,
+            LegField::Resistance,
+            load.clone(),
+        ));
+        assert_eq!(divider.legs[1].resistance_raw, "4.7k 1%");
+
+        divider.update(Message::LegPresetSelected(1, LegField::Voltage, load));
+        assert_eq!(divider.legs[1].voltage_raw, "");
+    }
+
+    #[test]
+    fn test_next_field() {
+        let mut three = divider(&[("1k", "10"), ("1k", ""), ("2k", "")]);
+
+        assert_eq!(
+            three.next_field(0, LegField::Resistance),
+            Some((0, LegField::Voltage))
+        );
+        assert_eq!(
+            three.next_field(0, LegField::Voltage),
+            Some((1, LegField::Resistance))
+        );
+        // from the bottom leg back to the top
+        assert_eq!(
+            three.next_field(2, LegField::Voltage),
+            Some((0, LegField::Resistance))
+        );
+
+        // закреплённые поля пропускаются
+        three.update(Message::LegLockToggled(1, LegField::Resistance));
+        three.update(Message::LegLockToggled(1, LegField::Voltage));
+        assert_eq!(
+            three.next_field(0, LegField::Voltage),
+            Some((2, LegField::Resistance))
+        );
+
+        let mut single = divider(&[("1k", "")]);
+        single.update(Message::LegLockToggled(0, LegField::Voltage));
+        assert_eq!(single.next_field(0, LegField::Resistance), None);
+        assert_eq!(single.next_field(5, LegField::Resistance), None);
+    }
+
+    #[test]
+    fn test_work_steps() {
+        let divider = divider(&[("1k", "10"), ("4k", "")]);
+
+        assert_eq!(
+            divider.steps(),
+            [
+                "I = (U − U0) / ΣR = (10.00V − 0) / 5.00kR = 2.00mA",
+                "U2 = I × R2 + 0 = 2.00mA × 4.00kR + 0 = 8.00V",
+                "P2 = (U2 − 0) × I = (8.00V − 0) × 2.00mA = 16.00mW",
+                "P1 = (U1 − U2) × I = (10.00V − 8.00V) × 2.00mA = 4.00mW",
+            ]
+        );
+
+        let mut unsolved = divider;
+        unsolved.update(Message::InputResistanceChanged(1, String::new()));
+        assert!(unsolved.steps().is_empty());
+    }
+
+    #[test]
+    fn test_clear_leg_field() {
+        let mut divider = divider(&[("1k", "10"), ("1k", "")]);
+        assert_eq!(divider.status(), SceneStatus::Solved);
+
+        divider.update(Message::ClearLegField(0, LegField::Voltage));
+        assert!(divider.legs[0].voltage_raw.is_empty());
+        assert!(matches!(
+            divider.legs[0].voltage,
+            Err(ParserError::EmptyInput)
+        ));
+        // сопротивления остаются
+        assert_eq!(divider.legs[0].resistance_raw, "1k");
+        assert!(divider.legs[1].current.is_err());
+        assert!(divider.is_dirty());
+
+        divider.update(Message::ClearLegField(1, LegField::Resistance));
+        assert!(matches!(
+            divider.legs[1].resistance,
+            Err(ParserError::EmptyInput)
+        ));
+        assert_eq!(divider.legs.len(), 2);
+    }
+
+    #[test]
+    fn test_stacked_tolerance_clamp() {
+        // допуски складываются: ток ±40%, напряжение нижнего плеча ±80%, мощность ±120%
+        let divider = divider(&[("1k 40%", "10"), ("1k 40%", "")]);
+        let leg = &divider.legs[1];
+
+        let power = leg.power.as_ref().unwrap();
+        assert_eq!(power.get_tol_percent_minus(), "-120.00%");
+        assert_eq!(power.get_value_min(), "0.00W (clamped)");
+        // the current may change its direction, it is not clamped
+        assert_eq!(leg.current.as_ref().unwrap().get_value_min(), "3.00mA");
+        assert_eq!(leg.voltage.as_ref().unwrap().get_value_min(), "1.00V");
+    }
+
+    #[test]
+    fn test_leg_hint() {
+        let divider = divider(&[("4.7k", "12"), ("1k", "")]);
+
+        assert_eq!(
+            leg_hint("R1", "U1", &divider.legs[0]),
+            Some("R1 = 4700 Ω, U1 = 12 V".to_string())
+        );
+        // рассчитанное напряжение не подписывается
+        assert_eq!(
+            leg_hint("R2", "U2", &divider.legs[1]),
+            Some("R2 = 1000 Ω".to_string())
+        );
+        assert_eq!(leg_hint("R1", "U1", &Leg::default()), None);
+    }
+
+    #[test]
+    fn test_parse_taps() {
+        assert_eq!(parse_taps("1, 2.5, 3.3"), Ok(vec![1.0, 2.5, 3.3]));
+        assert_eq!(parse_taps(" "), Err(ParserError::EmptyInput));
+        assert!(matches!(
+            parse_taps("1,,2"),
+            Err(ParserError::IncorrectInput(_))
+        ));
+        assert!(matches!(
+            parse_taps("1, x"),
+            Err(ParserError::IncorrectInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_legs() {
+        let mut divider = VoltageDivider::default();
+        divider.update(Message::GeneratorModeToggled(true));
+        divider.update(Message::InputTopVoltageChanged("5".to_string()));
+        divider.update(Message::InputTapsChanged("1, 2.5, 3.3".to_string()));
+        divider.update(Message::InputGeneratorTotalChanged("100k".to_string()));
+        assert_eq!(divider.status(), SceneStatus::Solved);
+        assert_eq!(divider.create_legs_count(), Some(4));
+
+        divider.update(Message::CreateLegs);
+        assert!(!divider.generator_mode);
+        let raw: Vec<_> = divider
+            .legs
+            .iter()
+            .map(|leg| (leg.resistance_raw.as_str(), leg.voltage_raw.as_str()))
+            .collect();
+        assert_eq!(
+            raw,
+            [("34k", "5"), ("16k", "3.3"), ("30k", "2.5"), ("20k", "1")]
+        );
+        let current = divider.series_current().unwrap();
+        assert_eq!(current.get_value_nom(), "50.00uA");
+
+        // нижняя шина не земля - отдельное плечо только с напряжением
+        divider.update(Message::InputBottomVoltageChanged("-5".to_string()));
+        divider.update(Message::InputTapsChanged("0".to_string()));
+        divider.update(Message::CreateLegs);
+        let raw: Vec<_> = divider
+            .legs
+            .iter()
+            .map(|leg| (leg.resistance_raw.as_str(), leg.voltage_raw.as_str()))
+            .collect();
+        assert_eq!(raw, [("50k", "5"), ("50k", "0"), ("", "-5")]);
+        assert_eq!(
+            divider.series_current().unwrap().get_value_nom(),
+            "100.00uA"
+        );
+    }
+
+    #[test]
+    fn test_create_legs_invalid() {
+        let mut divider = VoltageDivider::default();
+        divider.update(Message::GeneratorModeToggled(true));
+        divider.update(Message::InputTopVoltageChanged("5".to_string()));
+        divider.update(Message::InputTapsChanged("1, 3.3, 2.5".to_string()));
+        divider.update(Message::InputGeneratorTotalChanged("100k".to_string()));
+        assert_eq!(
+            divider.generated_legs(),
+            Some(Err(GeneratorError::TapsNotMonotonic))
+        );
+        assert_eq!(divider.status(), SceneStatus::Error);
+
+        // плечи не меняются
+        divider.update(Message::CreateLegs);
+        assert!(divider.generator_mode);
+        assert_eq!(divider.legs.len(), 2);
+    }
+
+    #[test]
+    fn test_leg_power() {
+        let divider = divider(&[("1k", "10"), ("4k", "")]);
+
+        let power: Vec<_> = divider
+            .legs
+            .iter()
+            .map(|leg| leg.power.as_ref().unwrap().get_nominal_value())
+            .collect();
+        assert!((power[0] - 4e-3).abs() < 1e-12);
+        assert!((power[1] - 16e-3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_power_ratio() {
+        let mut divider = divider(&[("1k", "10"), ("4k", "")]);
+
+        // пустое поле: 0.125W
+        let ratio = divider.legs[1].power_ratio().unwrap();
+        assert!((ratio - 16e-3 / 0.125).abs() < 1e-12);
+
+        divider.update(Message::LegRatedPowerChanged(1, "16m".to_string()));
+        assert!((divider.legs[1].power_ratio().unwrap() - 1.0).abs() < 1e-12);
+
+        divider.update(Message::LegRatedPowerChanged(1, "1/4W".to_string()));
+        assert_eq!(divider.legs[1].power_ratio(), None);
+
+        // без мощности нет и нагрузки
+        divider.update(Message::InputVoltageChanged(0, String::new()));
+        assert_eq!(divider.legs[0].power_ratio(), None);
+    }
+
+    fn ratio_divider(ratio: &str, total: &str) -> VoltageDivider {
+        let mut divider = VoltageDivider::default();
+        divider.update(Message::RatioModeToggled(true));
+        divider.update(Message::InputRatioChanged(ratio.to_string()));
+        divider.update(Message::InputTotalResistanceChanged(total.to_string()));
+
+        divider
+    }
+
+    #[test]
+    fn test_ratio_half() {
+        let divider = ratio_divider("0.5", "10k");
+        let (r1, r2) = divider.ratio_legs().unwrap();
+        assert_eq!(r1.get_nominal_value(), 5000.0);
+        assert_eq!(r2.get_nominal_value(), 5000.0);
+
+        let r1 = ESeries::E24.nearest_resistance(&r1).unwrap();
+        let r2 = ESeries::E24.nearest_resistance(&r2).unwrap();
+        assert_eq!(r1.get_value_input(), "5.1k 5%");
+        assert_eq!(r2.get_value_input(), "5.1k 5%");
+        assert_eq!(ratio_of(&r1, &r2), 0.5);
+    }
+
+    #[test]
+    fn test_ratio_0_3() {
+        let divider = ratio_divider("0.3", "10k 1%");
+        let (r1, r2) = divider.ratio_legs().unwrap();
+        assert!((r1.get_nominal_value() - 7000.0).abs() < 1e-9);
+        assert!((r2.get_nominal_value() - 3000.0).abs() < 1e-9);
+        assert_eq!(
+            r2.get_tolerance(),
+            Some(Tolerance {
+                plus: 1.0,
+                minus: 1.0
+            })
+        );
+
+        let r1 = ESeries::E24.nearest_resistance(&r1).unwrap();
+        let r2 = ESeries::E24.nearest_resistance(&r2).unwrap();
+        assert_eq!(r1.get_nominal_value(), 6800.0);
+        assert_eq!(r2.get_nominal_value(), 3000.0);
+        assert!((ratio_of(&r1, &r2) - 3.0 / 9.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ratio_out_of_range() {
+        let divider = ratio_divider("1.5", "10k");
+        assert!(matches!(
+            divider.ratio.ratio,
+            Err(ParserError::IncorrectInput(_))
+        ));
+        assert!(divider.ratio_legs().is_none());
+    }
+
+    #[test]
+    fn test_recalculate() {
+        let mut divider = ratio_divider("2", "10k");
+        crate::i18n::set_lang(crate::i18n::Lang::Russian);
+        divider.recalculate();
+        crate::i18n::set_lang(crate::i18n::Lang::English);
+
+        assert_eq!(
+            divider.ratio.ratio,
+            Err(ParserError::IncorrectInput(
+                "коэффициент должен быть между 0 и 1".to_string()
+            ))
+        );
+        assert_eq!(divider.ratio.total_raw, "10k");
+    }
+
+    #[test]
+    fn test_diagnostic_report() {
+        let divider = divider(&[("1k 1%", "10"), ("1k", ""), ("x", "")]);
+
+        let report: serde_json::Value = serde_json::from_str(&divider.diagnostic_report()).unwrap();
+        let mut fixture: serde_json::Value = serde_json::from_str(include_str!(
+            "../../tests/fixtures/voltage_divider_report.json"
+        ))
+        .unwrap();
+        fixture["version"] = env!("CARGO_PKG_VERSION").into();
+
+        assert_eq!(report, fixture);
+    }
+
+    #[test]
+    fn test_pin_result() {
+        let mut divider = divider(&[("1k", "10"), ("1k", "")]);
+        divider.update(Message::PinResult);
+
+        divider.update(Message::InputResistanceChanged(1, "3k".to_string()));
+        let pinned = divider.pinned.clone().unwrap();
+        let live = divider.snapshot();
+        // ток 5 мА -> 2.5 мА, напряжение середины 5 В -> 7.5 В
+        let delta = pinned[1].delta(&live[1]);
+        assert!((delta[0].unwrap() - 50.0).abs() < 1e-9);
+        assert!((delta[1].unwrap() + 50.0).abs() < 1e-9);
+        assert!((delta[2].unwrap() - 200.0).abs() < 1e-9);
+
+        divider.update(Message::Unpin);
+        assert!(divider.pinned.is_none());
+    }
+
+    #[test]
+    fn test_dirty() {
+        let mut ratio = VoltageDivider::default();
+        ratio.update(Message::LegAdd);
+        ratio.update(Message::RatioModeToggled(true));
+        assert!(!ratio.is_dirty());
+
+        ratio.update(Message::InputTotalResistanceChanged("10k".to_string()));
+        assert!(ratio.is_dirty());
+        ratio.update(Message::InputTotalResistanceChanged("".to_string()));
+        assert!(!ratio.is_dirty());
+
+        let mut divider = divider(&[("1k", "10"), ("1k", "")]);
+        assert!(divider.is_dirty());
+        // the second leg voltage is calculated, it does not count
+        divider.update(Message::InputResistanceChanged(0, "".to_string()));
+        divider.update(Message::InputResistanceChanged(1, "".to_string()));
+        divider.update(Message::InputVoltageChanged(0, "".to_string()));
+        assert!(!divider.is_dirty());
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(VoltageDivider::default().status(), SceneStatus::Empty);
+        assert_eq!(
+            divider(&[("1k", "10"), ("", "")]).status(),
+            SceneStatus::Incomplete
+        );
+        assert_eq!(
+            divider(&[("1k", "10"), ("1kk", "")]).status(),
+            SceneStatus::Error
+        );
+        assert_eq!(
+            divider(&[("1k", "10"), ("1k", "")]).status(),
+            SceneStatus::Solved
+        );
+
+        assert_eq!(ratio_divider("0.3", "").status(), SceneStatus::Incomplete);
+        assert_eq!(ratio_divider("1.5", "10k").status(), SceneStatus::Error);
+        assert_eq!(ratio_divider("0.3", "10k").status(), SceneStatus::Solved);
+    }
+
+    #[test]
+    fn test_scale_resistors_without_budget() {
+        let mut divider = divider(&[("1k", "10"), ("1k", "")]);
+
+        divider.update(Message::ScaleResistors);
+
+        assert_eq!(divider.legs[0].resistance_raw, "1k");
+        assert_eq!(divider.legs[1].resistance_raw, "1k");
+    }
+}
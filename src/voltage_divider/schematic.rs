@@ -0,0 +1,191 @@
+//! # Schematic
+//!
+//! The divider drawn as a vertical resistor ladder: the top rail with U1, a resistor per leg
+//! with the node voltages between them and ground at the bottom. Every label shows the
+//! solved value or `?` while it is not known, so the picture follows the inputs live.
+
+use iced::alignment::Vertical;
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, Text};
+use iced::{mouse, Element, Fill, Point, Rectangle, Renderer, Size, Theme};
+
+use super::Leg;
+use crate::style;
+use crate::types::{Measurement, ParserError};
+
+const MARGIN: f32 = 20.0;
+const WIRE_X: f32 = 40.0;
+// расстояние между соседними узлами
+const LEG_HEIGHT: f32 = 70.0;
+const RESISTOR: Size = Size::new(14.0, 36.0);
+const NODE_RADIUS: f32 = 3.0;
+const GROUND_WIDTH: f32 = 24.0;
+const GROUND_HEIGHT: f32 = 14.0;
+const LABEL_GAP: f32 = 14.0;
+const TEXT_SIZE: f32 = 13.0;
+
+/// Labels of one leg: its top node, its resistor and the current through it.
+#[derive(Debug, Clone, PartialEq)]
+struct LegLabels {
+    node: String,
+    resistor: String,
+    current: Option<String>,
+}
+
+fn labels(legs: &[Leg]) -> Vec<LegLabels> {
+    fn value<M: Measurement>(data: &Result<M, ParserError>) -> String {
+        data.as_ref()
+            .map(|m| m.get_value_nom())
+            .unwrap_or_else(|_| "?".to_string())
+    }
+
+    legs.iter()
+        .enumerate()
+        .map(|(id, leg)| LegLabels {
+            node: format!("U{} = {}", id + 1, value(&leg.voltage)),
+            resistor: format!("R{} = {}", id + 1, value(&leg.resistance)),
+            current: leg
+                .current
+                .as_ref()
+                .ok()
+                .map(|i| format!("I = {}", i.get_value_nom())),
+        })
+        .collect()
+}
+
+struct Schematic {
+    legs: Vec<LegLabels>,
+}
+
+impl<Message> canvas::Program<Message> for Schematic {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let color = theme.palette().text;
+        let hint = style::hint_text(theme).color.unwrap_or(color);
+        let stroke = || Stroke::default().with_color(color).with_width(1.5);
+        let text = |content: String, position: Point, color| Text {
+            content,
+            position,
+            color,
+            size: TEXT_SIZE.into(),
+            vertical_alignment: Vertical::Center,
+            ..Text::default()
+        };
+
+        let bottom = MARGIN + LEG_HEIGHT * self.legs.len() as f32;
+        for (id, leg) in self.legs.iter().enumerate() {
+            let top = MARGIN + LEG_HEIGHT * id as f32;
+            let middle = top + LEG_HEIGHT / 2.0;
+            let resistor_top = middle - RESISTOR.height / 2.0;
+
+            // провод от узла до резистора и от резистора до следующего узла
+            frame.stroke(
+                &Path::line(Point::new(WIRE_X, top), Point::new(WIRE_X, resistor_top)),
+                stroke(),
+            );
+            frame.stroke(
+                &Path::line(
+                    Point::new(WIRE_X, resistor_top + RESISTOR.height),
+                    Point::new(WIRE_X, top + LEG_HEIGHT),
+                ),
+                stroke(),
+            );
+            frame.stroke(
+                &Path::rectangle(
+                    Point::new(WIRE_X - RESISTOR.width / 2.0, resistor_top),
+                    RESISTOR,
+                ),
+                stroke(),
+            );
+
+            frame.fill(&Path::circle(Point::new(WIRE_X, top), NODE_RADIUS), color);
+            frame.fill_text(text(
+                leg.node.clone(),
+                Point::new(WIRE_X + LABEL_GAP, top),
+                color,
+            ));
+
+            let label_x = WIRE_X + RESISTOR.width / 2.0 + LABEL_GAP;
+            match &leg.current {
+                Some(current) => {
+                    frame.fill_text(text(
+                        leg.resistor.clone(),
+                        Point::new(label_x, middle - TEXT_SIZE / 2.0 - 1.0),
+                        color,
+                    ));
+                    frame.fill_text(text(
+                        current.clone(),
+                        Point::new(label_x, middle + TEXT_SIZE / 2.0 + 1.0),
+                        hint,
+                    ));
+                }
+                None => {
+                    frame.fill_text(text(
+                        leg.resistor.clone(),
+                        Point::new(label_x, middle),
+                        color,
+                    ));
+                }
+            }
+        }
+
+        // земля: три сужающиеся черты под последним резистором
+        for line in 0..3 {
+            let half = GROUND_WIDTH / 2.0 * (1.0 - line as f32 / 3.0);
+            let y = bottom + GROUND_HEIGHT / 3.0 * line as f32;
+            frame.stroke(
+                &Path::line(Point::new(WIRE_X - half, y), Point::new(WIRE_X + half, y)),
+                stroke(),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// The schematic of `legs`, as tall as the ladder needs.
+pub fn view<'a, Message: 'a>(legs: &[Leg]) -> Element<'a, Message> {
+    let height = 2.0 * MARGIN + LEG_HEIGHT * legs.len() as f32 + GROUND_HEIGHT;
+
+    canvas::Canvas::new(Schematic { legs: labels(legs) })
+        .width(Fill)
+        .height(height)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voltage_divider::solve_legs;
+
+    #[test]
+    fn test_labels() {
+        let mut legs = [
+            Leg::new("1k".to_string(), "10".to_string()),
+            Leg::new("4k".to_string(), String::new()),
+        ];
+        solve_legs(&mut legs);
+
+        let solved = labels(&legs);
+        assert_eq!(solved.len(), 2);
+        assert_eq!(solved[0].node, "U1 = 10.00V");
+        assert_eq!(solved[0].resistor, "R1 = 1.00kR");
+        assert_eq!(solved[0].current.as_deref(), Some("I = 2.00mA"));
+        assert_eq!(solved[1].node, "U2 = 8.00V");
+
+        // нерешённый делитель: неизвестные значения помечены
+        let legs = [Leg::new("1k".to_string(), String::new()), Leg::default()];
+        let unsolved = labels(&legs);
+        assert_eq!(unsolved[0].node, "U1 = ?");
+        assert_eq!(unsolved[1].resistor, "R2 = ?");
+        assert_eq!(unsolved[1].current, None);
+    }
+}
@@ -0,0 +1,116 @@
+//! # Supply Sweep
+//!
+//! Tap voltages of the divider while the supply voltage of the top leg runs over a sweep,
+//! e.g. whether a reset threshold divider still trips from 4.5 to 5.5 V. Every point is
+//! solved as if its voltage were entered in the top leg, with the tolerance of the entered
+//! supply or the default one.
+
+use serde::Serialize;
+
+use super::{solve_legs, Leg};
+use crate::report::Bounds;
+use crate::types::{default_tolerance, voltage::Voltage, Measurement};
+
+/// Tap voltages of the divider at every point of a supply sweep, ready to be plotted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SweepResult {
+    /// Supply voltage of every point.
+    pub supply: Vec<f64>,
+    /// Taps from the top down.
+    pub taps: Vec<TapSweep>,
+}
+
+/// Voltage of one tap over the sweep.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TapSweep {
+    /// Number of the leg whose voltage is the tap, e.g. 2 for U2.
+    pub leg: usize,
+    /// Voltage at every point of the sweep.
+    pub voltages: Vec<Bounds>,
+    /// Lowest and highest voltage over the whole sweep, tolerances included.
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Solves the divider at every supply voltage of `points`. `None` if there is no tap below
+/// the top leg or the divider does not solve at some point.
+pub fn sweep_supply(legs: &[Leg], points: &[f64]) -> Option<SweepResult> {
+    let top = legs.first()?;
+    if legs.len() < 2 || points.is_empty() {
+        return None;
+    }
+
+    let tolerance = top.voltage.as_ref().ok().and_then(|v| v.tolerance);
+    let mut taps: Vec<TapSweep> = (2..=legs.len())
+        .map(|leg| TapSweep {
+            leg,
+            voltages: Vec::with_capacity(points.len()),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        })
+        .collect();
+
+    for &value in points {
+        let supply = Voltage { value, tolerance };
+        let supply = Voltage {
+            tolerance: default_tolerance::apply(&supply),
+            ..supply
+        };
+
+        let mut legs = legs.to_vec();
+        // как будто напряжение введено в верхнее плечо
+        legs[0].voltage_raw = supply.get_value_input();
+        legs[0].voltage = Ok(supply);
+        solve_legs(&mut legs);
+
+        for (tap, leg) in taps.iter_mut().zip(&legs[1..]) {
+            let voltage = Bounds::of(leg.voltage.as_ref().ok()?);
+            tap.min = tap.min.min(voltage.min);
+            tap.max = tap.max.max(voltage.max);
+            tap.voltages.push(voltage);
+        }
+    }
+
+    Some(SweepResult {
+        supply: points.to_vec(),
+        taps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_leg_sweep() {
+        let legs = [
+            Leg::new("10k 1%".to_string(), String::new()),
+            Leg::new("10k 1%".to_string(), String::new()),
+        ];
+
+        let result = sweep_supply(&legs, &[4.5, 5.0, 5.5]).unwrap();
+        assert_eq!(result.supply, [4.5, 5.0, 5.5]);
+        assert_eq!(result.taps.len(), 1);
+
+        let tap = &result.taps[0];
+        assert_eq!(tap.leg, 2);
+        let nominal: Vec<f64> = tap.voltages.iter().map(|v| v.nominal).collect();
+        for (nominal, expected) in nominal.iter().zip([2.25, 2.5, 2.75]) {
+            assert!((nominal - expected).abs() < 1e-12, "{:?}", nominal);
+        }
+        // крайние значения учитывают допуски резисторов
+        assert!(tap.min < 2.25 && tap.min > 2.2);
+        assert!(tap.max > 2.75 && tap.max < 2.81);
+        assert_eq!(tap.min, tap.voltages[0].min);
+        assert_eq!(tap.max, tap.voltages[2].max);
+    }
+
+    #[test]
+    fn test_sweep_unsolved() {
+        let legs = [Leg::new("10k".to_string(), String::new()), Leg::default()];
+        assert_eq!(sweep_supply(&legs, &[5.0]), None);
+
+        let single = [Leg::new("10k".to_string(), String::new())];
+        assert_eq!(sweep_supply(&single, &[5.0]), None);
+    }
+}
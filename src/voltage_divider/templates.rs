@@ -0,0 +1,50 @@
+//! # Divider Templates
+//!
+//! Common topologies to start from instead of an empty pair of legs. A template fills in
+//! the resistance and top voltage of every leg as if typed, so every value stays editable;
+//! a leg without a resistance is solved from the voltages around it.
+
+use std::fmt;
+
+use crate::i18n::{t, Key};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Template {
+    pub name: Key,
+    pub description: Key,
+    /// Resistance and voltage of every leg from the top, empty where the value is solved.
+    pub legs: &'static [(&'static str, &'static str)],
+}
+
+pub const TEMPLATES: [Template; 3] = [
+    Template {
+        name: Key::TemplateHalfSupply,
+        description: Key::TemplateHalfSupplyHint,
+        legs: &[("100k", "12"), ("100k", "")],
+    },
+    Template {
+        name: Key::TemplateLadder,
+        description: Key::TemplateLadderHint,
+        legs: &[("10k", "12"), ("10k", ""), ("10k", ""), ("10k", "")],
+    },
+    // R1 рассчитывается по напряжению обратной связи 0.8V
+    Template {
+        name: Key::TemplateFeedback,
+        description: Key::TemplateFeedbackHint,
+        legs: &[("", "3.3"), ("10k 1%", "0.8")],
+    },
+];
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", t(self.name))
+    }
+}
+
+/// Help section listing the templates.
+pub fn help() -> String {
+    TEMPLATES
+        .iter()
+        .map(|template| format!("- **{}**: {}\n", template, t(template.description)))
+        .collect()
+}
@@ -0,0 +1,68 @@
+//! # Standard Value Check
+//!
+//! A collapsible list under the results: for every calculated resistance, the nearest value
+//! of each precise E-series and the error it brings in. Nothing is shown while no resistance
+//! is calculated.
+
+use iced::widget::{button, Button, Column, Text};
+use iced::Element;
+
+use super::table::{Table, TableRow};
+use crate::eseries::ESeries;
+use crate::i18n::{t, Key};
+use crate::types::{resistance::Resistance, Measurement};
+
+/// Rows of one resistance: the series, its nearest value and the error in percent.
+fn rows<Message>(ideal: f64) -> Vec<TableRow<Message>> {
+    ESeries::PRECISE
+        .iter()
+        .filter_map(|series| {
+            let (standard, error) = series.nearest_error(ideal)?;
+            let standard = Resistance {
+                value: standard,
+                tolerance: None,
+            };
+
+            Some(TableRow::new(vec![
+                series.to_string().into(),
+                standard.get_value_nom().into(),
+                format!("{:+.2}", error).into(),
+            ]))
+        })
+        .collect()
+}
+
+/// The list for the calculated `resistances`, given by their labels, e.g. `("R2", 3456.0)`.
+/// `on_toggle` expands or collapses it.
+pub fn view<Message: Clone + 'static>(
+    resistances: &[(String, f64)],
+    expanded: bool,
+    on_toggle: Message,
+) -> Element<'static, Message> {
+    if resistances.is_empty() {
+        return Column::new().into();
+    }
+
+    let arrow = if expanded { "▾" } else { "▸" };
+    let toggle = Button::new(Text::new(format!("{} {}", arrow, t(Key::StandardValues))).size(14))
+        .on_press(on_toggle)
+        .style(button::text)
+        .padding([2, 0]);
+    let column = Column::new().push(toggle).padding([5, 0]);
+    if !expanded {
+        return column.into();
+    }
+
+    let header = vec![
+        String::new(),
+        t(Key::NearestValue).to_string(),
+        t(Key::ErrorPercent).to_string(),
+    ];
+    let table = resistances
+        .iter()
+        .fold(Table::new(header), |table, (label, ideal)| {
+            table.section(label.clone(), rows(*ideal))
+        });
+
+    column.push(table.view()).into()
+}
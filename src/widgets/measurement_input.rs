@@ -0,0 +1,310 @@
+//! # Measurement Input
+//!
+//! A labelled input field of a measurement with the controls next to it: the SI prefix list,
+//! the presets of the same kind, a ✕ button that clears the field and the lock toggle, and
+//! the hint or parse error under it. The controls report an [`Event`], the scene maps it to
+//! its own message.
+//!
+//! ```rust
+//! # use ecw::widgets::measurement_input::{Event, MeasurementInput};
+//! # let _: iced::Element<'_, Event> =
+//! MeasurementInput::new("Voltage", "12 5%", |event| event)
+//!     .lock(false)
+//!     .hint("12.00V", ecw::style::hint_text)
+//!     .view()
+//! # ;
+//! ```
+
+use std::rc::Rc;
+
+use iced::widget::{button, pick_list, text_input, Column, Container, Row, Text, TextInput};
+use iced::{Alignment, Element};
+
+use crate::i18n::{t, Key};
+use crate::presets::Preset;
+use crate::style::TextStyle;
+use crate::types::Dim;
+
+const FIELD_HEIGHT: u16 = 30;
+const PREFIX_WIDTH: u16 = 60;
+const PRESET_WIDTH: u16 = 50;
+const BUTTON_WIDTH: u16 = 30;
+const HINT_SIZE: u16 = 12;
+
+/// What the user did with the input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Input(String),
+    /// Enter in the field.
+    Submit,
+    Clear,
+    /// A prefix picked from the list, see [`Dim::append_to`].
+    Prefix(Dim),
+    Preset(Preset),
+    LockToggled,
+    /// The button next to a parse error, see [`MeasurementInput::error_report`].
+    CopyErrorReport,
+}
+
+/// Controls shown next to the field, following from the options of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Controls {
+    editable: bool,
+    prefix: bool,
+    preset: bool,
+    clear: bool,
+    lock: bool,
+    error_report: bool,
+}
+
+pub struct MeasurementInput<'a, Message> {
+    label: String,
+    value: &'a str,
+    on_event: Rc<dyn Fn(Event) -> Message + 'a>,
+    id: Option<text_input::Id>,
+    label_width: u16,
+    text_size: Option<u16>,
+    enabled: bool,
+    locked: Option<bool>,
+    prefixes: bool,
+    presets: Vec<Preset>,
+    hint: Option<(String, TextStyle)>,
+    error_report: bool,
+}
+
+impl<'a, Message: Clone + 'a> MeasurementInput<'a, Message> {
+    /// An enabled field with the prefix list and without a lock, presets or hint.
+    pub fn new(
+        label: impl Into<String>,
+        value: &'a str,
+        on_event: impl Fn(Event) -> Message + 'a,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            on_event: Rc::new(on_event),
+            id: None,
+            label_width: BUTTON_WIDTH,
+            text_size: None,
+            enabled: true,
+            locked: None,
+            prefixes: true,
+            presets: Vec::new(),
+            hint: None,
+            error_report: false,
+        }
+    }
+
+    /// Name of the input widget, see [`crate::focus`].
+    pub fn id(mut self, id: impl Into<text_input::Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Width of the label, the hint line is indented by it too.
+    pub fn label_width(mut self, width: u16) -> Self {
+        self.label_width = width;
+        self
+    }
+
+    /// Text size of the label and the field, the default one if not set.
+    pub fn text_size(mut self, size: u16) -> Self {
+        self.text_size = Some(size);
+        self
+    }
+
+    /// A disabled field is read-only and has no controls but the lock.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Shows the lock toggle; a locked field is read-only like a disabled one.
+    pub fn lock(mut self, locked: bool) -> Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    pub fn prefixes(mut self, prefixes: bool) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Presets to offer, no list if empty.
+    pub fn presets(mut self, presets: Vec<Preset>) -> Self {
+        self.presets = presets;
+        self
+    }
+
+    /// The line under the field, e.g. the parsed value or the parse error.
+    pub fn hint(mut self, text: impl Into<String>, style: TextStyle) -> Self {
+        self.hint = Some((text.into(), style));
+        self
+    }
+
+    /// Shows the "Copy error report" button next to the hint.
+    pub fn error_report(mut self, shown: bool) -> Self {
+        self.error_report = shown;
+        self
+    }
+
+    fn controls(&self) -> Controls {
+        let editable = self.enabled && self.locked != Some(true);
+
+        Controls {
+            editable,
+            prefix: editable && self.prefixes,
+            preset: editable && !self.presets.is_empty(),
+            clear: editable && !self.value.is_empty(),
+            lock: self.locked.is_some(),
+            error_report: self.error_report && self.hint.is_some(),
+        }
+    }
+
+    pub fn view(self) -> Element<'a, Message> {
+        let controls = self.controls();
+        let on_event = self.on_event;
+        // пустое место вместо скрытой кнопки, чтобы поля оставались выровненными
+        let blank = |width: u16| -> Element<'a, Message> { Text::new("").width(width).into() };
+
+        let mut label = Text::new(self.label)
+            .width(self.label_width)
+            .height(FIELD_HEIGHT)
+            .align_y(Alignment::Center);
+        let mut input = TextInput::new("", self.value);
+        if let Some(size) = self.text_size {
+            label = label.size(size);
+            input = input.size(size);
+        }
+        if let Some(id) = self.id {
+            input = input.id(id);
+        }
+        if controls.editable {
+            let on_input = on_event.clone();
+            input = input
+                .on_input(move |s| on_input(Event::Input(s)))
+                .on_submit(on_event(Event::Submit));
+        }
+
+        let prefix = if controls.prefix {
+            let on_prefix = on_event.clone();
+            pick_list(Dim::PREFIXES, None::<Dim>, move |dim| {
+                on_prefix(Event::Prefix(dim))
+            })
+            .placeholder("10ⁿ")
+            .text_size(12)
+            .width(PREFIX_WIDTH)
+            .into()
+        } else {
+            blank(PREFIX_WIDTH)
+        };
+        let preset = if controls.preset {
+            let on_preset = on_event.clone();
+            pick_list(self.presets, None::<Preset>, move |preset| {
+                on_preset(Event::Preset(preset))
+            })
+            .placeholder("★")
+            .text_size(12)
+            .width(PRESET_WIDTH)
+            .into()
+        } else {
+            blank(PRESET_WIDTH)
+        };
+        let clear = if controls.clear {
+            button(Text::new("✕").size(12))
+                .on_press(on_event(Event::Clear))
+                .style(button::text)
+                .width(BUTTON_WIDTH)
+                .height(FIELD_HEIGHT)
+                .into()
+        } else {
+            blank(BUTTON_WIDTH)
+        };
+
+        let mut row = Row::new()
+            .push(label)
+            .push(input)
+            .push(prefix)
+            .push(preset)
+            .push(clear);
+        // закреплённое поле выделено основным стилем кнопки
+        if let Some(locked) = self.locked {
+            row = row.push(
+                button(Text::new(if locked { "🔒" } else { "🔓" }).size(12))
+                    .on_press(on_event(Event::LockToggled))
+                    .style(if locked {
+                        button::primary
+                    } else {
+                        button::text
+                    })
+                    .width(BUTTON_WIDTH)
+                    .height(FIELD_HEIGHT),
+            );
+        }
+
+        let Some((hint, hint_style)) = self.hint else {
+            return row.into();
+        };
+        let mut hint = Row::new()
+            .push(Text::new(hint).size(HINT_SIZE).style(hint_style))
+            .spacing(10)
+            .align_y(Alignment::Center);
+        if controls.error_report {
+            hint = hint.push(
+                button(Text::new(t(Key::CopyErrorReport)).size(HINT_SIZE))
+                    .on_press(on_event(Event::CopyErrorReport))
+                    .style(button::text)
+                    .padding(0),
+            );
+        }
+        let hint = Container::new(hint)
+            .align_y(Alignment::Center)
+            .padding([0, self.label_width]);
+
+        Column::new().push(row).push(hint).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::Kind;
+    use crate::style;
+
+    fn input(value: &str) -> MeasurementInput<'_, Event> {
+        MeasurementInput::new("U", value, |event| event)
+    }
+
+    #[test]
+    fn test_controls() {
+        let controls = input("").controls();
+        assert!(controls.editable && controls.prefix);
+        assert!(!controls.preset && !controls.clear && !controls.lock);
+
+        let preset = Preset::new("Li-ion", Kind::Voltage, "3.7").unwrap();
+        let controls = input("12")
+            .presets(vec![preset.clone()])
+            .lock(false)
+            .controls();
+        assert!(controls.preset && controls.clear && controls.lock);
+
+        // закреплённое и недоступное поля только для чтения, замок остаётся
+        for field in [
+            input("12").presets(vec![preset]).lock(true),
+            input("12").enabled(false).lock(false),
+        ] {
+            let controls = field.controls();
+            assert!(!controls.editable && !controls.prefix && !controls.preset);
+            assert!(!controls.clear && controls.lock);
+        }
+
+        assert!(!input("5").prefixes(false).controls().prefix);
+
+        // кнопка отчёта стоит рядом с подсказкой
+        assert!(!input("x").error_report(true).controls().error_report);
+        let field = input("x")
+            .hint("error", style::hint_text)
+            .error_report(true);
+        assert!(field.controls().error_report);
+    }
+}
@@ -0,0 +1,6 @@
+//! Reusable view components shared by the scenes.
+
+pub mod eseries_check;
+pub mod measurement_input;
+pub mod row_menu;
+pub mod table;
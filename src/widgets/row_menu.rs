@@ -0,0 +1,43 @@
+//! # Row Menu
+//!
+//! A gear button next to a result table that opens a checkbox per result row: an unchecked
+//! row is hidden from the table. The hidden rows themselves are kept in
+//! [`crate::result_rows`], the menu only shows and toggles them.
+
+use iced::widget::{button, checkbox, Button, Column, Row, Text};
+use iced::Element;
+
+use crate::i18n::{t, Key};
+use crate::result_rows::{ResultRow, RowSet};
+
+/// The gear button and, when `expanded`, the row checkboxes under it. `on_toggle` opens or
+/// closes the menu, `on_row` shows or hides a row.
+pub fn view<Message: Clone + 'static>(
+    hidden: RowSet,
+    expanded: bool,
+    on_toggle: Message,
+    on_row: fn(ResultRow) -> Message,
+) -> Element<'static, Message> {
+    let toggle = Button::new(Text::new(format!("⚙ {}", t(Key::TableRows))).size(14))
+        .on_press(on_toggle)
+        .style(button::text)
+        .padding([2, 0]);
+    let column = Column::new().push(toggle);
+    if !expanded {
+        return column.into();
+    }
+
+    let rows = ResultRow::ALL
+        .iter()
+        .fold(Row::new().spacing(10), |rows, row| {
+            let row = *row;
+            rows.push(
+                checkbox(row.label(), !hidden.contains(row))
+                    .on_toggle(move |_| on_row(row))
+                    .size(15)
+                    .text_size(13),
+            )
+        });
+
+    column.push(rows.wrap()).into()
+}
@@ -0,0 +1,420 @@
+//! # Result Table
+//!
+//! A bordered table laid out as fixed-width and filling `Container`s. The first column holds
+//! the row labels, rows can be grouped into labelled sections (e.g. one per divider leg),
+//! selected and made clickable. A right click on a value cell copies it, see
+//! [`Table::on_copy`].
+//!
+//! ```rust
+//! # use ecw::widgets::table::{Table, TableRow};
+//! # let _: iced::Element<'_, ()> =
+//! Table::new(vec!["".into(), "Voltage".into()])
+//!     .rows(vec![TableRow::new(vec!["Value nom".into(), "12.00V".into()])])
+//!     .view()
+//! # ;
+//! ```
+
+use iced::widget::{container, mouse_area, Column, Container, Row, Text};
+use iced::{Element, Fill, Font, Length, Theme};
+
+use crate::style::{self, TextStyle};
+
+const ROW_HEIGHT: f32 = 30.0;
+const CELL_PADDING: u16 = 5;
+const LABEL_WIDTH: f32 = 110.0;
+const SECTION_WIDTH: f32 = 50.0;
+
+/// Font of the value cells when [`monospace_values`] is on. The renderer resolves it to an
+/// installed monospaced face, falling back to the default font if there is none.
+pub const VALUE_FONT: Font = Font::MONOSPACE;
+
+thread_local! {
+    static MONOSPACE_VALUES: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
+}
+
+/// Sets whether the value cells use [`VALUE_FONT`], so the digits of a column line up.
+pub fn set_monospace_values(on: bool) {
+    MONOSPACE_VALUES.with(|m| m.set(on));
+}
+
+pub fn monospace_values() -> bool {
+    MONOSPACE_VALUES.with(|m| m.get())
+}
+
+/// Font of the value cells, `None` for the default one. Labels and captions keep the default.
+fn value_font() -> Option<Font> {
+    monospace_values().then_some(VALUE_FONT)
+}
+
+/// A text cell with an optional highlight, e.g. [`style::warning_text`]. `exact` is the
+/// full-precision number behind a rounded `text`, if there is one.
+#[derive(Debug, Clone, Default)]
+pub struct Cell {
+    pub text: String,
+    pub exact: Option<String>,
+    pub style: Option<TextStyle>,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            exact: None,
+            style: None,
+        }
+    }
+
+    pub fn exact(mut self, exact: impl Into<String>) -> Self {
+        self.exact = Some(exact.into());
+        self
+    }
+
+    pub fn style(mut self, style: TextStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Both forms of the cell to copy; the exact one is the text if there is no number.
+    pub fn copy(&self) -> CellCopy {
+        CellCopy {
+            display: self.text.clone(),
+            exact: self.exact.clone().unwrap_or_else(|| self.text.clone()),
+        }
+    }
+}
+
+/// A copied cell as displayed, e.g. "1.53mA" for a chat message, and as the exact number,
+/// "0.0015319148936170213" for a spreadsheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellCopy {
+    pub display: String,
+    pub exact: String,
+}
+
+impl CellCopy {
+    pub fn text(&self, exact: bool) -> &str {
+        if exact {
+            &self.exact
+        } else {
+            &self.display
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell::new(text)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Cell::new(text)
+    }
+}
+
+/// A table row: the first cell is the row label, the rest are the values.
+#[derive(Debug, Clone)]
+pub struct TableRow<Message> {
+    cells: Vec<Cell>,
+    on_press: Option<Message>,
+    selected: bool,
+}
+
+impl<Message> TableRow<Message> {
+    pub fn new(cells: Vec<Cell>) -> Self {
+        Self {
+            cells,
+            on_press: None,
+            selected: false,
+        }
+    }
+
+    /// Message emitted when the row is clicked.
+    pub fn on_press(mut self, message: Message) -> Self {
+        self.on_press = Some(message);
+        self
+    }
+
+    /// Highlights the row.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Section<Message> {
+    label: Option<String>,
+    rows: Vec<TableRow<Message>>,
+    // доля нагрузки для фона подписи, см. style::heat_color
+    heat: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Table<Message> {
+    header: Vec<String>,
+    sections: Vec<Section<Message>>,
+    on_copy: Option<fn(CellCopy) -> Message>,
+}
+
+impl<Message: Clone + 'static> Table<Message> {
+    /// Creates a table with the given header captions, the first one is above the row labels.
+    pub fn new(header: Vec<String>) -> Self {
+        Self {
+            header,
+            sections: Vec::new(),
+            on_copy: None,
+        }
+    }
+
+    /// Message emitted on a right click on a value cell; the application copies one of the
+    /// forms, see [`CellCopy::text`].
+    pub fn on_copy(mut self, on_copy: fn(CellCopy) -> Message) -> Self {
+        self.on_copy = Some(on_copy);
+        self
+    }
+
+    /// Appends rows without a section label.
+    pub fn rows(mut self, rows: Vec<TableRow<Message>>) -> Self {
+        self.sections.push(Section {
+            label: None,
+            rows,
+            heat: None,
+        });
+        self
+    }
+
+    /// Appends rows grouped under a section label shown to their left.
+    pub fn section(mut self, label: impl Into<String>, rows: Vec<TableRow<Message>>) -> Self {
+        self.sections.push(Section {
+            label: Some(label.into()),
+            rows,
+            heat: None,
+        });
+        self
+    }
+
+    /// Tints the label of the last section by a load against its rating, see
+    /// [`style::heat_color`].
+    pub fn heat(mut self, ratio: f64) -> Self {
+        if let Some(section) = self.sections.last_mut() {
+            section.heat = Some(ratio);
+        }
+        self
+    }
+
+    /// Width of the header cell above the row labels, it spans the section labels too.
+    fn header_label_width(&self) -> f32 {
+        if self.sections.iter().any(|s| s.label.is_some()) {
+            SECTION_WIDTH + LABEL_WIDTH
+        } else {
+            LABEL_WIDTH
+        }
+    }
+
+    pub fn view(self) -> Element<'static, Message> {
+        let has_sections = self.sections.iter().any(|s| s.label.is_some());
+
+        let mut header = Row::new().height(ROW_HEIGHT).width(Fill);
+        for (id, caption) in self.header.iter().enumerate() {
+            let width = if id == 0 {
+                Length::Fixed(self.header_label_width())
+            } else {
+                Fill
+            };
+            header = header.push(cell(Cell::new(caption.clone()), width, header_style, None));
+        }
+
+        let mut column = Column::new().push(header).width(Fill);
+
+        for section in self.sections {
+            let height = ROW_HEIGHT * section.rows.len() as f32;
+
+            let mut rows = Column::new().width(Fill);
+            for row in section.rows {
+                rows = rows.push(table_row(row, self.on_copy));
+            }
+
+            let heat = section.heat;
+            let content: Element<'static, Message> = match section.label {
+                Some(label) => Row::new()
+                    .push(
+                        Container::new(Text::new(label))
+                            .padding(CELL_PADDING)
+                            .center_y(height)
+                            .width(SECTION_WIDTH)
+                            .style(move |theme| match heat {
+                                Some(ratio) => {
+                                    header_style(theme).background(style::heat_color(ratio, theme))
+                                }
+                                None => header_style(theme),
+                            }),
+                    )
+                    .push(rows)
+                    .into(),
+                None if has_sections => Row::new()
+                    .push(Container::new(Text::new("")).width(SECTION_WIDTH))
+                    .push(rows)
+                    .into(),
+                None => rows.into(),
+            };
+            column = column.push(content);
+        }
+
+        column.padding([5, 0]).into()
+    }
+}
+
+fn table_row<Message: Clone + 'static>(
+    row: TableRow<Message>,
+    on_copy: Option<fn(CellCopy) -> Message>,
+) -> Element<'static, Message> {
+    let selected = row.selected;
+    let font = value_font();
+    let mut line = Row::new().height(ROW_HEIGHT).width(Fill);
+
+    for (id, content) in row.cells.into_iter().enumerate() {
+        let width = if id == 0 {
+            Length::Fixed(LABEL_WIDTH)
+        } else {
+            Fill
+        };
+        let style = if id == 0 {
+            header_style
+        } else if selected {
+            selected_style
+        } else {
+            cell_style
+        };
+        // подпись строки остаётся шрифтом по умолчанию
+        let font = if id == 0 { None } else { font };
+        line = match on_copy {
+            Some(on_copy) if id > 0 => {
+                let copy = on_copy(content.copy());
+                line.push(mouse_area(cell(content, width, style, font)).on_right_press(copy))
+            }
+            _ => line.push(cell(content, width, style, font)),
+        };
+    }
+
+    match row.on_press {
+        Some(message) => mouse_area(line).on_press(message).into(),
+        None => line.into(),
+    }
+}
+
+fn cell<Message: 'static>(
+    content: Cell,
+    width: Length,
+    style: fn(&Theme) -> container::Style,
+    font: Option<Font>,
+) -> Element<'static, Message> {
+    let mut text = Text::new(content.text);
+    if let Some(style) = content.style {
+        text = text.style(style);
+    }
+    if let Some(font) = font {
+        text = text.font(font);
+    }
+
+    Container::new(text)
+        .padding(CELL_PADDING)
+        .width(width)
+        .height(Fill)
+        .style(style)
+        .into()
+}
+
+/// Swaps the rows and the columns of a table given by its rows, header included: the first
+/// column becomes the header. Short rows are padded with empty cells.
+pub fn transpose<T: Clone + Default>(rows: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+
+    (0..width)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row.get(column).cloned().unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
+
+fn cell_style(theme: &Theme) -> container::Style {
+    container::Style::default().border(style::table_rule(theme))
+}
+
+fn header_style(theme: &Theme) -> container::Style {
+    container::Style::default()
+        .border(style::table_rule(theme))
+        .background(theme.extended_palette().background.weak.color)
+}
+
+fn selected_style(theme: &Theme) -> container::Style {
+    container::Style::default()
+        .border(style::table_rule(theme))
+        .background(theme.extended_palette().primary.weak.color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_label_width() {
+        let table: Table<()> = Table::new(vec!["".into(), "Voltage".into()])
+            .rows(vec![TableRow::new(vec!["Value nom".into(), "1V".into()])]);
+        assert_eq!(table.header_label_width(), LABEL_WIDTH);
+
+        let table = table.section("R1", vec![]);
+        assert_eq!(table.header_label_width(), SECTION_WIDTH + LABEL_WIDTH);
+    }
+
+    #[test]
+    fn test_value_font() {
+        assert_eq!(value_font(), Some(VALUE_FONT));
+        assert_eq!(VALUE_FONT.family, iced::font::Family::Monospace);
+
+        set_monospace_values(false);
+        assert_eq!(value_font(), None);
+        set_monospace_values(true);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let rows = vec![
+            vec!["".to_string(), "Voltage".into(), "Current".into()],
+            vec!["Value nom".into(), "1V".into(), "2A".into()],
+            vec!["Value min".into(), "0.9V".into()],
+        ];
+
+        let transposed = transpose(rows);
+        assert_eq!(transposed.len(), 3);
+        assert!(transposed.iter().all(|row| row.len() == 3));
+        assert_eq!(transposed[0], ["", "Value nom", "Value min"]);
+        assert_eq!(transposed[1], ["Voltage", "1V", "0.9V"]);
+        assert_eq!(transposed[2], ["Current", "2A", ""]);
+
+        assert!(transpose(Vec::<Vec<String>>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_cell() {
+        let cell = Cell::from("1.00mA").style(style::warning_text);
+        assert_eq!(cell.text, "1.00mA");
+        assert_eq!(
+            cell.style.map(|style| style(&Theme::Light).color),
+            Some(style::warning_text(&Theme::Light).color)
+        );
+    }
+
+    #[test]
+    fn test_cell_copy() {
+        let cell = Cell::new("1.53mA").exact("0.0015319148936170213");
+        assert_eq!(cell.copy().text(false), "1.53mA");
+        assert_eq!(cell.copy().text(true), "0.0015319148936170213");
+
+        // у подписи нет числа: точная форма совпадает с текстом
+        assert_eq!(Cell::from("Value nom").copy().text(true), "Value nom");
+    }
+}
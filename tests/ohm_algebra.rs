@@ -0,0 +1,206 @@
+//! Every arithmetic operator between the quantities, spot-checked against known values.
+
+use ecw::types::impedance::Impedance;
+use ecw::types::{
+    current::Current, power::Power, ratio::Ratio, resistance::Resistance, voltage::Voltage,
+    Measurement, Tolerance,
+};
+
+fn voltage(value: f64) -> Voltage {
+    Voltage {
+        value,
+        tolerance: None,
+    }
+}
+
+fn current(value: f64) -> Current {
+    Current {
+        value,
+        tolerance: None,
+    }
+}
+
+fn resistance(value: f64) -> Resistance {
+    Resistance {
+        value,
+        tolerance: None,
+    }
+}
+
+fn power(value: f64) -> Power {
+    Power {
+        value,
+        tolerance: None,
+    }
+}
+
+fn assert_value(actual: &impl Measurement, expected: f64) {
+    let value = actual.get_nominal_value();
+    assert!(
+        (value - expected).abs() <= 1e-12 * expected.abs(),
+        "{} != {}",
+        value,
+        expected
+    );
+}
+
+#[test]
+fn voltage_add_sub() {
+    assert_value(&(voltage(10.0) + voltage(2.0)), 12.0);
+    assert_value(&(voltage(10.0) - voltage(2.0)), 8.0);
+}
+
+#[test]
+fn voltage_div_current() {
+    let r: Resistance = voltage(10.0) / current(2.0);
+    assert_value(&r, 5.0);
+}
+
+#[test]
+fn voltage_div_resistance() {
+    let i: Current = voltage(10.0) / resistance(5.0);
+    assert_value(&i, 2.0);
+}
+
+#[test]
+fn voltage_mul_current() {
+    let p: Power = voltage(10.0) * current(2.0);
+    assert_value(&p, 20.0);
+}
+
+#[test]
+fn voltage_mul_ratio() {
+    let ratio = Ratio {
+        value: 0.25,
+        tolerance: None,
+    };
+    let v: Voltage = voltage(12.0) * ratio;
+    assert_value(&v, 3.0);
+}
+
+#[test]
+fn current_mul_resistance() {
+    let v: Voltage = current(2.0) * resistance(5.0);
+    assert_value(&v, 10.0);
+}
+
+#[test]
+fn resistance_mul_current() {
+    let v: Voltage = resistance(5.0) * current(2.0);
+    assert_value(&v, 10.0);
+}
+
+#[test]
+fn resistance_add() {
+    assert_value(&(resistance(1e3) + resistance(2.2e3)), 3.2e3);
+
+    let mut r = resistance(1e3);
+    r += resistance(500.0);
+    assert_value(&r, 1.5e3);
+}
+
+#[test]
+fn resistance_sub() {
+    assert_value(&(resistance(0.5) - resistance(0.02)), 0.48);
+}
+
+#[test]
+fn resistance_mul_f64() {
+    let r = Resistance {
+        value: 1e3,
+        tolerance: Some(Tolerance {
+            plus: 1.0,
+            minus: 1.0,
+        }),
+    };
+
+    let scaled = r * 2.5;
+    assert_value(&scaled, 2.5e3);
+    assert_eq!(scaled.tolerance, r.tolerance);
+}
+
+#[test]
+fn power_add() {
+    assert_value(&(power(20.0) + power(5.0)), 25.0);
+
+    let mut p = power(20.0);
+    p += power(1.0);
+    assert_value(&p, 21.0);
+}
+
+#[test]
+fn power_div_voltage() {
+    let i: Current = power(20.0) / voltage(10.0);
+    assert_value(&i, 2.0);
+}
+
+#[test]
+fn power_div_current() {
+    let v: Voltage = power(20.0) / current(2.0);
+    assert_value(&v, 10.0);
+}
+
+#[test]
+fn impedance_add() {
+    let z = Impedance::new(100.0, 50.0) + Impedance::reactance(-20.0);
+    assert_eq!(z, Impedance::new(100.0, 30.0));
+}
+
+#[test]
+fn neg() {
+    assert_value(&-voltage(10.0), -10.0);
+    assert_value(&-current(2.0), -2.0);
+    assert_value(&-resistance(5.0), -5.0);
+    assert_value(&-power(20.0), -20.0);
+}
+
+#[test]
+fn ohm_law_round_trip() {
+    // U = I·R, I = U/R, P = U·I = U²/R
+    let u = voltage(12.0);
+    let r = resistance(4.0);
+
+    let i = u / r;
+    let p = u * i;
+
+    assert_value(&(i * r), 12.0);
+    assert_value(&(p / u), 3.0);
+    assert_value(&(p / i), 12.0);
+    assert_value(&(u / i), 4.0);
+    assert_value(&p, 36.0);
+}
+
+#[test]
+fn tolerance_of_product() {
+    let u = Voltage {
+        value: 10.0,
+        tolerance: Some(Tolerance {
+            plus: 1.0,
+            minus: 2.0,
+        }),
+    };
+    let i = Current {
+        value: 2.0,
+        tolerance: Some(Tolerance {
+            plus: 3.0,
+            minus: 1.0,
+        }),
+    };
+
+    // worst case: the relative tolerances add up
+    assert_eq!(
+        (u * i).tolerance,
+        Some(Tolerance {
+            plus: 4.0,
+            minus: 3.0
+        })
+    );
+    // the maximum of a quotient takes the minimum of the divisor
+    assert_eq!(
+        (u / i).tolerance,
+        Some(Tolerance {
+            plus: 2.0,
+            minus: 5.0
+        })
+    );
+}
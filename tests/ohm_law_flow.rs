@@ -0,0 +1,39 @@
+//! The Ohm law scene driven end to end by its public messages, the way the interface does.
+//! Needs the `test-internals` feature: `cargo test --features test-internals`.
+
+use ecw::ohm_law::{FieldId, Message, OhmLaw};
+use ecw::status::SceneStatus;
+use ecw::types::Measurement;
+
+fn input(ohm_law: &mut OhmLaw, message: fn(String) -> Message, text: &str) {
+    ohm_law.update(message(text.to_string()));
+}
+
+#[test]
+fn test_ohm_law_flow() {
+    let mut ohm_law = OhmLaw::default();
+    assert_eq!(ohm_law.status(), SceneStatus::Empty);
+
+    input(&mut ohm_law, Message::InputVoltageChanged, "12 5%");
+    assert_eq!(ohm_law.status(), SceneStatus::Incomplete);
+    assert!(ohm_law.data().current.is_err());
+
+    input(&mut ohm_law, Message::InputResistanceChanged, "1k 1%");
+    assert_eq!(ohm_law.status(), SceneStatus::Solved);
+
+    let data = ohm_law.data();
+    let current = data.current.as_ref().unwrap();
+    assert_eq!(current.get_value_nom(), "12.00mA");
+    assert_eq!(current.get_tol_percent_plus(), "6.00%");
+    assert_eq!(data.power.as_ref().unwrap().get_value_nom(), "144.00mW");
+
+    // ошибка ввода видна в статусе
+    input(&mut ohm_law, Message::InputResistanceChanged, "1x");
+    assert_eq!(ohm_law.status(), SceneStatus::Error);
+    assert!(ohm_law.data().resistance.is_err());
+
+    ohm_law.update(Message::ClearField(FieldId::Resistance));
+    ohm_law.update(Message::ClearField(FieldId::Voltage));
+    assert_eq!(ohm_law.status(), SceneStatus::Empty);
+    assert!(!ohm_law.is_dirty());
+}